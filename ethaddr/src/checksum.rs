@@ -72,7 +72,7 @@ pub const fn const_verify(bytes: &[u8; 20], checksum: &str) -> bool {
 }
 
 /// Perform Keccak-256 hash over some input bytes.
-fn keccak256(bytes: &[u8]) -> [u8; 32] {
+pub(crate) fn keccak256(bytes: &[u8]) -> [u8; 32] {
     #[cfg(feature = "sha3")]
     {
         let mut hasher = sha3::Keccak256::default();
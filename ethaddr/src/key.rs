@@ -0,0 +1,62 @@
+//! Address derivation from secp256k1 keys.
+
+use crate::{checksum, Address};
+use k256::ecdsa::{SigningKey, VerifyingKey};
+
+impl Address {
+    /// Returns the address of a secp256k1 public key. This is the last 20
+    /// bytes of the Keccak-256 digest of its uncompressed encoding.
+    pub fn from_public_key(key: &VerifyingKey) -> Self {
+        let point = key.to_encoded_point(false);
+        let digest = checksum::keccak256(&point.as_bytes()[1..]);
+        Self::from_slice(&digest[12..])
+    }
+
+    /// Returns a deterministic address for a label. The private key for the
+    /// address is the Keccak-256 digest of the label, matching the addresses
+    /// created by Foundry's `makeAddr` cheatcode.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the digest of the label is not a valid secp256k1
+    /// private key. This happens with a probability lower than 1 in 2^127.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::{address, Address};
+    /// assert_eq!(
+    ///     Address::from_label("alice"),
+    ///     address!("0x328809Bc894f92807417D2dAD6b7C998c1aFdac6"),
+    /// );
+    /// ```
+    pub fn from_label(label: &str) -> Self {
+        let key = SigningKey::from_bytes(&checksum::keccak256(label.as_bytes()).into())
+            .expect("label digest is not a valid private key");
+        Self::from_public_key(key.verifying_key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address;
+
+    #[test]
+    fn foundry_labels() {
+        for (label, address) in [
+            (
+                "alice",
+                address!("0x328809Bc894f92807417D2dAD6b7C998c1aFdac6"),
+            ),
+            (
+                "bob",
+                address!("0x1D96F2f6BeF1202E4Ce1Ff6Dad0c2CB002861d3e"),
+            ),
+        ] {
+            assert_eq!(Address::from_label(label), address);
+        }
+    }
+}
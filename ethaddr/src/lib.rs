@@ -20,6 +20,8 @@
 //! - **_default_ `std`**: Additional integration with Rust standard library
 //!   types. Notably, this includes [`std::error::Error`] implementation on the
 //!   [`ParseAddressError`] type and conversions from [`Vec<u8>`].
+//! - **`k256`**: Address derivation from secp256k1 keys using the [`k256`]
+//!   crate, including Foundry-style [`Address::from_label()`] test addresses.
 //! - **`serde`**: Serialization traits for the [`serde`] crate. Note that the
 //!   implementation is very much geared towards JSON serialization with
 //!   [`serde_json`].
@@ -29,6 +31,7 @@
 //!   verification, as [`sha3`] does not expose a `const fn` API.
 //!
 //! [ERC-55]: https://eips.ethereum.org/EIPS/eip-55
+//! [`k256`]: https://crates.io/crates/k256
 //! [`serde`]: https://crates.io/crates/serde
 //! [`serde_json`]: https://crates.io/crates/serde_json
 //! [`sha3`]: https://crates.io/crates/sha3
@@ -38,6 +41,8 @@
 mod checksum;
 mod hex;
 mod keccak;
+#[cfg(feature = "k256")]
+mod key;
 #[cfg(feature = "serde")]
 mod serde;
 
@@ -0,0 +1,110 @@
+//! Derive macros for [`ethprim`](https://docs.rs/ethprim) traits.
+//!
+//! These macros are re-exported by `ethprim` when its `derive` feature is
+//! enabled, and generate code that refers to items through the `::ethprim`
+//! path.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Generics,
+    Index, Member,
+};
+
+/// Derives `ethprim::rlp::Encodable` for a struct, encoding its fields in
+/// declaration order as an RLP list.
+#[proc_macro_derive(RlpEncodable)]
+pub fn derive_rlp_encodable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_encodable(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derives `ethprim::rlp::Decodable` for a struct, decoding its fields in
+/// declaration order from an RLP list.
+#[proc_macro_derive(RlpDecodable)]
+pub fn derive_rlp_decodable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_decodable(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_encodable(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let members = struct_members(&input)?;
+    let name = &input.ident;
+    let generics = add_bounds(input.generics, parse_quote!(::ethprim::rlp::Encodable));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ethprim::rlp::Encodable for #name #ty_generics #where_clause {
+            fn encode(&self, out: &mut dyn ::ethprim::rlp::Output) {
+                let payload_length = 0 #(+ ::ethprim::rlp::Encodable::length(&self.#members))*;
+                ::ethprim::rlp::Header::list(payload_length).encode(out);
+                #(::ethprim::rlp::Encodable::encode(&self.#members, out);)*
+            }
+
+            fn length(&self) -> usize {
+                let payload_length = 0 #(+ ::ethprim::rlp::Encodable::length(&self.#members))*;
+                ::ethprim::rlp::Header::list(payload_length).length_with_payload()
+            }
+        }
+    })
+}
+
+fn expand_decodable(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let members = struct_members(&input)?;
+    let name = &input.ident;
+    let generics = add_bounds(input.generics, parse_quote!(::ethprim::rlp::Decodable));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ethprim::rlp::Decodable for #name #ty_generics #where_clause {
+            fn decode(
+                buf: &mut &[u8],
+            ) -> ::core::result::Result<Self, ::ethprim::rlp::DecodeError> {
+                let mut payload = ::ethprim::rlp::Header::decode_list(buf)?;
+                let value = Self {
+                    #(#members: ::ethprim::rlp::Decodable::decode(&mut payload)?,)*
+                };
+                if !payload.is_empty() {
+                    return ::core::result::Result::Err(
+                        ::ethprim::rlp::DecodeError::ListLengthMismatch,
+                    );
+                }
+                ::core::result::Result::Ok(value)
+            }
+        }
+    })
+}
+
+fn struct_members(input: &DeriveInput) -> syn::Result<Vec<Member>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "RLP derive macros only support structs",
+        ));
+    };
+    Ok(match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| Member::Named(field.ident.clone().unwrap()))
+            .collect(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len())
+            .map(|i| Member::Unnamed(Index::from(i)))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    })
+}
+
+fn add_bounds(mut generics: Generics, bound: syn::TypeParamBound) -> Generics {
+    for param in &mut generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(bound.clone());
+        }
+    }
+    generics
+}
@@ -8,15 +8,31 @@ use core::{
 
 /// Decode a hex string into a byte array.
 pub fn decode<const N: usize>(s: &str) -> Result<[u8; N], ParseHexError> {
-    let (s, ch_offset) = match s.strip_prefix("0x") {
-        Some(s) => (s, 2),
-        None => (s, 0),
-    };
+    let (s, ch_offset) = split_prefix(s);
     if s.len() != N * 2 {
         return Err(ParseHexError::InvalidLength);
     }
 
     let mut bytes = [MaybeUninit::<u8>::uninit(); N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        byte.write(decode_byte(s, ch_offset, i)?);
+    }
+
+    let bytes = unsafe { (&bytes as *const _ as *const [u8; N]).read() };
+    Ok(bytes)
+}
+
+/// Splits the optional `0x` prefix from a hex string, returning the remaining
+/// digits and the offset of the first digit.
+pub fn split_prefix(s: &str) -> (&str, usize) {
+    match s.strip_prefix("0x") {
+        Some(s) => (s, 2),
+        None => (s, 0),
+    }
+}
+
+/// Decodes the `i`-th byte of a hex string without prefix.
+pub fn decode_byte(s: &str, ch_offset: usize, i: usize) -> Result<u8, ParseHexError> {
     let nibble = |c| match c {
         b'0'..=b'9' => Some(c - b'0'),
         b'A'..=b'F' => Some(c - b'A' + 0xa),
@@ -28,14 +44,10 @@ pub fn decode<const N: usize>(s: &str) -> Result<[u8; N], ParseHexError> {
         index: i + ch_offset,
     };
 
-    for (i, ch) in s.as_bytes().chunks(2).enumerate() {
-        let hi = nibble(ch[0]).ok_or_else(|| invalid_char(i * 2))?;
-        let lo = nibble(ch[1]).ok_or_else(|| invalid_char(i * 2 + 1))?;
-        bytes[i].write((hi << 4) + lo);
-    }
-
-    let bytes = unsafe { (&bytes as *const _ as *const [u8; N]).read() };
-    Ok(bytes)
+    let ch = &s.as_bytes()[i * 2..i * 2 + 2];
+    let hi = nibble(ch[0]).ok_or_else(|| invalid_char(i * 2))?;
+    let lo = nibble(ch[1]).ok_or_else(|| invalid_char(i * 2 + 1))?;
+    Ok((hi << 4) + lo)
 }
 
 /// Decode a hex string into a byte array as a `const fn`.
@@ -168,7 +180,7 @@ pub enum Alphabet {
 
 impl Alphabet {
     /// Returns the nibble lookup-table for the alphabet.
-    const fn lut(&self) -> &'static [u8; 16] {
+    pub const fn lut(&self) -> &'static [u8; 16] {
         match self {
             Alphabet::Lower => b"0123456789abcdef",
             Alphabet::Upper => b"0123456789ABCDEF",
@@ -0,0 +1,484 @@
+//! Solidity ABI encoding and decoding.
+
+use super::{ParamType, Value};
+use crate::{Address, Digest, I256, U256};
+use std::{
+    fmt::{self, Display, Formatter},
+    iter,
+};
+
+/// ABI encodes a tuple of values.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::abi::{self, ParamType, Value};
+/// # use ethprim::U256;
+/// let values = [Value::Uint(U256::new(42)), Value::String("hello".to_owned())];
+/// let data = abi::encode(&values);
+/// assert_eq!(data.len(), 128);
+/// assert_eq!(
+///     abi::decode(&[ParamType::Uint(256), ParamType::String], &data).unwrap(),
+///     values,
+/// );
+/// ```
+pub fn encode(values: &[Value]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    encode_tuple(values, &mut buffer);
+    buffer
+}
+
+/// ABI decodes a tuple of values with the specified types.
+pub fn decode(kinds: &[ParamType], data: &[u8]) -> Result<Vec<Value>, DecodeError> {
+    decode_tuple(kinds.iter(), data)
+}
+
+/// Encodes a value as an event topic. Elementary value types are encoded as
+/// 32-byte words, while all other types are hashed.
+pub(crate) fn encode_topic(value: &Value) -> Digest {
+    match value {
+        Value::Bytes(bytes) => Digest::of(bytes),
+        Value::String(string) => Digest::of(string),
+        Value::Array(values) | Value::FixedArray(values) | Value::Tuple(values) => {
+            let mut buffer = Vec::new();
+            for value in values {
+                encode_topic_preimage(value, &mut buffer);
+            }
+            Digest::of(buffer)
+        }
+        _ => {
+            let mut buffer = Vec::with_capacity(32);
+            encode_value(value, &mut buffer);
+            Digest::from_slice(&buffer)
+        }
+    }
+}
+
+/// Returns `true` if a parameter type is encoded in place as an event topic.
+pub(crate) fn is_topic_value_type(kind: &ParamType) -> bool {
+    !matches!(
+        kind,
+        ParamType::Bytes
+            | ParamType::String
+            | ParamType::Array(_)
+            | ParamType::FixedArray(..)
+            | ParamType::Tuple(_)
+    )
+}
+
+fn encode_topic_preimage(value: &Value, buffer: &mut Vec<u8>) {
+    match value {
+        Value::Bytes(bytes) => encode_padded(bytes, buffer),
+        Value::String(string) => encode_padded(string.as_bytes(), buffer),
+        Value::Array(values) | Value::FixedArray(values) | Value::Tuple(values) => {
+            for value in values {
+                encode_topic_preimage(value, buffer);
+            }
+        }
+        _ => encode_value(value, buffer),
+    }
+}
+
+/// Returns the size of the head of an encoded value.
+fn head_size(value: &Value) -> usize {
+    match value {
+        _ if value.is_dynamic() => 32,
+        Value::FixedArray(values) | Value::Tuple(values) => values.iter().map(head_size).sum(),
+        _ => 32,
+    }
+}
+
+fn encode_tuple(values: &[Value], buffer: &mut Vec<u8>) {
+    let head = values.iter().map(head_size).sum::<usize>();
+    let mut tail = Vec::new();
+    for value in values {
+        if value.is_dynamic() {
+            encode_len(head + tail.len(), buffer);
+            encode_value(value, &mut tail);
+        } else {
+            encode_value(value, buffer);
+        }
+    }
+    buffer.extend_from_slice(&tail);
+}
+
+fn encode_value(value: &Value, buffer: &mut Vec<u8>) {
+    match value {
+        Value::Address(address) => encode_word_left(address.as_slice(), buffer),
+        Value::Bool(value) => encode_word_left(&[*value as u8], buffer),
+        Value::Int(value) => encode_word(&value.to_be_bytes(), buffer),
+        Value::Uint(value) => encode_word(&value.to_be_bytes(), buffer),
+        Value::FixedBytes(bytes) => encode_padded(bytes, buffer),
+        Value::Function(address, selector) => {
+            let mut word = [0; 24];
+            word[..20].copy_from_slice(address.as_slice());
+            word[20..].copy_from_slice(selector);
+            encode_padded(&word, buffer);
+        }
+        Value::Bytes(bytes) => {
+            encode_len(bytes.len(), buffer);
+            encode_padded(bytes, buffer);
+        }
+        Value::String(string) => {
+            encode_len(string.len(), buffer);
+            encode_padded(string.as_bytes(), buffer);
+        }
+        Value::Array(values) => {
+            encode_len(values.len(), buffer);
+            encode_tuple(values, buffer);
+        }
+        Value::FixedArray(values) | Value::Tuple(values) => encode_tuple(values, buffer),
+    }
+}
+
+fn encode_len(len: usize, buffer: &mut Vec<u8>) {
+    encode_word_left(&(len as u64).to_be_bytes(), buffer);
+}
+
+fn encode_word(word: &[u8; 32], buffer: &mut Vec<u8>) {
+    buffer.extend_from_slice(word);
+}
+
+/// Encodes bytes left-padded to a 32-byte word.
+fn encode_word_left(bytes: &[u8], buffer: &mut Vec<u8>) {
+    buffer.extend(iter::repeat_n(0, 32 - bytes.len()));
+    buffer.extend_from_slice(bytes);
+}
+
+/// Encodes bytes right-padded to a multiple of 32 bytes.
+fn encode_padded(bytes: &[u8], buffer: &mut Vec<u8>) {
+    buffer.extend_from_slice(bytes);
+    buffer.extend(iter::repeat_n(
+        0,
+        bytes.len().next_multiple_of(32) - bytes.len(),
+    ));
+}
+
+/// Returns the size of the head of an encoded parameter type.
+fn kind_head_size(kind: &ParamType) -> usize {
+    match kind {
+        _ if kind.is_dynamic() => 32,
+        ParamType::FixedArray(kind, len) => kind_head_size(kind).saturating_mul(*len),
+        ParamType::Tuple(kinds) => kinds.iter().map(kind_head_size).sum(),
+        _ => 32,
+    }
+}
+
+fn decode_tuple<'a>(
+    kinds: impl Iterator<Item = &'a ParamType>,
+    data: &[u8],
+) -> Result<Vec<Value>, DecodeError> {
+    let mut head = 0;
+    kinds
+        .map(|kind| {
+            let value = if kind.is_dynamic() {
+                let offset = decode_len(data, head)?;
+                decode_value(kind, data.get(offset..).ok_or(DecodeError::InvalidOffset)?)?
+            } else {
+                decode_value(kind, data.get(head..).ok_or(DecodeError::UnexpectedEof)?)?
+            };
+            head += kind_head_size(kind);
+            Ok(value)
+        })
+        .collect()
+}
+
+fn decode_value(kind: &ParamType, data: &[u8]) -> Result<Value, DecodeError> {
+    let value = match kind {
+        ParamType::Address => {
+            let word = decode_word(data, 0)?;
+            check_padding(&word[..12])?;
+            Value::Address(Address::from_slice(&word[12..]))
+        }
+        ParamType::Bool => {
+            let word = decode_word(data, 0)?;
+            check_padding(&word[..31])?;
+            match word[31] {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                _ => return Err(DecodeError::InvalidValue),
+            }
+        }
+        ParamType::Int(_) => Value::Int(I256::from_be_bytes(decode_word(data, 0)?)),
+        ParamType::Uint(_) => Value::Uint(U256::from_be_bytes(decode_word(data, 0)?)),
+        ParamType::FixedBytes(size) => {
+            let word = decode_word(data, 0)?;
+            check_padding(&word[*size..])?;
+            Value::FixedBytes(word[..*size].to_vec())
+        }
+        ParamType::Function => {
+            let word = decode_word(data, 0)?;
+            check_padding(&word[24..])?;
+            Value::Function(
+                Address::from_slice(&word[..20]),
+                word[20..24].try_into().unwrap(),
+            )
+        }
+        ParamType::Bytes => Value::Bytes(decode_bytes(data)?.to_vec()),
+        ParamType::String => Value::String(
+            String::from_utf8(decode_bytes(data)?.to_vec())
+                .map_err(|_| DecodeError::InvalidUtf8)?,
+        ),
+        ParamType::Array(kind) => {
+            let len = decode_len(data, 0)?;
+            let data = &data[32..];
+            // Guard against huge lengths causing large allocations. Each element
+            // is required to take at least one word, including zero-sized ones
+            // such as `()` and `T[0]`, so the length is bounded by the data.
+            if len.saturating_mul(kind_head_size(kind).max(32)) > data.len() {
+                return Err(DecodeError::UnexpectedEof);
+            }
+            Value::Array(decode_tuple(iter::repeat_n(&**kind, len), data)?)
+        }
+        ParamType::FixedArray(kind, len) => {
+            if len.saturating_mul(kind_head_size(kind).max(32)) > data.len() {
+                return Err(DecodeError::UnexpectedEof);
+            }
+            Value::FixedArray(decode_tuple(iter::repeat_n(&**kind, *len), data)?)
+        }
+        ParamType::Tuple(kinds) => Value::Tuple(decode_tuple(kinds.iter(), data)?),
+    };
+
+    // Integers need to additionally be verified to be in range for their
+    // specific bit size.
+    if matches!(kind, ParamType::Int(_) | ParamType::Uint(_)) && !value.type_check(kind) {
+        return Err(DecodeError::InvalidValue);
+    }
+    Ok(value)
+}
+
+fn decode_word(data: &[u8], offset: usize) -> Result<[u8; 32], DecodeError> {
+    data.get(offset..)
+        .and_then(|data| data.get(..32))
+        .map(|word| word.try_into().unwrap())
+        .ok_or(DecodeError::UnexpectedEof)
+}
+
+fn decode_len(data: &[u8], offset: usize) -> Result<usize, DecodeError> {
+    let word = decode_word(data, offset)?;
+    check_padding(&word[..24]).map_err(|_| DecodeError::InvalidOffset)?;
+    usize::try_from(u64::from_be_bytes(word[24..].try_into().unwrap()))
+        .map_err(|_| DecodeError::InvalidOffset)
+}
+
+fn decode_bytes(data: &[u8]) -> Result<&[u8], DecodeError> {
+    let len = decode_len(data, 0)?;
+    data[32..].get(..len).ok_or(DecodeError::UnexpectedEof)
+}
+
+fn check_padding(padding: &[u8]) -> Result<(), DecodeError> {
+    if padding.iter().any(|b| *b != 0) {
+        return Err(DecodeError::InvalidValue);
+    }
+    Ok(())
+}
+
+/// Represents an error ABI encoding values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EncodeError {
+    /// The number of values does not match the number of parameters.
+    InvalidLength,
+    /// A value does not match its parameter type.
+    TypeMismatch,
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "incorrect number of values for parameters"),
+            Self::TypeMismatch => write!(f, "value does not match parameter type"),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Represents an error ABI decoding data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The data ended unexpectedly.
+    UnexpectedEof,
+    /// An offset or length is out of range.
+    InvalidOffset,
+    /// A value is incorrectly padded or out of range for its type.
+    InvalidValue,
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+    /// The log topics do not match the event.
+    TopicMismatch,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => write!(f, "unexpected end of ABI encoded data"),
+            Self::InvalidOffset => write!(f, "invalid offset in ABI encoded data"),
+            Self::InvalidValue => write!(f, "invalid value in ABI encoded data"),
+            Self::InvalidUtf8 => write!(f, "invalid UTF-8 string in ABI encoded data"),
+            Self::TopicMismatch => write!(f, "log topics do not match event"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, int, tests::hex, uint};
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        for (kinds, values, data) in [
+            (
+                "(address,bool,int8)",
+                vec![
+                    Value::Address(address!("0x1111111111111111111111111111111111111111")),
+                    Value::Bool(true),
+                    Value::Int(int!("-1")),
+                ],
+                "0000000000000000000000001111111111111111111111111111111111111111
+                 0000000000000000000000000000000000000000000000000000000000000001
+                 ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+            (
+                "(uint256,uint32[],bytes10,bytes)",
+                vec![
+                    Value::Uint(uint!("0x123")),
+                    Value::Array(vec![
+                        Value::Uint(uint!("0x456")),
+                        Value::Uint(uint!("0x789")),
+                    ]),
+                    Value::FixedBytes(b"1234567890".to_vec()),
+                    Value::Bytes(b"Hello, world!".to_vec()),
+                ],
+                "0000000000000000000000000000000000000000000000000000000000000123
+                 0000000000000000000000000000000000000000000000000000000000000080
+                 3132333435363738393000000000000000000000000000000000000000000000
+                 00000000000000000000000000000000000000000000000000000000000000e0
+                 0000000000000000000000000000000000000000000000000000000000000002
+                 0000000000000000000000000000000000000000000000000000000000000456
+                 0000000000000000000000000000000000000000000000000000000000000789
+                 000000000000000000000000000000000000000000000000000000000000000d
+                 48656c6c6f2c20776f726c642100000000000000000000000000000000000000",
+            ),
+            (
+                "(uint256[][],string[])",
+                vec![
+                    Value::Array(vec![
+                        Value::Array(vec![Value::Uint(uint!("1")), Value::Uint(uint!("2"))]),
+                        Value::Array(vec![Value::Uint(uint!("3"))]),
+                    ]),
+                    Value::Array(vec![
+                        Value::String("one".to_owned()),
+                        Value::String("two".to_owned()),
+                    ]),
+                ],
+                "0000000000000000000000000000000000000000000000000000000000000040
+                 0000000000000000000000000000000000000000000000000000000000000140
+                 0000000000000000000000000000000000000000000000000000000000000002
+                 0000000000000000000000000000000000000000000000000000000000000040
+                 00000000000000000000000000000000000000000000000000000000000000a0
+                 0000000000000000000000000000000000000000000000000000000000000002
+                 0000000000000000000000000000000000000000000000000000000000000001
+                 0000000000000000000000000000000000000000000000000000000000000002
+                 0000000000000000000000000000000000000000000000000000000000000001
+                 0000000000000000000000000000000000000000000000000000000000000003
+                 0000000000000000000000000000000000000000000000000000000000000002
+                 0000000000000000000000000000000000000000000000000000000000000040
+                 0000000000000000000000000000000000000000000000000000000000000080
+                 0000000000000000000000000000000000000000000000000000000000000003
+                 6f6e650000000000000000000000000000000000000000000000000000000000
+                 0000000000000000000000000000000000000000000000000000000000000003
+                 74776f0000000000000000000000000000000000000000000000000000000000",
+            ),
+        ] {
+            let ParamType::Tuple(kinds) = kinds.parse().unwrap() else {
+                unreachable!()
+            };
+            let data = hex(data);
+            assert_eq!(encode(&values), data);
+            assert_eq!(decode(&kinds, &data).unwrap(), values);
+        }
+    }
+
+    #[test]
+    fn decode_rejects_invalid_data() {
+        for (kind, data, err) in [
+            ("address", "01", DecodeError::UnexpectedEof),
+            (
+                "address",
+                "0100000000000000000000000000000000000000000000000000000000000000",
+                DecodeError::InvalidValue,
+            ),
+            (
+                "bool",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                DecodeError::InvalidValue,
+            ),
+            (
+                "uint8",
+                "0000000000000000000000000000000000000000000000000000000000000100",
+                DecodeError::InvalidValue,
+            ),
+            (
+                "int8",
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+                DecodeError::InvalidValue,
+            ),
+            (
+                "bytes1",
+                "0101000000000000000000000000000000000000000000000000000000000000",
+                DecodeError::InvalidValue,
+            ),
+            (
+                "bytes",
+                "0000000000000000000000000000000000000000000000000000000000000040",
+                DecodeError::InvalidOffset,
+            ),
+            (
+                "uint256[]",
+                "0000000000000000000000000000000000000000000000000000000000000020
+                 00000000000000000000000000000000000000000000000000000000ffffffff",
+                DecodeError::UnexpectedEof,
+            ),
+            (
+                "string",
+                "0000000000000000000000000000000000000000000000000000000000000020
+                 0000000000000000000000000000000000000000000000000000000000000001
+                 ff00000000000000000000000000000000000000000000000000000000000000",
+                DecodeError::InvalidUtf8,
+            ),
+        ] {
+            assert_eq!(
+                decode(&[kind.parse().unwrap()], &hex(data)).unwrap_err(),
+                err,
+                "{kind}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_rejects_huge_arrays_of_zero_sized_types() {
+        let data = hex(
+            "0000000000000000000000000000000000000000000000000000000000000020
+             00000000000000000000000000000000000000000000000000000000ffffffff",
+        );
+        for kind in [
+            // `()[]`
+            ParamType::Array(Box::new(ParamType::Tuple(vec![]))),
+            // `uint256[0][]`
+            ParamType::Array(Box::new(ParamType::FixedArray(
+                Box::new(ParamType::Uint(256)),
+                0,
+            ))),
+        ] {
+            assert_eq!(
+                decode(&[kind], &data).unwrap_err(),
+                DecodeError::UnexpectedEof
+            );
+        }
+    }
+}
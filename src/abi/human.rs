@@ -0,0 +1,385 @@
+//! Parsing of human-readable ABI fragments.
+//!
+//! This implements the human-readable ABI format popularized by `ethers`,
+//! where ABI items are specified with Solidity-like declarations such as
+//! `function balanceOf(address owner) view returns (uint256)`.
+
+use super::{
+    param::split_components, Abi, Constructor, Error, Event, EventParam, Function, Param,
+    ParamType, ParseAbiError, StateMutability,
+};
+use std::str::FromStr;
+
+/// A parsed human-readable ABI fragment.
+enum Fragment {
+    Function(Function),
+    Event(Event),
+    Error(Error),
+    Constructor(Constructor),
+    Fallback(StateMutability),
+    Receive,
+}
+
+impl Fragment {
+    /// Parses a human-readable ABI fragment.
+    fn parse(s: &str) -> Result<Self, ParseAbiError> {
+        let invalid = || ParseAbiError::InvalidFragment(s.to_owned());
+        let fragment = s.trim().trim_end_matches(';').trim_end();
+
+        let (keyword, rest) = match fragment.find(|c: char| c == '(' || c.is_whitespace()) {
+            Some(i) => fragment.split_at(i),
+            None => return Err(invalid()),
+        };
+        let (keyword, rest) = match keyword {
+            "function" | "event" | "error" => (keyword, rest.trim_start()),
+            "constructor" | "fallback" | "receive" => (keyword, rest),
+            _ => ("function", fragment),
+        };
+
+        let (name, rest) = rest.split_at(rest.find('(').ok_or_else(invalid)?);
+        let name = name.trim();
+        let (inputs, rest) = parenthesized(rest).ok_or_else(invalid)?;
+
+        let mut rest = rest.trim_start();
+        let mut state_mutability = StateMutability::NonPayable;
+        let mut anonymous = false;
+        let mut outputs = None;
+        while !rest.is_empty() {
+            if let Some(returns) = rest.strip_prefix("returns") {
+                let returns = returns.trim_start();
+                if keyword != "function" || outputs.is_some() || !returns.starts_with('(') {
+                    return Err(invalid());
+                }
+                let (params, remaining) = parenthesized(returns).ok_or_else(invalid)?;
+                outputs = Some(params);
+                rest = remaining.trim_start();
+                continue;
+            }
+
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (modifier, remaining) = rest.split_at(end);
+            match modifier {
+                "pure" => state_mutability = StateMutability::Pure,
+                "view" | "constant" => state_mutability = StateMutability::View,
+                "payable" => state_mutability = StateMutability::Payable,
+                "nonpayable" => state_mutability = StateMutability::NonPayable,
+                "external" | "public" | "virtual" | "override" => {}
+                "anonymous" if keyword == "event" => anonymous = true,
+                _ => return Err(invalid()),
+            }
+            rest = remaining.trim_start();
+        }
+
+        let has_name = matches!(keyword, "function" | "event" | "error");
+        if has_name != is_identifier(name) {
+            return Err(invalid());
+        }
+        if keyword != "function" && keyword != "constructor" {
+            let payable = state_mutability == StateMutability::Payable;
+            let allowed = match keyword {
+                "fallback" => state_mutability == StateMutability::NonPayable || payable,
+                "receive" => payable,
+                _ => state_mutability == StateMutability::NonPayable,
+            };
+            if !allowed {
+                return Err(invalid());
+            }
+        }
+
+        let params = |params: &str| -> Result<Vec<Param>, ParseAbiError> {
+            parse_params(params)?
+                .into_iter()
+                .map(|(name, kind, indexed)| {
+                    if indexed {
+                        return Err(invalid());
+                    }
+                    Ok(Param::new(name, kind))
+                })
+                .collect()
+        };
+
+        Ok(match keyword {
+            "function" => Self::Function(Function {
+                name: name.to_owned(),
+                inputs: params(inputs)?,
+                outputs: params(outputs.unwrap_or_default())?,
+                state_mutability,
+            }),
+            "event" => Self::Event(Event {
+                name: name.to_owned(),
+                inputs: parse_params(inputs)?
+                    .into_iter()
+                    .map(|(name, kind, indexed)| EventParam::new(name, kind, indexed))
+                    .collect(),
+                anonymous,
+            }),
+            "error" => Self::Error(Error {
+                name: name.to_owned(),
+                inputs: params(inputs)?,
+            }),
+            "constructor" => Self::Constructor(Constructor {
+                inputs: params(inputs)?,
+                state_mutability,
+            }),
+            "fallback" if inputs.trim().is_empty() => Self::Fallback(state_mutability),
+            "receive" if inputs.trim().is_empty() => Self::Receive,
+            _ => return Err(invalid()),
+        })
+    }
+}
+
+impl Abi {
+    /// Parses an ABI from human-readable fragments.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::abi::{Abi, ParamType};
+    /// let abi = Abi::from_fragments([
+    ///     "function balanceOf(address owner) view returns (uint256)",
+    ///     "event Transfer(address indexed from, address indexed to, uint256 value)",
+    ///     "error Unauthorized(address)",
+    /// ])
+    /// .unwrap();
+    ///
+    /// let balance_of = abi.function("balanceOf", &[ParamType::Address]).unwrap();
+    /// assert_eq!(balance_of.selector(), [0x70, 0xa0, 0x82, 0x31]);
+    /// ```
+    pub fn from_fragments<I>(fragments: I) -> Result<Self, ParseAbiError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut abi = Abi::default();
+        for fragment in fragments {
+            let fragment = fragment.as_ref();
+            match Fragment::parse(fragment)? {
+                Fragment::Function(function) => abi.functions.push(function),
+                Fragment::Event(event) => abi.events.push(event),
+                Fragment::Error(error) => abi.errors.push(error),
+                Fragment::Constructor(constructor) => {
+                    if abi.constructor.is_some() {
+                        return Err(ParseAbiError::InvalidFragment(fragment.to_owned()));
+                    }
+                    abi.constructor = Some(constructor);
+                }
+                Fragment::Fallback(state_mutability) => abi.fallback = Some(state_mutability),
+                Fragment::Receive => abi.receive = true,
+            }
+        }
+        Ok(abi)
+    }
+}
+
+macro_rules! impl_from_str {
+    ($($t:ident),*) => {$(
+        impl FromStr for $t {
+            type Err = ParseAbiError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match Fragment::parse(s)? {
+                    Fragment::$t(value) => Ok(value),
+                    _ => Err(ParseAbiError::InvalidFragment(s.to_owned())),
+                }
+            }
+        }
+    )*};
+}
+
+impl_from_str!(Function, Event, Error, Constructor);
+
+/// Splits a string starting with an opening parenthesis into the contents of
+/// the parenthesis and the remaining string.
+fn parenthesized(s: &str) -> Option<(&str, &str)> {
+    let s = s.strip_prefix('(')?;
+    let mut depth = 0_usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some((&s[..i], &s[i + 1..])),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses a comma separated parameter list, returning the name, type and
+/// whether or not it is indexed for each parameter.
+fn parse_params(s: &str) -> Result<Vec<(String, ParamType, bool)>, ParseAbiError> {
+    split_components(s)
+        .ok_or_else(|| ParseAbiError::InvalidFragment(s.to_owned()))?
+        .into_iter()
+        .map(parse_param)
+        .collect()
+}
+
+/// Parses a single parameter in the form `type [indexed] [location] [name]`.
+fn parse_param(s: &str) -> Result<(String, ParamType, bool), ParseAbiError> {
+    let invalid = || ParseAbiError::InvalidFragment(s.to_owned());
+    let param = s.trim();
+
+    let (kind, rest) = match param.strip_prefix("tuple").unwrap_or(param) {
+        tuple if tuple.starts_with('(') => {
+            let (components, rest) = parenthesized(tuple).ok_or_else(invalid)?;
+            let components = parse_params(components)?
+                .into_iter()
+                .map(|(_, kind, indexed)| (!indexed).then_some(kind).ok_or_else(invalid))
+                .collect::<Result<_, _>>()?;
+            let end = rest
+                .find(|c: char| c != '[' && c != ']' && !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let (suffix, rest) = rest.split_at(end);
+            let kind = format!("{}{suffix}", ParamType::Tuple(components)).parse()?;
+            (kind, rest)
+        }
+        _ => {
+            let end = param.find(char::is_whitespace).unwrap_or(param.len());
+            let (kind, rest) = param.split_at(end);
+            (kind.parse()?, rest)
+        }
+    };
+
+    let mut indexed = false;
+    let mut name = None;
+    for token in rest.split_whitespace() {
+        match token {
+            "indexed" if !indexed && name.is_none() => indexed = true,
+            "memory" | "calldata" | "storage" if name.is_none() => {}
+            _ if name.is_none() && is_identifier(token) => name = Some(token),
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok((name.unwrap_or_default().to_owned(), kind, indexed))
+}
+
+/// Returns `true` if the specified string is a valid Solidity identifier.
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digest;
+
+    #[test]
+    fn parse_function() {
+        let function = "function balanceOf(address owner) view returns (uint256)"
+            .parse::<Function>()
+            .unwrap();
+        assert_eq!(
+            function,
+            Function {
+                name: "balanceOf".to_owned(),
+                inputs: vec![Param::new("owner", ParamType::Address)],
+                outputs: vec![Param::new("", ParamType::Uint(256))],
+                state_mutability: StateMutability::View,
+            }
+        );
+        assert_eq!(function.selector(), [0x70, 0xa0, 0x82, 0x31]);
+    }
+
+    #[test]
+    fn parse_function_with_tuples() {
+        for s in [
+            "function settle(tuple(address token, uint256[] amounts)[] calldata trades, bytes) \
+             external payable returns (bool success)",
+            "function settle((address,uint256[])[], bytes memory data) payable returns (bool)",
+            "settle((address token,uint256[] amounts)[],bytes)payable returns(bool);",
+        ] {
+            let function = s.parse::<Function>().unwrap();
+            assert_eq!(function.signature(), "settle((address,uint256[])[],bytes)");
+            assert_eq!(function.state_mutability, StateMutability::Payable);
+            assert_eq!(function.outputs[0].kind, ParamType::Bool);
+        }
+    }
+
+    #[test]
+    fn parse_event() {
+        let event = "event Transfer(address indexed from, address indexed to, uint256 value)"
+            .parse::<Event>()
+            .unwrap();
+        assert_eq!(
+            event.inputs,
+            [
+                EventParam::new("from", ParamType::Address, true),
+                EventParam::new("to", ParamType::Address, true),
+                EventParam::new("value", ParamType::Uint(256), false),
+            ]
+        );
+        assert!(!event.anonymous);
+        assert_eq!(
+            event.topic(),
+            digest!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+        );
+
+        let event = "event Log(bytes32 indexed) anonymous"
+            .parse::<Event>()
+            .unwrap();
+        assert!(event.anonymous && event.inputs[0].indexed);
+    }
+
+    #[test]
+    fn parse_error() {
+        let error = "error Unauthorized(address)".parse::<Error>().unwrap();
+        assert_eq!(error.signature(), "Unauthorized(address)");
+        assert_eq!(error.selector(), [0x8e, 0x4a, 0x23, 0xd6]);
+    }
+
+    #[test]
+    fn parse_constructor() {
+        let constructor = "constructor(address owner, uint256) payable"
+            .parse::<Constructor>()
+            .unwrap();
+        assert_eq!(
+            constructor.input_types(),
+            [ParamType::Address, ParamType::Uint(256)]
+        );
+        assert_eq!(constructor.state_mutability, StateMutability::Payable);
+    }
+
+    #[test]
+    fn parse_abi() {
+        let abi = Abi::from_fragments([
+            "constructor()",
+            "function transfer(address to, uint256 value) returns (bool)",
+            "function transfer(address to, uint256 value, bytes data) returns (bool)",
+            "event Approval(address indexed owner, address indexed spender, uint256 value)",
+            "error InsufficientBalance(uint256 available, uint256 required)",
+            "fallback() external",
+            "receive() external payable",
+        ])
+        .unwrap();
+
+        assert_eq!(abi.functions_by_name("transfer").count(), 2);
+        assert_eq!(abi.events[0].name, "Approval");
+        assert_eq!(abi.errors[0].inputs[1].name, "required");
+        assert_eq!(abi.fallback, Some(StateMutability::NonPayable));
+        assert!(abi.receive);
+    }
+
+    #[test]
+    fn invalid_fragments() {
+        for s in [
+            "function (uint256)",
+            "function foo(uint256",
+            "function foo(uint256 indexed value)",
+            "function foo(uint7)",
+            "function foo() returns",
+            "function foo() sometimes",
+            "function foo(address a b)",
+            "event Foo(uint256) view",
+            "error Foo() anonymous",
+            "receive() external",
+            "constructor foo()",
+        ] {
+            assert!(Abi::from_fragments([s]).is_err(), "{s}");
+        }
+    }
+}
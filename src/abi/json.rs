@@ -0,0 +1,296 @@
+//! Deserialization of the standard JSON contract ABI.
+
+use super::{
+    Abi, Constructor, Error, Event, EventParam, Function, Param, ParamType, StateMutability,
+};
+use serde::{
+    de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserialize,
+};
+use std::fmt::{self, Formatter};
+
+impl<'de> Deserialize<'de> for Abi {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(AbiVisitor)
+    }
+}
+
+struct AbiVisitor;
+
+impl<'de> Visitor<'de> for AbiVisitor {
+    type Value = Abi;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a JSON ABI array or a contract artifact with an `abi` field")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut abi = Abi::default();
+        while let Some(item) = seq.next_element::<Item>()? {
+            item.add_to(&mut abi).map_err(de::Error::custom)?;
+        }
+        Ok(abi)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut abi = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "abi" {
+                if abi.is_some() {
+                    return Err(de::Error::duplicate_field("abi"));
+                }
+                abi = Some(map.next_value()?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        abi.ok_or_else(|| de::Error::missing_field("abi"))
+    }
+}
+
+/// A raw JSON ABI item.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Item {
+    #[serde(rename = "type", default = "Item::default_type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<RawParam>,
+    #[serde(default)]
+    outputs: Vec<RawParam>,
+    state_mutability: Option<String>,
+    #[serde(default)]
+    anonymous: bool,
+    #[serde(default)]
+    constant: bool,
+    #[serde(default)]
+    payable: bool,
+}
+
+impl Item {
+    fn default_type() -> String {
+        "function".to_owned()
+    }
+
+    /// Returns the state mutability of the item, taking into account legacy
+    /// `constant` and `payable` fields for older ABIs.
+    fn state_mutability(&self) -> Result<StateMutability, String> {
+        Ok(match self.state_mutability.as_deref() {
+            Some("pure") => StateMutability::Pure,
+            Some("view") => StateMutability::View,
+            Some("nonpayable") => StateMutability::NonPayable,
+            Some("payable") => StateMutability::Payable,
+            Some(other) => return Err(format!("invalid state mutability `{other}`")),
+            None if self.constant => StateMutability::View,
+            None if self.payable => StateMutability::Payable,
+            None => StateMutability::NonPayable,
+        })
+    }
+
+    /// Adds the item to the ABI.
+    fn add_to(self, abi: &mut Abi) -> Result<(), String> {
+        let params = |params: Vec<RawParam>| {
+            params
+                .into_iter()
+                .map(|param| Ok(Param::new(param.name.clone(), param.kind()?)))
+                .collect::<Result<Vec<_>, String>>()
+        };
+
+        let state_mutability = self.state_mutability()?;
+        match self.kind.as_str() {
+            "function" => abi.functions.push(Function {
+                name: self.name,
+                inputs: params(self.inputs)?,
+                outputs: params(self.outputs)?,
+                state_mutability,
+            }),
+            "event" => abi.events.push(Event {
+                name: self.name,
+                inputs: self
+                    .inputs
+                    .into_iter()
+                    .map(|param| {
+                        Ok(EventParam::new(
+                            param.name.clone(),
+                            param.kind()?,
+                            param.indexed,
+                        ))
+                    })
+                    .collect::<Result<_, String>>()?,
+                anonymous: self.anonymous,
+            }),
+            "error" => abi.errors.push(Error {
+                name: self.name,
+                inputs: params(self.inputs)?,
+            }),
+            "constructor" => {
+                if abi.constructor.is_some() {
+                    return Err("duplicate constructor".to_owned());
+                }
+                abi.constructor = Some(Constructor {
+                    inputs: params(self.inputs)?,
+                    state_mutability,
+                });
+            }
+            "fallback" => abi.fallback = Some(state_mutability),
+            "receive" => abi.receive = true,
+            other => return Err(format!("invalid ABI item type `{other}`")),
+        }
+        Ok(())
+    }
+}
+
+/// A raw JSON ABI parameter.
+#[derive(Deserialize)]
+struct RawParam {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    components: Vec<RawParam>,
+    #[serde(default)]
+    indexed: bool,
+}
+
+impl RawParam {
+    /// Returns the parameter type, resolving `tuple` components.
+    fn kind(&self) -> Result<ParamType, String> {
+        let kind = match self.kind.strip_prefix("tuple") {
+            Some(suffix) => {
+                let components = self
+                    .components
+                    .iter()
+                    .map(RawParam::kind)
+                    .collect::<Result<_, _>>()?;
+                format!("{}{suffix}", ParamType::Tuple(components))
+            }
+            None => self.kind.clone(),
+        };
+        kind.parse().map_err(|err| format!("{err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digest;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_json_abi() {
+        let abi = serde_json::from_value::<Abi>(json!([
+            {
+                "type": "constructor",
+                "inputs": [{ "name": "owner", "type": "address" }],
+                "stateMutability": "nonpayable"
+            },
+            {
+                "type": "function",
+                "name": "balanceOf",
+                "inputs": [{ "name": "owner", "type": "address" }],
+                "outputs": [{ "name": "", "type": "uint256" }],
+                "stateMutability": "view"
+            },
+            {
+                "type": "function",
+                "name": "settle",
+                "inputs": [
+                    {
+                        "name": "trades",
+                        "type": "tuple[]",
+                        "components": [
+                            { "name": "sellToken", "type": "address" },
+                            {
+                                "name": "data",
+                                "type": "tuple",
+                                "components": [{ "name": "", "type": "bytes" }]
+                            }
+                        ]
+                    }
+                ],
+                "outputs": [],
+                "stateMutability": "nonpayable"
+            },
+            {
+                "type": "event",
+                "name": "Transfer",
+                "inputs": [
+                    { "name": "from", "type": "address", "indexed": true },
+                    { "name": "to", "type": "address", "indexed": true },
+                    { "name": "value", "type": "uint256", "indexed": false }
+                ],
+                "anonymous": false
+            },
+            {
+                "type": "error",
+                "name": "Unauthorized",
+                "inputs": [{ "name": "caller", "type": "address" }]
+            },
+            { "type": "fallback", "stateMutability": "payable" },
+            { "type": "receive", "stateMutability": "payable" }
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            abi.constructor.as_ref().unwrap().input_types(),
+            [ParamType::Address]
+        );
+
+        let balance_of = abi.function("balanceOf", &[ParamType::Address]).unwrap();
+        assert_eq!(balance_of.selector(), [0x70, 0xa0, 0x82, 0x31]);
+        assert_eq!(balance_of.state_mutability, StateMutability::View);
+
+        let settle = abi.functions_by_name("settle").next().unwrap();
+        assert_eq!(settle.signature(), "settle((address,(bytes))[])");
+
+        let transfer = abi
+            .event_by_topic(digest!(
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            ))
+            .unwrap();
+        assert_eq!(transfer.name, "Transfer");
+        assert!(transfer.inputs[0].indexed && !transfer.inputs[2].indexed);
+
+        let unauthorized = abi.error_by_selector([0x8e, 0x4a, 0x23, 0xd6]).unwrap();
+        assert_eq!(unauthorized.signature(), "Unauthorized(address)");
+
+        assert_eq!(abi.fallback, Some(StateMutability::Payable));
+        assert!(abi.receive);
+    }
+
+    #[test]
+    fn deserialize_artifact() {
+        let abi = serde_json::from_value::<Abi>(json!({
+            "abi": [{ "name": "totalSupply", "outputs": [{ "type": "uint256" }], "constant": true }],
+            "bytecode": { "object": "0x" },
+        }))
+        .unwrap();
+
+        let total_supply = abi.function("totalSupply", &[]).unwrap();
+        assert_eq!(total_supply.state_mutability, StateMutability::View);
+        assert_eq!(total_supply.selector(), [0x18, 0x16, 0x0d, 0xdd]);
+    }
+
+    #[test]
+    fn invalid_json_abi() {
+        for value in [
+            json!([{ "type": "function", "name": "f", "inputs": [{ "type": "uint7" }] }]),
+            json!([{ "type": "method", "name": "f" }]),
+            json!({ "bytecode": "0x" }),
+        ] {
+            assert!(serde_json::from_value::<Abi>(value).is_err());
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Event log decoding and topic filters.
+
+use super::{
+    codec::{self, DecodeError, EncodeError},
+    Event, Value,
+};
+use crate::Digest;
+use std::slice;
+
+/// A decoded event log parameter.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum LogValue {
+    /// A decoded parameter value.
+    Value(Value),
+    /// The digest of an indexed parameter of a dynamic or composite type. Only
+    /// the hash of these parameters is included in the log topics, so their
+    /// value cannot be recovered.
+    Hash(Digest),
+}
+
+impl Event {
+    /// Decodes the parameters of a log emitted by this event, returning the
+    /// decoded values in parameter order.
+    ///
+    /// Only the log topics and data are needed, and the log address is not
+    /// checked: an event ABI does not identify which contracts emit it, and
+    /// any contract may emit logs matching the event's signature. Callers
+    /// should filter logs by address before decoding if they only trust logs
+    /// from specific contracts.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::abi::{Event, LogValue, Value};
+    /// # use ethprim::{address, digest, uint};
+    /// let transfer = "event Transfer(address indexed from, address indexed to, uint256 value)"
+    ///     .parse::<Event>()
+    ///     .unwrap();
+    ///
+    /// let topics = [
+    ///     transfer.topic(),
+    ///     digest!("0x0000000000000000000000001111111111111111111111111111111111111111"),
+    ///     digest!("0x0000000000000000000000002222222222222222222222222222222222222222"),
+    /// ];
+    /// let data = uint!("1000").to_be_bytes();
+    ///
+    /// assert_eq!(
+    ///     transfer.decode_log(&topics, &data).unwrap(),
+    ///     [
+    ///         LogValue::Value(Value::Address(address!("0x1111111111111111111111111111111111111111"))),
+    ///         LogValue::Value(Value::Address(address!("0x2222222222222222222222222222222222222222"))),
+    ///         LogValue::Value(Value::Uint(uint!("1000"))),
+    ///     ],
+    /// );
+    /// ```
+    pub fn decode_log(&self, topics: &[Digest], data: &[u8]) -> Result<Vec<LogValue>, DecodeError> {
+        let topics = if self.anonymous {
+            topics
+        } else {
+            match topics.split_first() {
+                Some((topic, topics)) if *topic == self.topic() => topics,
+                _ => return Err(DecodeError::TopicMismatch),
+            }
+        };
+
+        let indexed = self.inputs.iter().filter(|input| input.indexed).count();
+        if topics.len() != indexed {
+            return Err(DecodeError::TopicMismatch);
+        }
+
+        let kinds = self
+            .inputs
+            .iter()
+            .filter(|input| !input.indexed)
+            .map(|input| input.kind.clone())
+            .collect::<Vec<_>>();
+        let mut values = codec::decode(&kinds, data)?.into_iter();
+        let mut topics = topics.iter();
+
+        self.inputs
+            .iter()
+            .map(|input| {
+                if !input.indexed {
+                    return Ok(LogValue::Value(values.next().unwrap()));
+                }
+
+                let topic = topics.next().unwrap();
+                if !codec::is_topic_value_type(&input.kind) {
+                    return Ok(LogValue::Hash(*topic));
+                }
+                let value = codec::decode(slice::from_ref(&input.kind), topic.as_slice())?
+                    .pop()
+                    .unwrap();
+                Ok(LogValue::Value(value))
+            })
+            .collect()
+    }
+
+    /// Builds log topic filters from partially specified indexed arguments.
+    /// The resulting topics include the event topic first for non-anonymous
+    /// events, followed by a topic for each indexed argument, where `None`
+    /// matches any value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::abi::{Event, Value};
+    /// # use ethprim::{address, digest};
+    /// let transfer = "event Transfer(address indexed from, address indexed to, uint256 value)"
+    ///     .parse::<Event>()
+    ///     .unwrap();
+    ///
+    /// let to = address!("0x2222222222222222222222222222222222222222");
+    /// assert_eq!(
+    ///     transfer.topic_filter(&[None, Some(Value::Address(to))]).unwrap(),
+    ///     [
+    ///         Some(transfer.topic()),
+    ///         None,
+    ///         Some(digest!("0x0000000000000000000000002222222222222222222222222222222222222222")),
+    ///     ],
+    /// );
+    /// ```
+    pub fn topic_filter(&self, args: &[Option<Value>]) -> Result<Vec<Option<Digest>>, EncodeError> {
+        let indexed = self.inputs.iter().filter(|input| input.indexed);
+        if args.len() > indexed.clone().count() {
+            return Err(EncodeError::InvalidLength);
+        }
+
+        let topic = (!self.anonymous).then(|| Some(self.topic()));
+        let args = indexed.zip(args).map(|(input, arg)| match arg {
+            Some(value) if !value.type_check(&input.kind) => Err(EncodeError::TypeMismatch),
+            Some(value) => Ok(Some(codec::encode_topic(value))),
+            None => Ok(None),
+        });
+
+        topic.into_iter().map(Ok).chain(args).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, uint};
+
+    #[test]
+    fn decode_indexed_dynamic_values() {
+        let event = "event Named(string indexed name, uint256[2] indexed values, bytes data)"
+            .parse::<Event>()
+            .unwrap();
+        let name = Digest::of("alice");
+        let values = {
+            let mut preimage = [0; 64];
+            preimage[31] = 1;
+            preimage[63] = 2;
+            Digest::of(preimage)
+        };
+
+        let data = codec::encode(&[Value::Bytes(b"hello".to_vec())]);
+        assert_eq!(
+            event
+                .decode_log(&[event.topic(), name, values], &data)
+                .unwrap(),
+            [
+                LogValue::Hash(name),
+                LogValue::Hash(values),
+                LogValue::Value(Value::Bytes(b"hello".to_vec())),
+            ],
+        );
+
+        assert_eq!(
+            event
+                .topic_filter(&[
+                    Some(Value::String("alice".to_owned())),
+                    Some(Value::FixedArray(vec![
+                        Value::Uint(uint!("1")),
+                        Value::Uint(uint!("2")),
+                    ])),
+                ])
+                .unwrap(),
+            [Some(event.topic()), Some(name), Some(values)],
+        );
+    }
+
+    #[test]
+    fn decode_anonymous_event() {
+        let event = "event Anonymous(address indexed owner, bool flag) anonymous"
+            .parse::<Event>()
+            .unwrap();
+        let owner = address!("0x1111111111111111111111111111111111111111");
+        let topic = codec::encode_topic(&Value::Address(owner));
+        let data = codec::encode(&[Value::Bool(true)]);
+
+        assert_eq!(
+            event.decode_log(&[topic], &data).unwrap(),
+            [
+                LogValue::Value(Value::Address(owner)),
+                LogValue::Value(Value::Bool(true)),
+            ],
+        );
+        assert_eq!(
+            event.topic_filter(&[Some(Value::Address(owner))]).unwrap(),
+            [Some(topic)],
+        );
+    }
+
+    #[test]
+    fn decode_log_topic_mismatch() {
+        let event = "event Transfer(address indexed from, address indexed to, uint256 value)"
+            .parse::<Event>()
+            .unwrap();
+        let data = codec::encode(&[Value::Uint(uint!("1"))]);
+
+        for topics in [
+            vec![],
+            vec![Digest::default(), Digest::default(), Digest::default()],
+            vec![event.topic(), Digest::default()],
+        ] {
+            assert_eq!(
+                event.decode_log(&topics, &data).unwrap_err(),
+                DecodeError::TopicMismatch,
+            );
+        }
+    }
+
+    #[test]
+    fn topic_filter_errors() {
+        let event = "event Transfer(address indexed from, address indexed to, uint256 value)"
+            .parse::<Event>()
+            .unwrap();
+
+        assert_eq!(
+            event.topic_filter(&[None, None, None]).unwrap_err(),
+            EncodeError::InvalidLength,
+        );
+        assert_eq!(
+            event.topic_filter(&[Some(Value::Bool(true))]).unwrap_err(),
+            EncodeError::TypeMismatch,
+        );
+    }
+}
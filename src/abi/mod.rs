@@ -0,0 +1,404 @@
+//! Solidity contract ABI descriptors.
+//!
+//! This module provides descriptors for the functions, events, errors and
+//! constructor of a Solidity contract, as well as an [`Abi`] type for a
+//! collection of these items. Descriptors can be parsed from human-readable ABI
+//! fragments such as `function balanceOf(address owner) view returns
+//! (uint256)`. With the **`serde`** feature, an [`Abi`] can additionally be
+//! deserialized from the standard JSON ABI produced by `solc` and Foundry.
+//!
+//! Additionally, ABI [`Value`]s can be [`encode`]d and [`decode`]d, and event
+//! logs can be decoded with [`Event::decode_log()`] and revert data with
+//! [`Revert::decode()`].
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::abi::{Function, Param, ParamType, StateMutability};
+//! let function = Function {
+//!     name: "transfer".to_owned(),
+//!     inputs: vec![
+//!         Param::new("to", ParamType::Address),
+//!         Param::new("value", ParamType::Uint(256)),
+//!     ],
+//!     outputs: vec![Param::new("", ParamType::Bool)],
+//!     state_mutability: StateMutability::NonPayable,
+//! };
+//! assert_eq!(function.signature(), "transfer(address,uint256)");
+//! assert_eq!(function.selector(), [0xa9, 0x05, 0x9c, 0xbb]);
+//! assert_eq!(
+//!     function,
+//!     "function transfer(address to, uint256 value) returns (bool)".parse().unwrap(),
+//! );
+//! ```
+
+mod codec;
+mod human;
+#[cfg(feature = "serde")]
+mod json;
+mod log;
+mod param;
+mod revert;
+mod value;
+
+pub use self::{
+    codec::{decode, encode, DecodeError, EncodeError},
+    log::LogValue,
+    param::ParamType,
+    revert::{PanicCode, Revert},
+    value::Value,
+};
+use crate::Digest;
+use std::fmt::{self, Display, Formatter};
+
+/// A named function, constructor or error parameter.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Param {
+    /// The name of the parameter. This is empty for unnamed parameters.
+    pub name: String,
+    /// The type of the parameter.
+    pub kind: ParamType,
+}
+
+impl Param {
+    /// Creates a new parameter.
+    pub fn new(name: impl Into<String>, kind: ParamType) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+        }
+    }
+}
+
+/// A named event parameter.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EventParam {
+    /// The name of the parameter. This is empty for unnamed parameters.
+    pub name: String,
+    /// The type of the parameter.
+    pub kind: ParamType,
+    /// Whether or not the parameter is indexed, and included in the log
+    /// topics instead of the log data.
+    pub indexed: bool,
+}
+
+impl EventParam {
+    /// Creates a new event parameter.
+    pub fn new(name: impl Into<String>, kind: ParamType, indexed: bool) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            indexed,
+        }
+    }
+}
+
+/// The state mutability of a function.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum StateMutability {
+    /// The function does not read or modify blockchain state.
+    Pure,
+    /// The function reads but does not modify blockchain state.
+    View,
+    /// The function may modify blockchain state, but does not accept Ether.
+    #[default]
+    NonPayable,
+    /// The function may modify blockchain state, and accepts Ether.
+    Payable,
+}
+
+impl StateMutability {
+    /// Returns the state mutability keyword as used in the JSON ABI.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pure => "pure",
+            Self::View => "view",
+            Self::NonPayable => "nonpayable",
+            Self::Payable => "payable",
+        }
+    }
+}
+
+impl Display for StateMutability {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A contract function descriptor.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Function {
+    /// The name of the function.
+    pub name: String,
+    /// The function input parameters.
+    pub inputs: Vec<Param>,
+    /// The function output parameters.
+    pub outputs: Vec<Param>,
+    /// The function state mutability.
+    pub state_mutability: StateMutability,
+}
+
+impl Function {
+    /// Returns the canonical signature of the function.
+    pub fn signature(&self) -> String {
+        signature(&self.name, self.inputs.iter().map(|input| &input.kind))
+    }
+
+    /// Returns the 4-byte selector of the function.
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// Returns the input parameter types of the function.
+    pub fn input_types(&self) -> Vec<ParamType> {
+        self.inputs.iter().map(|input| input.kind.clone()).collect()
+    }
+}
+
+/// A contract event descriptor.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Event {
+    /// The name of the event.
+    pub name: String,
+    /// The event parameters.
+    pub inputs: Vec<EventParam>,
+    /// Whether or not the event is anonymous. Anonymous events do not include
+    /// their topic as the first log topic.
+    pub anonymous: bool,
+}
+
+impl Event {
+    /// Returns the canonical signature of the event.
+    pub fn signature(&self) -> String {
+        signature(&self.name, self.inputs.iter().map(|input| &input.kind))
+    }
+
+    /// Returns the topic of the event. This is the Keccak-256 digest of its
+    /// canonical signature.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::abi::{Event, EventParam, ParamType};
+    /// # use ethprim::digest;
+    /// let event = Event {
+    ///     name: "Transfer".to_owned(),
+    ///     inputs: vec![
+    ///         EventParam::new("from", ParamType::Address, true),
+    ///         EventParam::new("to", ParamType::Address, true),
+    ///         EventParam::new("value", ParamType::Uint(256), false),
+    ///     ],
+    ///     anonymous: false,
+    /// };
+    /// assert_eq!(
+    ///     event.topic(),
+    ///     digest!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+    /// );
+    /// ```
+    pub fn topic(&self) -> Digest {
+        Digest::of(self.signature())
+    }
+
+    /// Returns the parameter types of the event.
+    pub fn input_types(&self) -> Vec<ParamType> {
+        self.inputs.iter().map(|input| input.kind.clone()).collect()
+    }
+}
+
+/// A contract custom error descriptor.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Error {
+    /// The name of the error.
+    pub name: String,
+    /// The error parameters.
+    pub inputs: Vec<Param>,
+}
+
+impl Error {
+    /// Returns the canonical signature of the error.
+    pub fn signature(&self) -> String {
+        signature(&self.name, self.inputs.iter().map(|input| &input.kind))
+    }
+
+    /// Returns the 4-byte selector of the error.
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// Returns the parameter types of the error.
+    pub fn input_types(&self) -> Vec<ParamType> {
+        self.inputs.iter().map(|input| input.kind.clone()).collect()
+    }
+}
+
+/// A contract constructor descriptor.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Constructor {
+    /// The constructor parameters.
+    pub inputs: Vec<Param>,
+    /// The constructor state mutability.
+    pub state_mutability: StateMutability,
+}
+
+impl Constructor {
+    /// Returns the parameter types of the constructor.
+    pub fn input_types(&self) -> Vec<ParamType> {
+        self.inputs.iter().map(|input| input.kind.clone()).collect()
+    }
+}
+
+/// A contract ABI.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Abi {
+    /// The contract constructor.
+    pub constructor: Option<Constructor>,
+    /// The contract functions.
+    pub functions: Vec<Function>,
+    /// The contract events.
+    pub events: Vec<Event>,
+    /// The contract custom errors.
+    pub errors: Vec<Error>,
+    /// The state mutability of the contract's fallback function, if it has
+    /// one.
+    pub fallback: Option<StateMutability>,
+    /// Whether or not the contract has a `receive` function.
+    pub receive: bool,
+}
+
+impl Abi {
+    /// Returns an iterator over all functions with the specified name.
+    pub fn functions_by_name<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a Function> + 'a {
+        self.functions.iter().filter(move |f| f.name == name)
+    }
+
+    /// Resolves an overloaded function by name and input parameter types.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::abi::{Abi, Function, Param, ParamType};
+    /// let abi = Abi {
+    ///     functions: vec![
+    ///         Function {
+    ///             name: "safeTransferFrom".to_owned(),
+    ///             inputs: [ParamType::Address, ParamType::Address, ParamType::Uint(256)]
+    ///                 .into_iter()
+    ///                 .map(|kind| Param::new("", kind))
+    ///                 .collect(),
+    ///             outputs: vec![],
+    ///             state_mutability: Default::default(),
+    ///         },
+    ///         Function {
+    ///             name: "safeTransferFrom".to_owned(),
+    ///             inputs: [
+    ///                 ParamType::Address,
+    ///                 ParamType::Address,
+    ///                 ParamType::Uint(256),
+    ///                 ParamType::Bytes,
+    ///             ]
+    ///             .into_iter()
+    ///             .map(|kind| Param::new("", kind))
+    ///             .collect(),
+    ///             outputs: vec![],
+    ///             state_mutability: Default::default(),
+    ///         },
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let function = abi
+    ///     .function(
+    ///         "safeTransferFrom",
+    ///         &[ParamType::Address, ParamType::Address, ParamType::Uint(256)],
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(function.selector(), [0x42, 0x84, 0x2e, 0x0e]);
+    /// ```
+    pub fn function(&self, name: &str, inputs: &[ParamType]) -> Option<&Function> {
+        self.functions
+            .iter()
+            .find(|f| f.name == name && f.inputs.iter().map(|input| &input.kind).eq(inputs))
+    }
+
+    /// Returns the function with the specified selector.
+    pub fn function_by_selector(&self, selector: [u8; 4]) -> Option<&Function> {
+        self.functions.iter().find(|f| f.selector() == selector)
+    }
+
+    /// Returns an iterator over all events with the specified name.
+    pub fn events_by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Event> + 'a {
+        self.events.iter().filter(move |e| e.name == name)
+    }
+
+    /// Resolves an overloaded event by name and parameter types.
+    pub fn event(&self, name: &str, inputs: &[ParamType]) -> Option<&Event> {
+        self.events
+            .iter()
+            .find(|e| e.name == name && e.inputs.iter().map(|input| &input.kind).eq(inputs))
+    }
+
+    /// Returns the non-anonymous event with the specified topic.
+    pub fn event_by_topic(&self, topic: Digest) -> Option<&Event> {
+        self.events
+            .iter()
+            .find(|e| !e.anonymous && e.topic() == topic)
+    }
+
+    /// Returns an iterator over all errors with the specified name.
+    pub fn errors_by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Error> + 'a {
+        self.errors.iter().filter(move |e| e.name == name)
+    }
+
+    /// Resolves an overloaded error by name and parameter types.
+    pub fn error(&self, name: &str, inputs: &[ParamType]) -> Option<&Error> {
+        self.errors
+            .iter()
+            .find(|e| e.name == name && e.inputs.iter().map(|input| &input.kind).eq(inputs))
+    }
+
+    /// Returns the error with the specified selector.
+    pub fn error_by_selector(&self, selector: [u8; 4]) -> Option<&Error> {
+        self.errors.iter().find(|e| e.selector() == selector)
+    }
+}
+
+/// Represents an error parsing ABI types and descriptors.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseAbiError {
+    /// An invalid Solidity type was specified.
+    InvalidType(String),
+    /// An invalid human-readable ABI fragment was specified.
+    InvalidFragment(String),
+}
+
+impl Display for ParseAbiError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidType(kind) => write!(f, "invalid Solidity type `{kind}`"),
+            Self::InvalidFragment(fragment) => write!(f, "invalid ABI fragment `{fragment}`"),
+        }
+    }
+}
+
+impl std::error::Error for ParseAbiError {}
+
+/// Computes a canonical signature from a name and parameter types.
+fn signature<'a>(name: &str, kinds: impl IntoIterator<Item = &'a ParamType>) -> String {
+    let kinds = ParamType::Tuple(kinds.into_iter().cloned().collect());
+    format!("{name}{kinds}")
+}
+
+/// Computes a 4-byte selector from a canonical signature.
+fn selector(signature: &str) -> [u8; 4] {
+    let digest = Digest::of(signature);
+    [digest[0], digest[1], digest[2], digest[3]]
+}
@@ -0,0 +1,257 @@
+//! Solidity ABI parameter types.
+
+use super::ParseAbiError;
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A Solidity ABI parameter type.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ParamType {
+    /// An Ethereum public address.
+    Address,
+    /// A boolean value.
+    Bool,
+    /// A signed integer of the specified bit size.
+    Int(usize),
+    /// An unsigned integer of the specified bit size.
+    Uint(usize),
+    /// Fixed length bytes of the specified byte size.
+    FixedBytes(usize),
+    /// A function pointer, consisting of an address and a selector.
+    Function,
+    /// Dynamic length bytes.
+    Bytes,
+    /// A dynamic length UTF-8 string.
+    String,
+    /// A dynamic length array.
+    Array(Box<ParamType>),
+    /// A fixed length array.
+    FixedArray(Box<ParamType>, usize),
+    /// A tuple of parameter types.
+    Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+    /// Returns `true` if the parameter type is dynamically sized.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::abi::ParamType;
+    /// assert!(!"uint256[2]".parse::<ParamType>().unwrap().is_dynamic());
+    /// assert!("(address,string)".parse::<ParamType>().unwrap().is_dynamic());
+    /// ```
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Bytes | Self::String | Self::Array(_) => true,
+            Self::FixedArray(kind, _) => kind.is_dynamic(),
+            Self::Tuple(kinds) => kinds.iter().any(Self::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Parses an elementary (i.e. non-array and non-tuple) type.
+    fn elementary(s: &str) -> Option<Self> {
+        let sized = |prefix: &str, default: usize| -> Option<usize> {
+            let size = s.strip_prefix(prefix)?;
+            if size.is_empty() {
+                return Some(default);
+            }
+            parse_size(size)
+        };
+
+        let kind = match s {
+            "address" => Self::Address,
+            "bool" => Self::Bool,
+            "function" => Self::Function,
+            "bytes" => Self::Bytes,
+            "string" => Self::String,
+            _ if s.starts_with("uint") => {
+                let bits = sized("uint", 256)?;
+                (bits > 0 && bits <= 256 && bits % 8 == 0).then_some(Self::Uint(bits))?
+            }
+            _ if s.starts_with("int") => {
+                let bits = sized("int", 256)?;
+                (bits > 0 && bits <= 256 && bits % 8 == 0).then_some(Self::Int(bits))?
+            }
+            _ if s.starts_with("bytes") => {
+                let size = sized("bytes", 0)?;
+                (size > 0 && size <= 32).then_some(Self::FixedBytes(size))?
+            }
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+impl Display for ParamType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Address => f.write_str("address"),
+            Self::Bool => f.write_str("bool"),
+            Self::Int(bits) => write!(f, "int{bits}"),
+            Self::Uint(bits) => write!(f, "uint{bits}"),
+            Self::FixedBytes(size) => write!(f, "bytes{size}"),
+            Self::Function => f.write_str("function"),
+            Self::Bytes => f.write_str("bytes"),
+            Self::String => f.write_str("string"),
+            Self::Array(kind) => write!(f, "{kind}[]"),
+            Self::FixedArray(kind, len) => write!(f, "{kind}[{len}]"),
+            Self::Tuple(kinds) => {
+                f.write_str("(")?;
+                for (i, kind) in kinds.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{kind}")?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+impl FromStr for ParamType {
+    type Err = ParseAbiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseAbiError::InvalidType(s.to_owned());
+        let s = s.trim();
+
+        if let Some(rest) = s.strip_suffix(']') {
+            let (kind, len) = rest.rsplit_once('[').ok_or_else(invalid)?;
+            let kind = Box::new(kind.parse()?);
+            return Ok(if len.is_empty() {
+                Self::Array(kind)
+            } else {
+                Self::FixedArray(kind, parse_size(len).ok_or_else(invalid)?)
+            });
+        }
+
+        if let Some(inner) = s
+            .strip_prefix("tuple")
+            .unwrap_or(s)
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+        {
+            return Ok(Self::Tuple(
+                split_components(inner)
+                    .ok_or_else(invalid)?
+                    .into_iter()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?,
+            ));
+        }
+
+        Self::elementary(s).ok_or_else(invalid)
+    }
+}
+
+/// Parses a type size or array length. Only canonical non-zero decimal
+/// numbers are accepted, so signs and leading zeros are rejected.
+fn parse_size(s: &str) -> Option<usize> {
+    if !s.bytes().all(|b| b.is_ascii_digit()) || s.starts_with('0') {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Splits a comma separated list of components, taking nested parenthesis
+/// into account. Returns `None` if the parenthesis are unbalanced.
+pub(crate) fn split_components(s: &str) -> Option<Vec<&str>> {
+    if s.trim().is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut components = Vec::new();
+    let (mut depth, mut start) = (0_usize, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                components.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    components.push(&s[start..]);
+
+    Some(components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_canonical_types() {
+        for (s, kind) in [
+            ("address", ParamType::Address),
+            ("bool", ParamType::Bool),
+            ("uint", ParamType::Uint(256)),
+            ("int8", ParamType::Int(8)),
+            ("bytes4", ParamType::FixedBytes(4)),
+            ("bytes", ParamType::Bytes),
+            (
+                "uint256[2][]",
+                ParamType::Array(Box::new(ParamType::FixedArray(
+                    Box::new(ParamType::Uint(256)),
+                    2,
+                ))),
+            ),
+            (
+                "(address,(bool,string)[])",
+                ParamType::Tuple(vec![
+                    ParamType::Address,
+                    ParamType::Array(Box::new(ParamType::Tuple(vec![
+                        ParamType::Bool,
+                        ParamType::String,
+                    ]))),
+                ]),
+            ),
+            ("tuple()", ParamType::Tuple(vec![])),
+        ] {
+            assert_eq!(s.parse::<ParamType>().unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn canonical_type_formatting() {
+        for s in ["uint256", "bytes32[3]", "(address,(uint8,bytes)[])[]"] {
+            assert_eq!(s.parse::<ParamType>().unwrap().to_string(), s);
+        }
+        assert_eq!("uint".parse::<ParamType>().unwrap().to_string(), "uint256");
+    }
+
+    #[test]
+    fn invalid_types() {
+        for s in [
+            "uint7",
+            "uint264",
+            "int0",
+            "uint08",
+            "uint+8",
+            "bytes0",
+            "bytes33",
+            "foo",
+            "(uint256",
+            "uint256[",
+            "uint256[x]",
+            "uint256[0]",
+            "uint256[01]",
+            "uint256[+2]",
+            "uint256[-2]",
+        ] {
+            assert!(s.parse::<ParamType>().is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Decoding of contract revert data.
+
+use super::{codec, Error, ParamType, Value};
+use crate::U256;
+use std::fmt::{self, Display, Formatter};
+
+/// Decoded contract revert data.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Revert {
+    /// A revert with a reason string, encoded as `Error(string)`. This is
+    /// produced by `require(condition, "reason")` and `revert("reason")`.
+    Error(String),
+    /// A panic, encoded as `Panic(uint256)`. This is produced by failing
+    /// assertions and other internal errors such as arithmetic overflow.
+    Panic(PanicCode),
+    /// A custom error.
+    Custom {
+        /// The selector of the custom error.
+        selector: [u8; 4],
+        /// The decoded custom error arguments.
+        args: Vec<Value>,
+    },
+    /// Raw revert data that could not be decoded.
+    Raw(Vec<u8>),
+}
+
+impl Revert {
+    /// The selector for `Error(string)` reverts.
+    pub const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+    /// The selector for `Panic(uint256)` reverts.
+    pub const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+    /// Decodes revert data. Custom errors are decoded with the specified error
+    /// descriptors. Revert data that does not match any known error is
+    /// returned as [`Revert::Raw`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::abi::{self, Error, PanicCode, Revert, Value};
+    /// # use ethprim::{address, U256};
+    /// let data = [
+    ///     &Revert::PANIC_SELECTOR[..],
+    ///     &abi::encode(&[Value::Uint(U256::new(0x11))]),
+    /// ]
+    /// .concat();
+    /// assert_eq!(
+    ///     Revert::decode(&data, &[]),
+    ///     Revert::Panic(PanicCode::ArithmeticOverflow),
+    /// );
+    ///
+    /// let unauthorized = "error Unauthorized(address)".parse::<Error>().unwrap();
+    /// let caller = address!("0x1111111111111111111111111111111111111111");
+    /// let data = [
+    ///     &unauthorized.selector()[..],
+    ///     &abi::encode(&[Value::Address(caller)]),
+    /// ]
+    /// .concat();
+    /// assert_eq!(
+    ///     Revert::decode(&data, &[unauthorized.clone()]),
+    ///     Revert::Custom {
+    ///         selector: unauthorized.selector(),
+    ///         args: vec![Value::Address(caller)],
+    ///     },
+    /// );
+    /// ```
+    pub fn decode(data: &[u8], errors: &[Error]) -> Self {
+        Self::try_decode(data, errors).unwrap_or_else(|| Self::Raw(data.to_vec()))
+    }
+
+    fn try_decode(data: &[u8], errors: &[Error]) -> Option<Self> {
+        let (selector, args) = data.split_first_chunk::<4>()?;
+        match *selector {
+            Self::ERROR_SELECTOR => match codec::decode(&[ParamType::String], args).ok()?.pop() {
+                Some(Value::String(reason)) => Some(Self::Error(reason)),
+                _ => None,
+            },
+            Self::PANIC_SELECTOR => {
+                match codec::decode(&[ParamType::Uint(256)], args).ok()?.pop() {
+                    Some(Value::Uint(code)) => Some(Self::Panic(PanicCode::from(code))),
+                    _ => None,
+                }
+            }
+            _ => errors
+                .iter()
+                .filter(|error| error.selector() == *selector)
+                .find_map(|error| codec::decode(&error.input_types(), args).ok())
+                .map(|args| Self::Custom {
+                    selector: *selector,
+                    args,
+                }),
+        }
+    }
+}
+
+impl Display for Revert {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Error(reason) => write!(f, "execution reverted: {reason}"),
+            Self::Panic(code) => write!(f, "execution panicked: {code}"),
+            Self::Custom { selector, .. } => write!(
+                f,
+                "execution reverted with custom error 0x{:02x}{:02x}{:02x}{:02x}",
+                selector[0], selector[1], selector[2], selector[3],
+            ),
+            Self::Raw(data) if data.is_empty() => write!(f, "execution reverted"),
+            Self::Raw(data) => {
+                write!(f, "execution reverted with data 0x")?;
+                for byte in data {
+                    write!(f, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A Solidity panic code.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PanicCode {
+    /// Generic compiler inserted panic.
+    Generic,
+    /// An `assert` failed.
+    Assert,
+    /// An arithmetic operation resulted in underflow or overflow outside of an
+    /// `unchecked { ... }` block.
+    ArithmeticOverflow,
+    /// Division or modulo by zero.
+    DivisionByZero,
+    /// A value that is too big or negative was converted into an enum type.
+    EnumConversion,
+    /// An incorrectly encoded storage byte array was accessed.
+    StorageEncoding,
+    /// `.pop()` was called on an empty array.
+    EmptyArrayPop,
+    /// An array, bytes or array slice was accessed at an out-of-bounds or
+    /// negative index.
+    ArrayOutOfBounds,
+    /// Too much memory was allocated, or an array was created that is too
+    /// large.
+    OutOfMemory,
+    /// A zero-initialized variable of internal function type was called.
+    ZeroFunction,
+    /// An unknown panic code.
+    Other(U256),
+}
+
+impl PanicCode {
+    /// Returns the numeric panic code.
+    pub fn code(&self) -> U256 {
+        U256::new(match self {
+            Self::Generic => 0x00,
+            Self::Assert => 0x01,
+            Self::ArithmeticOverflow => 0x11,
+            Self::DivisionByZero => 0x12,
+            Self::EnumConversion => 0x21,
+            Self::StorageEncoding => 0x22,
+            Self::EmptyArrayPop => 0x31,
+            Self::ArrayOutOfBounds => 0x32,
+            Self::OutOfMemory => 0x41,
+            Self::ZeroFunction => 0x51,
+            Self::Other(code) => return *code,
+        })
+    }
+}
+
+impl From<U256> for PanicCode {
+    fn from(code: U256) -> Self {
+        let Ok(known) = u8::try_from(code) else {
+            return Self::Other(code);
+        };
+        match known {
+            0x00 => Self::Generic,
+            0x01 => Self::Assert,
+            0x11 => Self::ArithmeticOverflow,
+            0x12 => Self::DivisionByZero,
+            0x21 => Self::EnumConversion,
+            0x22 => Self::StorageEncoding,
+            0x31 => Self::EmptyArrayPop,
+            0x32 => Self::ArrayOutOfBounds,
+            0x41 => Self::OutOfMemory,
+            0x51 => Self::ZeroFunction,
+            _ => Self::Other(code),
+        }
+    }
+}
+
+impl Display for PanicCode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let description = match self {
+            Self::Generic => "generic panic",
+            Self::Assert => "assertion failed",
+            Self::ArithmeticOverflow => "arithmetic underflow or overflow",
+            Self::DivisionByZero => "division or modulo by zero",
+            Self::EnumConversion => "invalid enum conversion",
+            Self::StorageEncoding => "invalid storage byte array encoding",
+            Self::EmptyArrayPop => "pop on empty array",
+            Self::ArrayOutOfBounds => "array index out of bounds",
+            Self::OutOfMemory => "out of memory",
+            Self::ZeroFunction => "call to zero-initialized internal function",
+            Self::Other(code) => return write!(f, "unknown panic code {code:#x}"),
+        };
+        write!(f, "{description} ({:#04x})", self.code().as_u8())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::Param;
+
+    fn revert_data(selector: [u8; 4], args: &[Value]) -> Vec<u8> {
+        [&selector[..], &codec::encode(args)].concat()
+    }
+
+    #[test]
+    fn decode_error_string() {
+        let data = revert_data(
+            Revert::ERROR_SELECTOR,
+            &[Value::String("insufficient balance".to_owned())],
+        );
+        assert_eq!(
+            Revert::decode(&data, &[]),
+            Revert::Error("insufficient balance".to_owned())
+        );
+    }
+
+    #[test]
+    fn decode_panic_codes() {
+        for (code, panic) in [
+            (0x01, PanicCode::Assert),
+            (0x12, PanicCode::DivisionByZero),
+            (0x32, PanicCode::ArrayOutOfBounds),
+            (0x42, PanicCode::Other(U256::new(0x42))),
+        ] {
+            let data = revert_data(Revert::PANIC_SELECTOR, &[Value::Uint(U256::new(code))]);
+            assert_eq!(Revert::decode(&data, &[]), Revert::Panic(panic));
+            assert_eq!(panic.code(), code);
+        }
+    }
+
+    #[test]
+    fn decode_custom_error_overloads() {
+        let errors = [
+            "error InsufficientBalance(uint256 available, uint256 required)"
+                .parse::<Error>()
+                .unwrap(),
+            Error {
+                name: "Unauthorized".to_owned(),
+                inputs: vec![Param::new("", ParamType::Address)],
+            },
+        ];
+        let args = [Value::Uint(U256::new(1)), Value::Uint(U256::new(2))];
+        let data = revert_data(errors[0].selector(), &args);
+        assert_eq!(
+            Revert::decode(&data, &errors),
+            Revert::Custom {
+                selector: errors[0].selector(),
+                args: args.to_vec(),
+            }
+        );
+    }
+
+    #[test]
+    fn decode_raw_revert_data() {
+        for data in [
+            vec![],
+            vec![0x08, 0xc3, 0x79],
+            vec![0xde, 0xad, 0xbe, 0xef],
+            revert_data(Revert::ERROR_SELECTOR, &[]),
+            revert_data(Revert::PANIC_SELECTOR, &[Value::Bool(true)])[..20].to_vec(),
+        ] {
+            assert_eq!(Revert::decode(&data, &[]), Revert::Raw(data.clone()));
+        }
+    }
+
+    #[test]
+    fn revert_formatting() {
+        assert_eq!(
+            Revert::Panic(PanicCode::ArithmeticOverflow).to_string(),
+            "execution panicked: arithmetic underflow or overflow (0x11)"
+        );
+        assert_eq!(
+            Revert::Raw(vec![0xde, 0xad]).to_string(),
+            "execution reverted with data 0xdead"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Solidity ABI values.
+
+use super::ParamType;
+use crate::{Address, I256, U256};
+
+/// A Solidity ABI value.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Value {
+    /// An Ethereum public address.
+    Address(Address),
+    /// A boolean value.
+    Bool(bool),
+    /// A signed integer.
+    Int(I256),
+    /// An unsigned integer.
+    Uint(U256),
+    /// Fixed length bytes.
+    FixedBytes(Vec<u8>),
+    /// A function pointer, consisting of an address and a selector.
+    Function(Address, [u8; 4]),
+    /// Dynamic length bytes.
+    Bytes(Vec<u8>),
+    /// A dynamic length UTF-8 string.
+    String(String),
+    /// A dynamic length array.
+    Array(Vec<Value>),
+    /// A fixed length array.
+    FixedArray(Vec<Value>),
+    /// A tuple of values.
+    Tuple(Vec<Value>),
+}
+
+impl Value {
+    /// Returns `true` if the value is compatible with the specified parameter
+    /// type.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::abi::{ParamType, Value};
+    /// # use ethprim::U256;
+    /// assert!(Value::Uint(U256::new(255)).type_check(&ParamType::Uint(8)));
+    /// assert!(!Value::Uint(U256::new(256)).type_check(&ParamType::Uint(8)));
+    /// ```
+    pub fn type_check(&self, kind: &ParamType) -> bool {
+        match (self, kind) {
+            (Self::Address(_), ParamType::Address)
+            | (Self::Bool(_), ParamType::Bool)
+            | (Self::Function(..), ParamType::Function)
+            | (Self::Bytes(_), ParamType::Bytes)
+            | (Self::String(_), ParamType::String) => true,
+            (Self::Int(value), ParamType::Int(bits)) => match *bits {
+                // Zero-sized integer types can't be parsed, but can still be
+                // constructed directly and only fit the value `0`.
+                0 => *value == 0,
+                1..=255 => {
+                    let sign = *value >> (*bits as u32 - 1);
+                    sign == 0 || sign == -1
+                }
+                _ => true,
+            },
+            (Self::Uint(value), ParamType::Uint(bits)) => {
+                *bits >= 256 || *value >> (*bits as u32) == 0
+            }
+            (Self::FixedBytes(bytes), ParamType::FixedBytes(size)) => bytes.len() == *size,
+            (Self::Array(values), ParamType::Array(kind)) => {
+                values.iter().all(|value| value.type_check(kind))
+            }
+            (Self::FixedArray(values), ParamType::FixedArray(kind, len)) => {
+                values.len() == *len && values.iter().all(|value| value.type_check(kind))
+            }
+            (Self::Tuple(values), ParamType::Tuple(kinds)) => {
+                values.len() == kinds.len()
+                    && values
+                        .iter()
+                        .zip(kinds)
+                        .all(|(value, kind)| value.type_check(kind))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the value is dynamically sized.
+    pub(crate) fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Bytes(_) | Self::String(_) | Self::Array(_) => true,
+            Self::FixedArray(values) | Self::Tuple(values) => values.iter().any(Self::is_dynamic),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{int, uint};
+
+    #[test]
+    fn type_check_integers() {
+        for (value, kind, ok) in [
+            (Value::Int(int!("127")), ParamType::Int(8), true),
+            (Value::Int(int!("-128")), ParamType::Int(8), true),
+            (Value::Int(int!("128")), ParamType::Int(8), false),
+            (Value::Int(int!("-129")), ParamType::Int(8), false),
+            (Value::Int(I256::MIN), ParamType::Int(256), true),
+            (Value::Uint(uint!("65535")), ParamType::Uint(16), true),
+            (Value::Uint(uint!("65536")), ParamType::Uint(16), false),
+            (Value::Uint(U256::MAX), ParamType::Uint(256), true),
+            (Value::Uint(U256::ONE), ParamType::Int(256), false),
+            (Value::Int(I256::ZERO), ParamType::Int(0), true),
+            (Value::Int(I256::MINUS_ONE), ParamType::Int(0), false),
+            (Value::Uint(U256::ZERO), ParamType::Uint(0), true),
+            (Value::Uint(U256::ONE), ParamType::Uint(0), false),
+        ] {
+            assert_eq!(value.type_check(&kind), ok, "{value:?} {kind}");
+        }
+    }
+
+    #[test]
+    fn type_check_composite() {
+        let kind = "(bytes2,string[2])[]".parse::<ParamType>().unwrap();
+        let value = |bytes: Vec<u8>, len: usize| {
+            Value::Array(vec![Value::Tuple(vec![
+                Value::FixedBytes(bytes),
+                Value::FixedArray(vec![Value::String("a".to_owned()); len]),
+            ])])
+        };
+        assert!(value(vec![1, 2], 2).type_check(&kind));
+        assert!(!value(vec![1], 2).type_check(&kind));
+        assert!(!value(vec![1, 2], 3).type_check(&kind));
+    }
+}
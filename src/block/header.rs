@@ -0,0 +1,332 @@
+//! Block headers.
+
+use super::BlockHash;
+use crate::{
+    rlp::{self, Decodable, DecodeError, Encodable, Output},
+    Address, Bloom, Digest, U256,
+};
+
+/// A block header.
+///
+/// Fields introduced by later forks are optional, and are only included in
+/// the header's encoding when they are set. Since fields are appended in fork
+/// order, an optional field must only be set if all optional fields before it
+/// are also set.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Header {
+    /// The hash of the parent block.
+    pub parent_hash: BlockHash,
+    /// The hash of the block's ommers list.
+    pub ommers_hash: Digest,
+    /// The address receiving the block's priority fees.
+    pub beneficiary: Address,
+    /// The root of the state trie after executing the block.
+    pub state_root: Digest,
+    /// The root of the block's transactions trie.
+    pub transactions_root: Digest,
+    /// The root of the block's receipts trie.
+    pub receipts_root: Digest,
+    /// The bloom of all logs emitted by the block's transactions.
+    pub logs_bloom: Bloom,
+    /// The proof-of-work difficulty of the block. This is zero for all blocks
+    /// since the Paris fork.
+    pub difficulty: U256,
+    /// The block number.
+    pub number: u64,
+    /// The gas limit of the block.
+    pub gas_limit: u64,
+    /// The total gas used by the block's transactions.
+    pub gas_used: u64,
+    /// The block timestamp in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Arbitrary extra data of at most 32 bytes.
+    pub extra_data: Vec<u8>,
+    /// The proof-of-work mix hash, or the beacon chain `prev_randao` value
+    /// since the Paris fork.
+    pub mix_hash: Digest,
+    /// The proof-of-work nonce. This is zero for all blocks since the Paris
+    /// fork.
+    pub nonce: [u8; 8],
+    /// The [EIP-1559] base fee per gas, added in the London fork.
+    ///
+    /// [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+    pub base_fee_per_gas: Option<u64>,
+    /// The root of the block's withdrawals trie, added in the Shanghai fork.
+    pub withdrawals_root: Option<Digest>,
+    /// The total blob gas used by the block's transactions, added in the
+    /// Cancun fork.
+    pub blob_gas_used: Option<u64>,
+    /// The running excess blob gas used for computing blob gas prices, added
+    /// in the Cancun fork.
+    pub excess_blob_gas: Option<u64>,
+    /// The root of the parent beacon block, added in the Cancun fork.
+    pub parent_beacon_block_root: Option<Digest>,
+    /// The [EIP-7685] execution layer requests hash, added in the Prague fork.
+    ///
+    /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+    pub requests_hash: Option<Digest>,
+}
+
+impl Header {
+    /// Returns the block hash, the Keccak-256 digest of the header's RLP
+    /// encoding.
+    pub fn hash(&self) -> BlockHash {
+        rlp::digest(self)
+    }
+
+    /// Returns `true` if an optional field is set after one that is not. Such
+    /// headers cannot be encoded, since optional fields are positional.
+    pub(super) fn has_optional_field_gaps(&self) -> bool {
+        self.optional_fields()
+            .windows(2)
+            .any(|pair| pair[0].is_none() && pair[1].is_some())
+    }
+
+    /// Returns the optional fields of the header, in fork order.
+    fn optional_fields(&self) -> [Option<&dyn Encodable>; 6] {
+        [
+            self.base_fee_per_gas.as_ref().map(|f| f as _),
+            self.withdrawals_root.as_ref().map(|f| f as _),
+            self.blob_gas_used.as_ref().map(|f| f as _),
+            self.excess_blob_gas.as_ref().map(|f| f as _),
+            self.parent_beacon_block_root.as_ref().map(|f| f as _),
+            self.requests_hash.as_ref().map(|f| f as _),
+        ]
+    }
+
+    /// Returns the RLP encoded fields of the header, including the optional
+    /// fields up to the first one that is not set.
+    fn fields(&self) -> Vec<&dyn Encodable> {
+        debug_assert!(
+            !self.has_optional_field_gaps(),
+            "optional header field set after an unset one",
+        );
+        let mut fields: Vec<&dyn Encodable> = vec![
+            &self.parent_hash,
+            &self.ommers_hash,
+            &self.beneficiary,
+            &self.state_root,
+            &self.transactions_root,
+            &self.receipts_root,
+            &self.logs_bloom,
+            &self.difficulty,
+            &self.number,
+            &self.gas_limit,
+            &self.gas_used,
+            &self.timestamp,
+            &self.extra_data,
+            &self.mix_hash,
+            &self.nonce,
+        ];
+        fields.extend(self.optional_fields().into_iter().map_while(|field| field));
+        fields
+    }
+}
+
+impl Encodable for Header {
+    fn encode(&self, out: &mut dyn Output) {
+        rlp::encode_fields(&self.fields(), &[], out);
+    }
+
+    fn length(&self) -> usize {
+        rlp::fields_length(&self.fields())
+    }
+}
+
+impl Decodable for Header {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let header = rlp::decode_fields(buf, |fields| {
+            Ok(Self {
+                parent_hash: Decodable::decode(fields)?,
+                ommers_hash: Decodable::decode(fields)?,
+                beneficiary: Decodable::decode(fields)?,
+                state_root: Decodable::decode(fields)?,
+                transactions_root: Decodable::decode(fields)?,
+                receipts_root: Decodable::decode(fields)?,
+                logs_bloom: Decodable::decode(fields)?,
+                difficulty: Decodable::decode(fields)?,
+                number: Decodable::decode(fields)?,
+                gas_limit: Decodable::decode(fields)?,
+                gas_used: Decodable::decode(fields)?,
+                timestamp: Decodable::decode(fields)?,
+                extra_data: Decodable::decode(fields)?,
+                mix_hash: Decodable::decode(fields)?,
+                nonce: Decodable::decode(fields)?,
+                base_fee_per_gas: decode_optional(fields)?,
+                withdrawals_root: decode_optional(fields)?,
+                blob_gas_used: decode_optional(fields)?,
+                excess_blob_gas: decode_optional(fields)?,
+                parent_beacon_block_root: decode_optional(fields)?,
+                requests_hash: decode_optional(fields)?,
+            })
+        })?;
+        if header.has_optional_field_gaps() {
+            return Err(DecodeError::InvalidValue);
+        }
+        Ok(header)
+    }
+}
+
+/// Decodes an optional trailing field, which is not set if there are no more
+/// fields remaining.
+fn decode_optional<T>(fields: &mut &[u8]) -> Result<Option<T>, DecodeError>
+where
+    T: Decodable,
+{
+    if fields.is_empty() {
+        return Ok(None);
+    }
+    T::decode(fields).map(Some)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::{address, block::EMPTY_OMMERS_HASH, digest, tests::hex, trie::EMPTY_ROOT_HASH};
+
+    pub fn prague() -> Header {
+        let mut logs_bloom = Bloom::default();
+        logs_bloom.0[0] = 0x80;
+        logs_bloom.0[255] = 0x01;
+        Header {
+            parent_hash: Digest([0x11; 32]),
+            ommers_hash: EMPTY_OMMERS_HASH,
+            beneficiary: address!("0x95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5"),
+            state_root: Digest([0x22; 32]),
+            transactions_root: Digest([0x33; 32]),
+            receipts_root: Digest([0x44; 32]),
+            logs_bloom,
+            difficulty: U256::ZERO,
+            number: 22_000_000,
+            gas_limit: 36_000_000,
+            gas_used: 12_345_678,
+            timestamp: 1_741_000_000,
+            extra_data: b"beaverbuild.org".to_vec(),
+            mix_hash: Digest([0x55; 32]),
+            nonce: [0; 8],
+            base_fee_per_gas: Some(1_000_000_000),
+            withdrawals_root: Some(Digest([0x66; 32])),
+            blob_gas_used: Some(393_216),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(Digest([0x77; 32])),
+            requests_hash: Some(digest!(
+                "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+            )),
+        }
+    }
+
+    #[test]
+    fn empty_ommers_hash() {
+        assert_eq!(
+            EMPTY_OMMERS_HASH,
+            digest!("0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"),
+        );
+    }
+
+    #[test]
+    fn hashes_across_forks() {
+        let prague = prague();
+        let cancun = Header {
+            requests_hash: None,
+            ..prague.clone()
+        };
+        let london = Header {
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            difficulty: U256::new(12345),
+            nonce: 0x0102030405060708_u64.to_be_bytes(),
+            ..cancun.clone()
+        };
+        let frontier = Header {
+            base_fee_per_gas: None,
+            ..london.clone()
+        };
+
+        for (header, hash) in [
+            (
+                prague,
+                digest!("0x30914e89fd0bf0ecb5943fa0d4c8077c9be9babce201c3c5c2cfbd8f9e9265de"),
+            ),
+            (
+                cancun,
+                digest!("0x861fac82961919251155595316b7863c38eabc7c22c26aa67646a8baea1ab453"),
+            ),
+            (
+                london,
+                digest!("0xcb8efcc8dfabb016c882686e74908beb0fc08270992c17c3fca84a0fa3356d03"),
+            ),
+            (
+                frontier,
+                digest!("0x09ed76a0ec2b1a83fcc61f049e723fb1c71bd721a841e946a372a15d8862939d"),
+            ),
+        ] {
+            assert_eq!(header.hash(), hash);
+
+            let encoded = rlp::encode(&header);
+            assert_eq!(encoded.len(), header.length());
+            assert_eq!(rlp::decode::<Header>(&encoded).unwrap(), header);
+        }
+    }
+
+    #[test]
+    fn hashes_prague_devnet_header() {
+        // Block 789 of a Prague devnet, as returned by `eth_getHeaderByHash`.
+        let header = Header {
+            parent_hash: digest!(
+                "0x60f1563d2c572116091a4b91421d8d972118e39604d23455d841f9431cea4b6a"
+            ),
+            ommers_hash: EMPTY_OMMERS_HASH,
+            beneficiary: address!("0xf97e180c050e5Ab072211Ad2C213Eb5AEE4DF134"),
+            state_root: digest!(
+                "0x8101d88f2761eb9849634740f92fe09735551ad5a4d5e9da9bcae1ef4726a475"
+            ),
+            transactions_root: digest!(
+                "0xf543eb3d405d2d6320344d348b06703ff1abeef71288181a24061e53f89bb5ef"
+            ),
+            receipts_root: digest!(
+                "0xeaa8c40899a61ae59615cf9985f5e2194f8fd2b57d273be63bde6733e89b12ab"
+            ),
+            logs_bloom: Bloom::default(),
+            difficulty: U256::ZERO,
+            number: 789,
+            gas_limit: 30_000_000,
+            gas_used: 21_000,
+            timestamp: 1_729_280_622,
+            extra_data: hex("d883010e0c846765746888676f312e32332e32856c696e7578"),
+            mix_hash: digest!("0xe6d9c084dd36560520d5776a5387a82fb44793c9cd1b69afb61d53af29ee64b0"),
+            nonce: [0; 8],
+            base_fee_per_gas: Some(7),
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            blob_gas_used: Some(131_072),
+            excess_blob_gas: Some(262_144),
+            parent_beacon_block_root: Some(digest!(
+                "0xd0bdb48ab45028568e66c8ddd600ac4c2a52522714bbfbf00ea6d20ba40f3ae2"
+            )),
+            requests_hash: Some(digest!(
+                "0x6036c41849da9c076ed79654d434017387a88fb833c2856b32e18218b3341c5f"
+            )),
+        };
+        assert_eq!(
+            header.hash(),
+            digest!("0x661da523f3e44725f3a1cee38183d35424155a05674609a9f6ed81243adf9e26"),
+        );
+    }
+
+    #[test]
+    fn optional_field_gaps() {
+        let prague = prague();
+        assert!(!prague.has_optional_field_gaps());
+        assert!(Header {
+            withdrawals_root: None,
+            ..prague.clone()
+        }
+        .has_optional_field_gaps());
+        assert!(Header {
+            base_fee_per_gas: None,
+            ..prague
+        }
+        .has_optional_field_gaps());
+    }
+}
@@ -0,0 +1,172 @@
+//! JSON-RPC block identifiers.
+
+use super::BlockHash;
+use crate::quantity::{ParseQuantityError, U64};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A block number or tag, used for identifying blocks in JSON-RPC requests.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::block::BlockNumberOrTag;
+/// assert_eq!("finalized".parse(), Ok(BlockNumberOrTag::Finalized));
+/// assert_eq!("0x400".parse(), Ok(BlockNumberOrTag::Number(1024)));
+/// assert_eq!(BlockNumberOrTag::from(1024).to_string(), "0x400");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum BlockNumberOrTag {
+    /// The latest block.
+    #[default]
+    Latest,
+    /// The latest block that is safe from re-orgs under honest majority and
+    /// certain synchronicity assumptions.
+    Safe,
+    /// The latest block finalized by the beacon chain.
+    Finalized,
+    /// The pending block being built on top of the latest block.
+    Pending,
+    /// The genesis block.
+    Earliest,
+    /// A block by number.
+    Number(u64),
+}
+
+impl Display for BlockNumberOrTag {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Latest => f.write_str("latest"),
+            Self::Safe => f.write_str("safe"),
+            Self::Finalized => f.write_str("finalized"),
+            Self::Pending => f.write_str("pending"),
+            Self::Earliest => f.write_str("earliest"),
+            Self::Number(number) => write!(f, "{}", U64(*number)),
+        }
+    }
+}
+
+impl FromStr for BlockNumberOrTag {
+    type Err = ParseQuantityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "latest" => Self::Latest,
+            "safe" => Self::Safe,
+            "finalized" => Self::Finalized,
+            "pending" => Self::Pending,
+            "earliest" => Self::Earliest,
+            _ => Self::Number(s.parse::<U64>()?.into()),
+        })
+    }
+}
+
+impl From<u64> for BlockNumberOrTag {
+    fn from(number: u64) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl From<U64> for BlockNumberOrTag {
+    fn from(number: U64) -> Self {
+        Self::Number(number.into())
+    }
+}
+
+/// An [EIP-1898] block identifier, either by number or tag, or by hash.
+///
+/// [EIP-1898]: https://eips.ethereum.org/EIPS/eip-1898
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BlockId {
+    /// A block by number or tag.
+    Number(BlockNumberOrTag),
+    /// A block by hash.
+    Hash {
+        /// The block hash.
+        hash: BlockHash,
+        /// Whether or not the block must be in the canonical chain. Nodes treat
+        /// an unset value as `false`.
+        require_canonical: Option<bool>,
+    },
+}
+
+impl BlockId {
+    /// Returns a block identifier for a block by hash, without requiring it to
+    /// be canonical.
+    pub fn hash(hash: BlockHash) -> Self {
+        Self::Hash {
+            hash,
+            require_canonical: None,
+        }
+    }
+
+    /// Returns a block identifier for a block by hash that must be in the
+    /// canonical chain.
+    pub fn canonical_hash(hash: BlockHash) -> Self {
+        Self::Hash {
+            hash,
+            require_canonical: Some(true),
+        }
+    }
+}
+
+impl Default for BlockId {
+    fn default() -> Self {
+        Self::Number(BlockNumberOrTag::Latest)
+    }
+}
+
+impl From<BlockNumberOrTag> for BlockId {
+    fn from(number: BlockNumberOrTag) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl From<u64> for BlockId {
+    fn from(number: u64) -> Self {
+        Self::Number(number.into())
+    }
+}
+
+impl From<BlockHash> for BlockId {
+    fn from(hash: BlockHash) -> Self {
+        Self::hash(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_number_or_tag_roundtrip() {
+        for (s, value) in [
+            ("latest", BlockNumberOrTag::Latest),
+            ("safe", BlockNumberOrTag::Safe),
+            ("finalized", BlockNumberOrTag::Finalized),
+            ("pending", BlockNumberOrTag::Pending),
+            ("earliest", BlockNumberOrTag::Earliest),
+            ("0x0", BlockNumberOrTag::Number(0)),
+            ("0x14fb180", BlockNumberOrTag::Number(22_000_000)),
+        ] {
+            assert_eq!(s.parse::<BlockNumberOrTag>().unwrap(), value);
+            assert_eq!(value.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn invalid_block_number_or_tag() {
+        assert_eq!(
+            "Latest".parse::<BlockNumberOrTag>(),
+            Err(ParseQuantityError::MissingPrefix)
+        );
+        assert_eq!(
+            "0x00ff".parse::<BlockNumberOrTag>(),
+            Ok(BlockNumberOrTag::Number(0xff))
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Ethereum blocks.
+//!
+//! This module provides the block [`Header`] type, covering the fields added
+//! by every fork up to and including Prague. Headers can be RLP encoded and
+//! hashed, so that chains of headers can be verified by checking that each
+//! header's `parent_hash` matches the hash of its parent.
+//!
+//! Additionally, it provides the [`BlockNumberOrTag`] and [`BlockId`] types
+//! for identifying blocks in JSON-RPC requests.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::block::Header;
+//! let parent = Header {
+//!     number: 1,
+//!     base_fee_per_gas: Some(7),
+//!     ..Default::default()
+//! };
+//! let child = Header {
+//!     parent_hash: parent.hash(),
+//!     number: 2,
+//!     base_fee_per_gas: Some(7),
+//!     ..Default::default()
+//! };
+//! assert_eq!(child.parent_hash, parent.hash());
+//! ```
+
+mod header;
+mod id;
+#[cfg(feature = "serde")]
+mod serde;
+
+pub use self::{
+    header::Header,
+    id::{BlockId, BlockNumberOrTag},
+};
+use crate::{keccak, Digest};
+
+/// A block hash.
+pub type BlockHash = Digest;
+
+/// The ommers hash of blocks without any ommers, the Keccak-256 digest of the
+/// RLP encoding of an empty list. This is the ommers hash of all blocks since
+/// the Paris fork.
+pub const EMPTY_OMMERS_HASH: Digest = keccak!(&[0xc0]);
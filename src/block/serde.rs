@@ -0,0 +1,460 @@
+//! Serialization of blocks in the JSON-RPC format.
+
+use super::{BlockHash, BlockId, BlockNumberOrTag, Header};
+use crate::{quantity, Address, Bloom, Digest, U256};
+use serde::{
+    de::{self, value::MapAccessDeserializer, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt::{self, Formatter};
+
+/// The JSON-RPC representation of a block header. Quantities are encoded as
+/// `0x`-prefixed hex strings, and the block hash is only included when
+/// serializing.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Json {
+    #[serde(skip_deserializing)]
+    hash: Digest,
+    parent_hash: Digest,
+    sha3_uncles: Digest,
+    miner: Address,
+    state_root: Digest,
+    transactions_root: Digest,
+    receipts_root: Digest,
+    logs_bloom: Bloom,
+    difficulty: U256,
+    #[serde(with = "quantity::strict")]
+    number: u64,
+    #[serde(with = "quantity::strict")]
+    gas_limit: u64,
+    #[serde(with = "quantity::strict")]
+    gas_used: u64,
+    #[serde(with = "quantity::strict")]
+    timestamp: u64,
+    #[serde(with = "bytes")]
+    extra_data: Vec<u8>,
+    mix_hash: Digest,
+    #[serde(with = "bytes")]
+    nonce: Vec<u8>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "quantity::strict"
+    )]
+    base_fee_per_gas: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    withdrawals_root: Option<Digest>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "quantity::strict"
+    )]
+    blob_gas_used: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "quantity::strict"
+    )]
+    excess_blob_gas: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent_beacon_block_root: Option<Digest>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    requests_hash: Option<Digest>,
+}
+
+impl Serialize for Header {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Json {
+            hash: self.hash(),
+            parent_hash: self.parent_hash,
+            sha3_uncles: self.ommers_hash,
+            miner: self.beneficiary,
+            state_root: self.state_root,
+            transactions_root: self.transactions_root,
+            receipts_root: self.receipts_root,
+            logs_bloom: self.logs_bloom,
+            difficulty: self.difficulty,
+            number: self.number,
+            gas_limit: self.gas_limit,
+            gas_used: self.gas_used,
+            timestamp: self.timestamp,
+            extra_data: self.extra_data.clone(),
+            mix_hash: self.mix_hash,
+            nonce: self.nonce.to_vec(),
+            base_fee_per_gas: self.base_fee_per_gas,
+            withdrawals_root: self.withdrawals_root,
+            blob_gas_used: self.blob_gas_used,
+            excess_blob_gas: self.excess_blob_gas,
+            parent_beacon_block_root: self.parent_beacon_block_root,
+            requests_hash: self.requests_hash,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Header {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let json = Json::deserialize(deserializer)?;
+        let header = Self {
+            parent_hash: json.parent_hash,
+            ommers_hash: json.sha3_uncles,
+            beneficiary: json.miner,
+            state_root: json.state_root,
+            transactions_root: json.transactions_root,
+            receipts_root: json.receipts_root,
+            logs_bloom: json.logs_bloom,
+            difficulty: json.difficulty,
+            number: json.number,
+            gas_limit: json.gas_limit,
+            gas_used: json.gas_used,
+            timestamp: json.timestamp,
+            extra_data: json.extra_data,
+            mix_hash: json.mix_hash,
+            nonce: json
+                .nonce
+                .try_into()
+                .map_err(|_| de::Error::invalid_length(0, &"8-byte nonce"))?,
+            base_fee_per_gas: json.base_fee_per_gas,
+            withdrawals_root: json.withdrawals_root,
+            blob_gas_used: json.blob_gas_used,
+            excess_blob_gas: json.excess_blob_gas,
+            parent_beacon_block_root: json.parent_beacon_block_root,
+            requests_hash: json.requests_hash,
+        };
+        if header.has_optional_field_gaps() {
+            return Err(de::Error::custom(
+                "optional header field set after an unset one",
+            ));
+        }
+        Ok(header)
+    }
+}
+
+impl Serialize for BlockNumberOrTag {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockNumberOrTag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BlockNumberOrTagVisitor)
+    }
+}
+
+struct BlockNumberOrTagVisitor;
+
+impl<'de> Visitor<'de> for BlockNumberOrTagVisitor {
+    type Value = BlockNumberOrTag;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a block tag or `0x`-prefixed hex block number")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// The EIP-1898 object representation of a block identifier.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct BlockIdJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    block_number: Option<BlockNumberOrTag>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    block_hash: Option<BlockHash>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    require_canonical: Option<bool>,
+}
+
+impl Serialize for BlockId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Number(number) => number.serialize(serializer),
+            Self::Hash {
+                hash,
+                require_canonical,
+            } => BlockIdJson {
+                block_number: None,
+                block_hash: Some(*hash),
+                require_canonical: *require_canonical,
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(BlockIdVisitor)
+    }
+}
+
+struct BlockIdVisitor;
+
+impl<'de> Visitor<'de> for BlockIdVisitor {
+    type Value = BlockId;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a block tag, number, hash or EIP-1898 block identifier object")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        // Block hashes are accepted as a shorthand for a non-canonical block
+        // identifier object. Note that block numbers are parsed leniently, so
+        // a zero-padded 32-byte number would also be accepted; such strings
+        // are always treated as hashes, matching the EIP-1898 shorthand.
+        if s.len() == 66 {
+            return s.parse().map(BlockId::hash).map_err(de::Error::custom);
+        }
+        BlockNumberOrTagVisitor.visit_str(s).map(BlockId::Number)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let json = BlockIdJson::deserialize(MapAccessDeserializer::new(map))?;
+        match (json.block_number, json.block_hash, json.require_canonical) {
+            (Some(number), None, None) => Ok(BlockId::Number(number)),
+            (None, Some(hash), require_canonical) => Ok(BlockId::Hash {
+                hash,
+                require_canonical,
+            }),
+            _ => Err(de::Error::custom(
+                "expected exactly one of `blockNumber` or `blockHash`",
+            )),
+        }
+    }
+}
+
+/// Serialization of bytes as `0x`-prefixed hex strings.
+mod bytes {
+    use serde::{de, Deserialize as _, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&crate::bytes::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if !s.starts_with("0x") {
+            return Err(de::Error::custom("bytes missing '0x' prefix"));
+        }
+        crate::bytes::decode(&s).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{block::header::tests::prague, digest};
+    use serde_json::json;
+
+    fn prague_json() -> serde_json::Value {
+        let header = prague();
+        json!({
+            "hash": "0x30914e89fd0bf0ecb5943fa0d4c8077c9be9babce201c3c5c2cfbd8f9e9265de",
+            "parentHash": header.parent_hash,
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "miner": "0x95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5",
+            "stateRoot": header.state_root,
+            "transactionsRoot": header.transactions_root,
+            "receiptsRoot": header.receipts_root,
+            "logsBloom": header.logs_bloom,
+            "difficulty": "0x0",
+            "number": "0x14fb180",
+            "gasLimit": "0x2255100",
+            "gasUsed": "0xbc614e",
+            "timestamp": "0x67c58d40",
+            "extraData": "0x6265617665726275696c642e6f7267",
+            "mixHash": header.mix_hash,
+            "nonce": "0x0000000000000000",
+            "baseFeePerGas": "0x3b9aca00",
+            "withdrawalsRoot": header.withdrawals_root,
+            "blobGasUsed": "0x60000",
+            "excessBlobGas": "0x0",
+            "parentBeaconBlockRoot": header.parent_beacon_block_root,
+            "requestsHash": header.requests_hash,
+        })
+    }
+
+    #[test]
+    fn serialize_header() {
+        let value = serde_json::to_value(prague()).unwrap();
+        let expected = prague_json();
+        for (key, value) in value.as_object().unwrap() {
+            assert_eq!(
+                value.to_string().to_lowercase(),
+                expected[key].to_string().to_lowercase(),
+                "{key}"
+            );
+        }
+        assert_eq!(value.as_object().unwrap().len(), 22);
+    }
+
+    #[test]
+    fn deserialize_header() {
+        let mut json = prague_json();
+        json["transactions"] = json!([]);
+        json["size"] = json!("0x100");
+        assert_eq!(serde_json::from_value::<Header>(json).unwrap(), prague());
+    }
+
+    #[test]
+    fn deserialize_pre_london_header() {
+        let mut json = prague_json();
+        for field in [
+            "baseFeePerGas",
+            "withdrawalsRoot",
+            "blobGasUsed",
+            "excessBlobGas",
+            "parentBeaconBlockRoot",
+            "requestsHash",
+        ] {
+            json.as_object_mut().unwrap().remove(field);
+        }
+        let header = serde_json::from_value::<Header>(json).unwrap();
+        assert_eq!(header.base_fee_per_gas, None);
+        assert_eq!(header.requests_hash, None);
+        assert!(!serde_json::to_value(&header)
+            .unwrap()
+            .as_object()
+            .unwrap()
+            .contains_key("baseFeePerGas"));
+    }
+
+    #[test]
+    fn deserialize_header_with_field_gaps() {
+        for field in ["baseFeePerGas", "withdrawalsRoot", "parentBeaconBlockRoot"] {
+            let mut json = prague_json();
+            json.as_object_mut().unwrap().remove(field);
+            assert!(serde_json::from_value::<Header>(json).is_err(), "{field}");
+        }
+    }
+
+    #[test]
+    fn invalid_header() {
+        for (field, value) in [
+            ("number", json!("1234")),
+            ("number", json!("0x01")),
+            ("gasLimit", json!("0x10000000000000000")),
+            ("nonce", json!("0x00")),
+            ("extraData", json!("6265")),
+        ] {
+            let mut json = prague_json();
+            json[field] = value;
+            assert!(serde_json::from_value::<Header>(json).is_err(), "{field}");
+        }
+    }
+
+    #[test]
+    fn block_number_or_tag_serde() {
+        for (value, json) in [
+            (BlockNumberOrTag::Safe, json!("safe")),
+            (BlockNumberOrTag::Number(1024), json!("0x400")),
+        ] {
+            assert_eq!(serde_json::to_value(value).unwrap(), json);
+            assert_eq!(
+                serde_json::from_value::<BlockNumberOrTag>(json).unwrap(),
+                value
+            );
+        }
+        assert_eq!(
+            serde_json::from_value::<BlockNumberOrTag>(json!("0x0400")).unwrap(),
+            BlockNumberOrTag::Number(1024),
+        );
+        assert!(serde_json::from_value::<BlockNumberOrTag>(json!("head")).is_err());
+    }
+
+    #[test]
+    fn block_id_serde() {
+        let hash = Digest([0x11; 32]);
+        for (value, json) in [
+            (BlockId::from(BlockNumberOrTag::Latest), json!("latest")),
+            (BlockId::from(1024), json!("0x400")),
+            (BlockId::hash(hash), json!({ "blockHash": hash })),
+            (
+                BlockId::canonical_hash(hash),
+                json!({ "blockHash": hash, "requireCanonical": true }),
+            ),
+        ] {
+            assert_eq!(serde_json::to_value(value).unwrap(), json);
+            assert_eq!(serde_json::from_value::<BlockId>(json).unwrap(), value);
+        }
+
+        for (json, value) in [
+            (
+                json!({ "blockNumber": "finalized" }),
+                BlockId::from(BlockNumberOrTag::Finalized),
+            ),
+            (json!({ "blockNumber": "0x400" }), BlockId::from(1024)),
+            (
+                json!({ "blockHash": hash, "requireCanonical": false }),
+                BlockId::Hash {
+                    hash,
+                    require_canonical: Some(false),
+                },
+            ),
+            (json!(hash), BlockId::hash(hash)),
+            (
+                json!("0x0000000000000000000000000000000000000000000000000000000000000400"),
+                BlockId::hash(digest!(
+                    "0x0000000000000000000000000000000000000000000000000000000000000400"
+                )),
+            ),
+        ] {
+            assert_eq!(serde_json::from_value::<BlockId>(json).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn invalid_block_id() {
+        let hash = Digest([0x11; 32]);
+        for json in [
+            json!({}),
+            json!({ "blockNumber": "0x1", "blockHash": hash }),
+            json!({ "blockNumber": "0x1", "requireCanonical": true }),
+            json!({ "blockHash": hash, "blockTag": "latest" }),
+            json!(1024),
+            json!("0x1111111111111111111111111111111111111111111111111111111111111"),
+        ] {
+            assert!(
+                serde_json::from_value::<BlockId>(json.clone()).is_err(),
+                "{json}"
+            );
+        }
+    }
+}
@@ -0,0 +1,297 @@
+//! Ethereum logs bloom filters.
+
+#[cfg(feature = "serde")]
+mod serde;
+
+use crate::{
+    hex::{self, Alphabet, FormattingBuffer, ParseHexError},
+    Address, Digest,
+};
+use core::{
+    fmt::{self, Debug, Display, Formatter, LowerHex, UpperHex},
+    ops::{BitOr, BitOrAssign},
+    str::FromStr,
+};
+
+/// A 2048-bit logs bloom filter.
+///
+/// Blooms are used in block headers and receipts for quickly checking whether
+/// or not logs with a given address or topic may have been emitted. Each
+/// input sets three bits, taken from the first six bytes of its Keccak-256
+/// digest. As with any bloom filter, membership tests may have false
+/// positives but never false negatives.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{address, digest, Bloom};
+/// let weth = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+/// let transfer = digest!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+///
+/// let mut bloom = Bloom::default();
+/// bloom.accrue_log(&weth, &[transfer]);
+/// assert!(bloom.contains_address(&weth));
+/// assert!(bloom.contains_topic(&transfer));
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Bloom(pub [u8; 256]);
+
+impl Bloom {
+    /// Creates a bloom with the bits of a single input set.
+    pub fn of(input: impl AsRef<[u8]>) -> Self {
+        let mut bloom = Self::default();
+        bloom.accrue(input);
+        bloom
+    }
+
+    /// Sets the bits of an input in the bloom. Inputs are raw bytes, such as
+    /// the 20 bytes of an address or the 32 bytes of a topic.
+    pub fn accrue(&mut self, input: impl AsRef<[u8]>) {
+        for (index, mask) in bits(input.as_ref()) {
+            self.0[index] |= mask;
+        }
+    }
+
+    /// Sets the bits of a log's address and topics in the bloom.
+    pub fn accrue_log(&mut self, address: &Address, topics: &[Digest]) {
+        self.accrue(address);
+        for topic in topics {
+            self.accrue(topic);
+        }
+    }
+
+    /// Sets all the bits of another bloom in the bloom.
+    pub fn accrue_bloom(&mut self, other: &Self) {
+        for (byte, other) in self.0.iter_mut().zip(other.0) {
+            *byte |= other;
+        }
+    }
+
+    /// Returns `true` if the bloom may contain an input, and `false` if it
+    /// definitely does not.
+    pub fn contains_input(&self, input: impl AsRef<[u8]>) -> bool {
+        bits(input.as_ref()).all(|(index, mask)| self.0[index] & mask == mask)
+    }
+
+    /// Returns `true` if the bloom may contain logs emitted by an address.
+    pub fn contains_address(&self, address: &Address) -> bool {
+        self.contains_input(address)
+    }
+
+    /// Returns `true` if the bloom may contain logs with a topic.
+    pub fn contains_topic(&self, topic: &Digest) -> bool {
+        self.contains_input(topic)
+    }
+
+    /// Returns `true` if all bits of another bloom are set in the bloom.
+    pub fn contains_bloom(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .zip(other.0)
+            .all(|(byte, other)| byte & other == other)
+    }
+
+    /// Returns `true` if no bits are set in the bloom.
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|byte| *byte == 0)
+    }
+
+    /// Returns a stack-allocated formatted string with the specified alphabet.
+    fn fmt_buffer(&self, alphabet: Alphabet) -> FormattingBuffer<514> {
+        hex::encode(&self.0, alphabet)
+    }
+}
+
+/// Returns the byte indices and masks of the three bits for an input. Each
+/// bit is selected by the low 11 bits of a big-endian pair of bytes of the
+/// input's digest, counting from the end of the bloom.
+fn bits(input: &[u8]) -> impl Iterator<Item = (usize, u8)> {
+    let digest = Digest::of(input);
+    (0..3).map(move |i| {
+        let bit = u16::from_be_bytes([digest[i * 2], digest[i * 2 + 1]]) as usize & 0x7ff;
+        (255 - bit / 8, 1 << (bit % 8))
+    })
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self([0; 256])
+    }
+}
+
+impl BitOr for Bloom {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self.accrue_bloom(&rhs);
+        self
+    }
+}
+
+impl BitOrAssign for Bloom {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.accrue_bloom(&rhs);
+    }
+}
+
+impl Debug for Bloom {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Bloom")
+            .field(&format_args!("{self}"))
+            .finish()
+    }
+}
+
+impl Display for Bloom {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(self.fmt_buffer(Alphabet::default()).as_str())
+    }
+}
+
+impl LowerHex for Bloom {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let buffer = self.fmt_buffer(Alphabet::Lower);
+        f.pad(if f.alternate() {
+            buffer.as_str()
+        } else {
+            buffer.as_bytes_str()
+        })
+    }
+}
+
+impl UpperHex for Bloom {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let buffer = self.fmt_buffer(Alphabet::Upper);
+        f.pad(if f.alternate() {
+            buffer.as_str()
+        } else {
+            buffer.as_bytes_str()
+        })
+    }
+}
+
+impl AsRef<[u8; 256]> for Bloom {
+    fn as_ref(&self) -> &[u8; 256] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Bloom {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl FromStr for Bloom {
+    type Err = ParseBloomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(hex::decode(s)?))
+    }
+}
+
+/// Represents an error parsing a bloom.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseBloomError {
+    /// The bloom does not have the correct length.
+    InvalidLength,
+    /// An invalid character was found.
+    InvalidHexCharacter { c: char, index: usize },
+}
+
+impl Display for ParseBloomError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "invalid bloom length"),
+            Self::InvalidHexCharacter { c, index } => {
+                let (c, index) = (*c, *index);
+                write!(f, "{}", ParseHexError::InvalidHexCharacter { c, index })
+            }
+        }
+    }
+}
+
+impl From<ParseHexError> for ParseBloomError {
+    fn from(err: ParseHexError) -> Self {
+        match err {
+            ParseHexError::InvalidLength => Self::InvalidLength,
+            ParseHexError::InvalidHexCharacter { c, index } => {
+                Self::InvalidHexCharacter { c, index }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseBloomError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest};
+
+    const WETH: Address = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    const TRANSFER: Digest =
+        digest!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+    const FROM: Digest =
+        digest!("0x0000000000000000000000001111111111111111111111111111111111111111");
+
+    fn bloom(bits: &[(usize, u8)]) -> Bloom {
+        let mut bloom = Bloom::default();
+        for (index, byte) in bits {
+            bloom.0[*index] = *byte;
+        }
+        bloom
+    }
+
+    #[test]
+    fn accrue_address() {
+        assert_eq!(
+            Bloom::of(WETH),
+            bloom(&[(48, 0x02), (52, 0x08), (226, 0x20)])
+        );
+    }
+
+    #[test]
+    fn accrue_log() {
+        let mut log = Bloom::default();
+        log.accrue_log(&WETH, &[TRANSFER, FROM]);
+        assert_eq!(
+            log,
+            bloom(&[
+                (48, 0x02),
+                (52, 0x08),
+                (75, 0x08),
+                (123, 0x10),
+                (125, 0x02),
+                (160, 0x24),
+                (195, 0x02),
+                (226, 0x20),
+            ]),
+        );
+
+        assert!(log.contains_address(&WETH));
+        assert!(log.contains_topic(&TRANSFER));
+        assert!(log.contains_topic(&FROM));
+        assert!(!log.contains_address(&Address([0x11; 20])));
+        assert!(log.contains_bloom(&Bloom::of(TRANSFER)));
+        assert!(!Bloom::of(TRANSFER).contains_bloom(&log));
+        assert_eq!(Bloom::of(WETH) | Bloom::of(TRANSFER) | Bloom::of(FROM), log);
+    }
+
+    #[test]
+    fn hex_roundtrip() {
+        let bloom = Bloom::of(WETH);
+        let s = bloom.to_string();
+        assert_eq!(s.len(), 514);
+        assert!(s.starts_with("0x0000"));
+        assert_eq!(s.parse::<Bloom>().unwrap(), bloom);
+        assert_eq!(format!("{bloom:x}"), s[2..]);
+        assert_eq!(format!("{bloom:#X}")[2..], s[2..].to_uppercase());
+        assert_eq!("0x00".parse::<Bloom>(), Err(ParseBloomError::InvalidLength));
+        assert!(Bloom::default().is_zero());
+    }
+}
@@ -0,0 +1,71 @@
+//! Serde serialization implementation for blooms.
+
+use super::Bloom;
+use core::fmt::{self, Formatter};
+use serde::{
+    de::{self, Deserializer, Visitor},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
+
+impl<'de> Deserialize<'de> for Bloom {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BloomVisitor)
+    }
+}
+
+struct BloomVisitor;
+
+impl<'de> Visitor<'de> for BloomVisitor {
+    type Value = Bloom;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a `0x`-prefixed 256-byte hex string")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        s.strip_prefix("0x")
+            .ok_or_else(|| de::Error::custom("missing `0x`-prefix"))?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Bloom {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let buffer = self.fmt_buffer(Default::default());
+        serializer.serialize_str(buffer.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::value::{self, BorrowedStrDeserializer};
+
+    #[test]
+    fn serde_roundtrip() {
+        let mut bloom = Bloom::default();
+        bloom.0[0] = 0x80;
+        bloom.0[255] = 0x01;
+        let json = serde_json::to_string(&bloom).unwrap();
+        assert_eq!(json, format!("\"{bloom}\""));
+        assert_eq!(serde_json::from_str::<Bloom>(&json).unwrap(), bloom);
+    }
+
+    #[test]
+    fn deserialize_bloom_requires_0x_prefix() {
+        let without_prefix = "00".repeat(256);
+        let deserializer = BorrowedStrDeserializer::<value::Error>::new(&without_prefix);
+        assert!(Bloom::deserialize(deserializer).is_err());
+    }
+}
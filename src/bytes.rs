@@ -0,0 +1,51 @@
+//! Hex encoding of variable-length byte strings.
+
+use crate::hex::{self, Alphabet, ParseHexError};
+
+/// Encodes bytes as a `0x`-prefixed lower case hex string.
+pub fn encode(bytes: &[u8]) -> String {
+    let lut = Alphabet::Lower.lut();
+    let mut s = String::with_capacity(2 + bytes.len() * 2);
+    s.push_str("0x");
+    for byte in bytes {
+        s.push(lut[(byte >> 4) as usize] as char);
+        s.push(lut[(byte & 0xf) as usize] as char);
+    }
+    s
+}
+
+/// Decodes a hex string of any even length, with an optional `0x` prefix.
+pub fn decode(s: &str) -> Result<Vec<u8>, ParseHexError> {
+    let (s, ch_offset) = hex::split_prefix(s);
+    if s.len() % 2 != 0 {
+        return Err(ParseHexError::InvalidLength);
+    }
+    (0..s.len() / 2)
+        .map(|i| hex::decode_byte(s, ch_offset, i))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        for bytes in [&[][..], &[0x00], &[0x01, 0x23, 0xab, 0xff]] {
+            let encoded = encode(bytes);
+            assert_eq!(decode(&encoded).unwrap(), bytes);
+            assert_eq!(decode(&encoded[2..]).unwrap(), bytes);
+        }
+        assert_eq!(encode(&[0x01, 0x23, 0xab, 0xff]), "0x0123abff");
+        assert_eq!(decode("0xABcd").unwrap(), [0xab, 0xcd]);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(decode("0x123"), Err(ParseHexError::InvalidLength));
+        assert_eq!(
+            decode("0x12zz"),
+            Err(ParseHexError::InvalidHexCharacter { c: 'z', index: 4 }),
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Log filters.
+//!
+//! This module provides the [`Filter`] type, matching logs with the same
+//! semantics as the `eth_getLogs` JSON-RPC method:
+//! - A log matches the address set if the set is empty or contains the log's
+//!   address.
+//! - Each topic position holds a set of alternatives, where an empty set is a
+//!   wildcard matching any topic at that position.
+//! - A log with fewer topics than the filter has positions never matches, even
+//!   if the extra positions are wildcards.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::{address, digest, filter::Filter, receipt::Log};
+//! let transfer = digest!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+//! let weth = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+//! let filter = Filter::new().address(weth).event(transfer);
+//!
+//! let log = Log {
+//!     address: weth,
+//!     topics: vec![transfer, Default::default(), Default::default()],
+//!     data: vec![],
+//! };
+//! assert!(filter.may_match(&log.bloom()));
+//! assert!(filter.matches(&log));
+//! ```
+
+#[cfg(feature = "serde")]
+mod serde;
+
+use crate::{
+    block::{BlockHash, BlockNumberOrTag},
+    receipt::Log,
+    Address, Bloom, Digest,
+};
+
+/// The maximum number of topics a log can have.
+pub const MAX_TOPICS: usize = 4;
+
+/// A log filter.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Filter {
+    /// The blocks to search for logs.
+    pub block_option: FilterBlockOption,
+    /// The set of addresses to match. An empty set matches logs from any
+    /// address.
+    pub address: Vec<Address>,
+    /// The sets of topics to match at each position. An empty set matches any
+    /// topic at its position.
+    pub topics: Vec<Vec<Digest>>,
+}
+
+/// The blocks to search for logs.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FilterBlockOption {
+    /// An inclusive range of blocks, where unset bounds default to the latest
+    /// block.
+    Range {
+        /// The first block of the range.
+        from_block: Option<BlockNumberOrTag>,
+        /// The last block of the range.
+        to_block: Option<BlockNumberOrTag>,
+    },
+    /// A single block by hash.
+    AtBlockHash(BlockHash),
+}
+
+impl Default for FilterBlockOption {
+    fn default() -> Self {
+        Self::Range {
+            from_block: None,
+            to_block: None,
+        }
+    }
+}
+
+impl Filter {
+    /// Creates a new filter matching all logs in the latest block.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the first block of the filter's range.
+    pub fn from_block(mut self, block: impl Into<BlockNumberOrTag>) -> Self {
+        let to_block = match self.block_option {
+            FilterBlockOption::Range { to_block, .. } => to_block,
+            FilterBlockOption::AtBlockHash(_) => None,
+        };
+        self.block_option = FilterBlockOption::Range {
+            from_block: Some(block.into()),
+            to_block,
+        };
+        self
+    }
+
+    /// Sets the last block of the filter's range.
+    pub fn to_block(mut self, block: impl Into<BlockNumberOrTag>) -> Self {
+        let from_block = match self.block_option {
+            FilterBlockOption::Range { from_block, .. } => from_block,
+            FilterBlockOption::AtBlockHash(_) => None,
+        };
+        self.block_option = FilterBlockOption::Range {
+            from_block,
+            to_block: Some(block.into()),
+        };
+        self
+    }
+
+    /// Restricts the filter to a single block by hash.
+    pub fn at_block_hash(mut self, hash: BlockHash) -> Self {
+        self.block_option = FilterBlockOption::AtBlockHash(hash);
+        self
+    }
+
+    /// Adds an address to the filter's address set.
+    pub fn address(mut self, address: Address) -> Self {
+        self.address.push(address);
+        self
+    }
+
+    /// Adds an alternative topic at the specified position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is not less than [`MAX_TOPICS`].
+    pub fn topic(mut self, position: usize, topic: Digest) -> Self {
+        assert!(position < MAX_TOPICS, "topic position out of range");
+        if self.topics.len() <= position {
+            self.topics.resize(position + 1, Vec::new());
+        }
+        self.topics[position].push(topic);
+        self
+    }
+
+    /// Adds an alternative event signature topic, the first topic of logs
+    /// emitted by non-anonymous events.
+    pub fn event(self, topic: Digest) -> Self {
+        self.topic(0, topic)
+    }
+
+    /// Returns whether or not the filter matches the log's address and topics.
+    ///
+    /// Note that consensus logs do not include the block they were emitted
+    /// in, so the filter's block option is not checked.
+    pub fn matches(&self, log: &Log) -> bool {
+        if !self.address.is_empty() && !self.address.contains(&log.address) {
+            return false;
+        }
+        if self.topics.len() > log.topics.len() {
+            return false;
+        }
+        self.topics
+            .iter()
+            .zip(&log.topics)
+            .all(|(alternatives, topic)| alternatives.is_empty() || alternatives.contains(topic))
+    }
+
+    /// Returns whether or not a block or receipt with the specified logs bloom
+    /// may contain logs matching the filter. A `false` result means that no
+    /// log can match, while a `true` result may be a false positive.
+    pub fn may_match(&self, bloom: &Bloom) -> bool {
+        let address = self.address.is_empty()
+            || self
+                .address
+                .iter()
+                .any(|address| bloom.contains_address(address));
+        address
+            && self.topics.iter().all(|alternatives| {
+                alternatives.is_empty()
+                    || alternatives.iter().any(|topic| bloom.contains_topic(topic))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest, keccak};
+
+    const TRANSFER: Digest =
+        digest!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+    const APPROVAL: Digest = keccak!(b"Approval(address,address,uint256)");
+
+    fn log(address: Address, topics: &[Digest]) -> Log {
+        Log {
+            address,
+            topics: topics.to_vec(),
+            data: vec![],
+        }
+    }
+
+    fn topic(byte: u8) -> Digest {
+        Digest([byte; 32])
+    }
+
+    #[test]
+    fn matches_addresses() {
+        let a = address!("0x1111111111111111111111111111111111111111");
+        let b = address!("0x2222222222222222222222222222222222222222");
+        let c = address!("0x3333333333333333333333333333333333333333");
+
+        let any = Filter::new();
+        let some = Filter::new().address(a).address(b);
+        for (address, expected) in [(a, true), (b, true), (c, false)] {
+            assert!(any.matches(&log(address, &[])));
+            assert_eq!(some.matches(&log(address, &[])), expected);
+        }
+    }
+
+    #[test]
+    fn matches_topics() {
+        let address = address!("0x1111111111111111111111111111111111111111");
+        let filter = Filter::new()
+            .event(TRANSFER)
+            .event(APPROVAL)
+            .topic(2, topic(2));
+
+        for (topics, expected) in [
+            (&[TRANSFER, topic(1), topic(2)][..], true),
+            (&[APPROVAL, topic(9), topic(2), topic(3)], true),
+            (&[TRANSFER, topic(1), topic(3)], false),
+            (&[topic(0), topic(1), topic(2)], false),
+            (&[TRANSFER, topic(1)], false),
+            (&[], false),
+        ] {
+            assert_eq!(filter.matches(&log(address, topics)), expected);
+        }
+    }
+
+    #[test]
+    fn trailing_wildcards_require_topics() {
+        let address = address!("0x1111111111111111111111111111111111111111");
+        let filter = Filter {
+            topics: vec![vec![TRANSFER], vec![], vec![]],
+            ..Default::default()
+        };
+        assert!(!filter.matches(&log(address, &[TRANSFER, topic(1)])));
+        assert!(filter.matches(&log(address, &[TRANSFER, topic(1), topic(2)])));
+    }
+
+    #[test]
+    fn bloom_pre_check() {
+        let a = address!("0x1111111111111111111111111111111111111111");
+        let b = address!("0x2222222222222222222222222222222222222222");
+        let bloom = log(a, &[TRANSFER, topic(1)]).bloom();
+
+        assert!(Filter::new().may_match(&bloom));
+        assert!(Filter::new().address(b).address(a).may_match(&bloom));
+        assert!(Filter::new()
+            .event(APPROVAL)
+            .event(TRANSFER)
+            .may_match(&bloom));
+        assert!(Filter::new().topic(1, topic(1)).may_match(&bloom));
+        assert!(!Filter::new().address(b).may_match(&bloom));
+        assert!(!Filter::new().event(APPROVAL).may_match(&bloom));
+        assert!(!Filter::new()
+            .event(TRANSFER)
+            .topic(1, topic(2))
+            .may_match(&bloom));
+    }
+
+    #[test]
+    fn block_options() {
+        assert_eq!(
+            Filter::new()
+                .from_block(1)
+                .to_block(BlockNumberOrTag::Finalized)
+                .block_option,
+            FilterBlockOption::Range {
+                from_block: Some(BlockNumberOrTag::Number(1)),
+                to_block: Some(BlockNumberOrTag::Finalized),
+            }
+        );
+        assert_eq!(
+            Filter::new()
+                .from_block(1)
+                .at_block_hash(topic(1))
+                .block_option,
+            FilterBlockOption::AtBlockHash(topic(1)),
+        );
+    }
+}
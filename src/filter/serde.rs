@@ -0,0 +1,207 @@
+//! Serialization of log filters in the `eth_getLogs` JSON-RPC format.
+
+use super::{Filter, FilterBlockOption, MAX_TOPICS};
+use crate::{
+    block::{BlockHash, BlockNumberOrTag},
+    Address, Digest,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The JSON-RPC representation of a log filter.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Json {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    from_block: Option<BlockNumberOrTag>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to_block: Option<BlockNumberOrTag>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    block_hash: Option<BlockHash>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<OneOrMany<Address>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    topics: Option<Vec<Option<OneOrMany<Digest>>>>,
+}
+
+/// A single value or an array of values. Filters accept single values as a
+/// shorthand for a set containing only that value.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn new(mut values: Vec<T>) -> Option<Self> {
+        match values.len() {
+            0 => None,
+            1 => values.pop().map(Self::One),
+            _ => Some(Self::Many(values)),
+        }
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::One(value) => vec![value],
+            Self::Many(values) => values,
+        }
+    }
+}
+
+impl Serialize for Filter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (from_block, to_block, block_hash) = match self.block_option {
+            FilterBlockOption::Range {
+                from_block,
+                to_block,
+            } => (from_block, to_block, None),
+            FilterBlockOption::AtBlockHash(hash) => (None, None, Some(hash)),
+        };
+        Json {
+            from_block,
+            to_block,
+            block_hash,
+            address: OneOrMany::new(self.address.clone()),
+            topics: (!self.topics.is_empty()).then(|| {
+                self.topics
+                    .iter()
+                    .map(|topics| OneOrMany::new(topics.clone()))
+                    .collect()
+            }),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Filter {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let json = Json::deserialize(deserializer)?;
+        let block_option = match (json.from_block, json.to_block, json.block_hash) {
+            (from_block, to_block, None) => FilterBlockOption::Range {
+                from_block,
+                to_block,
+            },
+            (None, None, Some(hash)) => FilterBlockOption::AtBlockHash(hash),
+            _ => {
+                return Err(de::Error::custom(
+                    "cannot specify both blockHash and fromBlock/toBlock",
+                ))
+            }
+        };
+        let topics = json.topics.unwrap_or_default();
+        if topics.len() > MAX_TOPICS {
+            return Err(de::Error::invalid_length(
+                topics.len(),
+                &"at most 4 topic positions",
+            ));
+        }
+        Ok(Self {
+            block_option,
+            address: json.address.map(OneOrMany::into_vec).unwrap_or_default(),
+            topics: topics
+                .into_iter()
+                .map(|topics| topics.map(OneOrMany::into_vec).unwrap_or_default())
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest};
+    use serde_json::json;
+
+    #[test]
+    fn roundtrip() {
+        let filter = Filter::new()
+            .from_block(0x10)
+            .to_block(0x20)
+            .address(address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"))
+            .event(digest!(
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            ))
+            .topic(2, Digest([1; 32]))
+            .topic(2, Digest([2; 32]));
+        let json = json!({
+            "fromBlock": "0x10",
+            "toBlock": "0x20",
+            "address": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+            "topics": [
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                null,
+                [
+                    "0x0101010101010101010101010101010101010101010101010101010101010101",
+                    "0x0202020202020202020202020202020202020202020202020202020202020202",
+                ],
+            ],
+        });
+
+        assert_eq!(serde_json::to_value(&filter).unwrap(), json);
+        assert_eq!(serde_json::from_value::<Filter>(json).unwrap(), filter);
+    }
+
+    #[test]
+    fn deserialize_shorthands() {
+        let a = address!("0x1111111111111111111111111111111111111111");
+        let b = address!("0x2222222222222222222222222222222222222222");
+        let hash = Digest([3; 32]);
+
+        assert_eq!(
+            serde_json::from_value::<Filter>(json!({})).unwrap(),
+            Filter::new(),
+        );
+        assert_eq!(
+            serde_json::from_value::<Filter>(json!({
+                "blockHash": hash,
+                "address": [a, b],
+                "topics": [[], null],
+            }))
+            .unwrap(),
+            Filter {
+                block_option: FilterBlockOption::AtBlockHash(hash),
+                address: vec![a, b],
+                topics: vec![vec![], vec![]],
+            },
+        );
+        assert_eq!(
+            serde_json::from_value::<Filter>(json!({
+                "fromBlock": "earliest",
+                "toBlock": "latest",
+            }))
+            .unwrap(),
+            Filter::new()
+                .from_block(BlockNumberOrTag::Earliest)
+                .to_block(BlockNumberOrTag::Latest),
+        );
+        assert_eq!(
+            serde_json::from_value::<Filter>(json!({
+                "fromBlock": null,
+                "address": null,
+                "topics": null,
+            }))
+            .unwrap(),
+            Filter::new(),
+        );
+    }
+
+    #[test]
+    fn invalid_filters() {
+        for json in [
+            json!({ "fromBlock": "0x1", "blockHash": Digest::default() }),
+            json!({ "toBlock": "1" }),
+            json!({ "toBlock": "head" }),
+            json!({ "topics": [null, null, null, null, null] }),
+            json!({ "address": "0x1234" }),
+        ] {
+            assert!(serde_json::from_value::<Filter>(json).is_err());
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! Gas and fee calculations.
+//!
+//! This module implements the fee market formulas of [EIP-1559] and
+//! [EIP-4844], as well as the intrinsic gas of transactions, including the
+//! calldata floor introduced by [EIP-7623] in the Prague fork.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::gas;
+//! // A full block increases the base fee by 12.5%.
+//! assert_eq!(gas::next_base_fee(30_000_000, 15_000_000, 8_000_000_000), 9_000_000_000);
+//!
+//! // Transactions pay the base fee plus a capped priority fee.
+//! let price = gas::effective_gas_price(30_000_000_000, 2_000_000_000, 9_000_000_000);
+//! assert_eq!(price, Some(11_000_000_000));
+//! ```
+//!
+//! [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+//! [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+//! [EIP-7623]: https://eips.ethereum.org/EIPS/eip-7623
+
+use crate::{tx::AccessList, U256};
+
+/// The bound on the change of the base fee from one block to the next, as the
+/// denominator of a fraction of the parent base fee.
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// The ratio of the gas limit to the gas target of a block.
+pub const ELASTICITY_MULTIPLIER: u64 = 2;
+
+/// The minimum blob base fee.
+pub const MIN_BLOB_BASE_FEE: u64 = 1;
+
+/// The blob base fee update fraction used from the Cancun fork.
+pub const BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN: u64 = 3_338_477;
+
+/// The blob base fee update fraction used from the Prague fork.
+pub const BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE: u64 = 5_007_716;
+
+/// The base gas cost of every transaction.
+pub const TX_BASE_GAS: u64 = 21_000;
+
+/// The additional gas cost of contract creation transactions.
+pub const TX_CREATE_GAS: u64 = 32_000;
+
+/// The gas cost per calldata token, where zero bytes count as one token and
+/// non-zero bytes as four tokens.
+pub const STANDARD_TOKEN_GAS: u64 = 4;
+
+/// The [EIP-7623] floor gas cost per calldata token.
+///
+/// [EIP-7623]: https://eips.ethereum.org/EIPS/eip-7623
+pub const FLOOR_TOKEN_GAS: u64 = 10;
+
+/// The [EIP-3860] gas cost per 32-byte word of initcode.
+///
+/// [EIP-3860]: https://eips.ethereum.org/EIPS/eip-3860
+pub const INITCODE_WORD_GAS: u64 = 2;
+
+/// The gas cost per address in a transaction's access list.
+pub const ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+
+/// The gas cost per storage key in a transaction's access list.
+pub const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
+
+/// The [EIP-7702] gas cost per authorization, charged up front as if the
+/// authorizing account were empty.
+///
+/// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+pub const AUTHORIZATION_GAS: u64 = 25_000;
+
+/// Returns the gas target of a block with the specified gas limit.
+pub fn gas_target(gas_limit: u64) -> u64 {
+    gas_limit / ELASTICITY_MULTIPLIER
+}
+
+/// Computes the base fee of the next block from its parent's gas used, gas
+/// target and base fee.
+///
+/// The base fee increases by at least 1 wei when the parent block used more
+/// than its target.
+pub fn next_base_fee(parent_gas_used: u64, parent_gas_target: u64, parent_base_fee: u64) -> u64 {
+    if parent_gas_target == 0 || parent_gas_used == parent_gas_target {
+        return parent_base_fee;
+    }
+
+    let delta = |gas_delta: u64| {
+        let delta = u128::from(parent_base_fee) * u128::from(gas_delta)
+            / u128::from(parent_gas_target)
+            / u128::from(BASE_FEE_MAX_CHANGE_DENOMINATOR);
+        u64::try_from(delta).unwrap_or(u64::MAX)
+    };
+    if parent_gas_used > parent_gas_target {
+        let delta = delta(parent_gas_used - parent_gas_target).max(1);
+        parent_base_fee.saturating_add(delta)
+    } else {
+        let delta = delta(parent_gas_target - parent_gas_used);
+        parent_base_fee.saturating_sub(delta)
+    }
+}
+
+/// Computes the effective gas price paid by a dynamic fee transaction, the
+/// base fee plus the priority fee capped by the maximum fee.
+///
+/// Returns `None` if the maximum fee does not cover the base fee, in which
+/// case the transaction cannot be included in the block.
+pub fn effective_gas_price(
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+    base_fee: u64,
+) -> Option<u128> {
+    let priority_fee = effective_priority_fee(max_fee_per_gas, max_priority_fee_per_gas, base_fee)?;
+    Some(u128::from(base_fee) + priority_fee)
+}
+
+/// Computes the priority fee per gas received by the block builder from a
+/// dynamic fee transaction.
+///
+/// Returns `None` if the maximum fee does not cover the base fee, in which
+/// case the transaction cannot be included in the block.
+pub fn effective_priority_fee(
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+    base_fee: u64,
+) -> Option<u128> {
+    let headroom = max_fee_per_gas.checked_sub(u128::from(base_fee))?;
+    Some(max_priority_fee_per_gas.min(headroom))
+}
+
+/// Computes the blob base fee from a block's excess blob gas and the blob
+/// base fee update fraction of the active fork.
+///
+/// Returns `None` if the update fraction is zero.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{gas, U256};
+/// assert_eq!(
+///     gas::blob_base_fee(0, gas::BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE),
+///     Some(U256::ONE),
+/// );
+/// assert_eq!(gas::blob_base_fee(0, 0), None);
+/// ```
+pub fn blob_base_fee(excess_blob_gas: u64, update_fraction: u64) -> Option<U256> {
+    if update_fraction == 0 {
+        return None;
+    }
+    Some(fake_exponential(
+        U256::from(MIN_BLOB_BASE_FEE),
+        U256::from(excess_blob_gas),
+        U256::from(update_fraction),
+    ))
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` using a Taylor
+/// expansion, as specified by EIP-4844. The result saturates on overflow.
+///
+/// The `denominator` must be non-zero.
+fn fake_exponential(factor: U256, numerator: U256, denominator: U256) -> U256 {
+    let mut output = U256::ZERO;
+    let mut accumulator = factor * denominator;
+    let mut i = U256::ONE;
+    while accumulator > 0 {
+        output = match output.checked_add(accumulator) {
+            Some(output) => output,
+            None => return U256::MAX,
+        };
+        accumulator = match accumulator.checked_mul(numerator) {
+            Some(accumulator) => accumulator / (denominator * i),
+            None => return U256::MAX,
+        };
+        i += 1;
+    }
+    output / denominator
+}
+
+/// The intrinsic gas of a transaction, charged before any execution.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntrinsicGas {
+    /// The standard intrinsic gas, including the base cost, calldata,
+    /// contract creation, access list and authorization costs.
+    pub gas: u64,
+    /// The [EIP-7623] calldata floor. Since the Prague fork, transactions are
+    /// charged at least this amount of gas.
+    ///
+    /// [EIP-7623]: https://eips.ethereum.org/EIPS/eip-7623
+    pub floor: u64,
+}
+
+impl IntrinsicGas {
+    /// Returns the minimum gas limit of the transaction since the Prague fork,
+    /// the larger of its intrinsic gas and calldata floor.
+    pub fn min_gas_limit(&self) -> u64 {
+        self.gas.max(self.floor)
+    }
+}
+
+/// Returns the number of calldata tokens, where zero bytes count as one token
+/// and non-zero bytes as four tokens.
+pub fn calldata_tokens(input: &[u8]) -> u64 {
+    input
+        .iter()
+        .map(|&byte| if byte == 0 { 1 } else { 4 })
+        .sum()
+}
+
+/// Computes the intrinsic gas of a transaction from its input, whether or not
+/// it creates a contract, its access list and its number of [EIP-7702]
+/// authorizations.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{gas, tx::AccessList};
+/// let intrinsic = gas::intrinsic_gas(&[0, 1], false, &AccessList::default(), 0);
+/// assert_eq!(intrinsic.gas, 21_000 + 4 + 16);
+/// assert_eq!(intrinsic.floor, 21_000 + 10 * 5);
+/// ```
+///
+/// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+pub fn intrinsic_gas(
+    input: &[u8],
+    create: bool,
+    access_list: &AccessList,
+    authorizations: usize,
+) -> IntrinsicGas {
+    let tokens = calldata_tokens(input);
+    let mut gas = TX_BASE_GAS + tokens * STANDARD_TOKEN_GAS;
+    if create {
+        let words = (input.len() as u64).div_ceil(32);
+        gas += TX_CREATE_GAS + words * INITCODE_WORD_GAS;
+    }
+    gas += access_list.0.len() as u64 * ACCESS_LIST_ADDRESS_GAS
+        + access_list.storage_key_count() as u64 * ACCESS_LIST_STORAGE_KEY_GAS
+        + authorizations as u64 * AUTHORIZATION_GAS;
+
+    IntrinsicGas {
+        gas,
+        floor: TX_BASE_GAS + tokens * FLOOR_TOKEN_GAS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        address,
+        tx::{
+            AccessListItem, Eip1559Transaction, Eip7702Transaction, SignedAuthorization,
+            Transaction,
+        },
+        uint, Digest,
+    };
+
+    #[test]
+    fn base_fee_updates() {
+        for (gas_used, gas_target, base_fee, next) in [
+            (15_000_000, 15_000_000, 1_000_000_000, 1_000_000_000),
+            (30_000_000, 15_000_000, 1_000_000_000, 1_125_000_000),
+            (0, 15_000_000, 1_000_000_000, 875_000_000),
+            (20_000_000, 15_000_000, 1_000_000_000, 1_041_666_666),
+            (10_000_000, 15_000_000, 1_000_000_000, 958_333_334),
+            (15_000_001, 15_000_000, 7, 8),
+            (0, 15_000_000, 7, 7),
+            (30_000_000, 15_000_000, u64::MAX, u64::MAX),
+        ] {
+            assert_eq!(next_base_fee(gas_used, gas_target, base_fee), next);
+        }
+        assert_eq!(gas_target(30_000_000), 15_000_000);
+    }
+
+    #[test]
+    fn effective_fees() {
+        for (max_fee, max_priority_fee, base_fee, price, priority_fee) in [
+            (100, 10, 50, Some(60), Some(10)),
+            (55, 10, 50, Some(55), Some(5)),
+            (50, 10, 50, Some(50), Some(0)),
+            (49, 10, 50, None, None),
+        ] {
+            assert_eq!(
+                effective_gas_price(max_fee, max_priority_fee, base_fee),
+                price
+            );
+            assert_eq!(
+                effective_priority_fee(max_fee, max_priority_fee, base_fee),
+                priority_fee
+            );
+        }
+    }
+
+    #[test]
+    fn blob_base_fees() {
+        for (excess_blob_gas, update_fraction, fee) in [
+            (0, BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN, uint!("1")),
+            (2_314_057, BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN, uint!("1")),
+            (2_314_058, BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN, uint!("2")),
+            (
+                10_000_000,
+                BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN,
+                uint!("19"),
+            ),
+            (10_000_000, BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE, uint!("7")),
+            (
+                100_000_000,
+                BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE,
+                uint!("470442149"),
+            ),
+        ] {
+            assert_eq!(blob_base_fee(excess_blob_gas, update_fraction), Some(fee));
+        }
+        assert_eq!(blob_base_fee(1_000_000, 0), None);
+    }
+
+    #[test]
+    fn intrinsic_gas_costs() {
+        let access_list = AccessList(vec![
+            AccessListItem {
+                address: address!("0x1111111111111111111111111111111111111111"),
+                storage_keys: vec![Digest::default(), Digest([1; 32])],
+            },
+            AccessListItem {
+                address: address!("0x2222222222222222222222222222222222222222"),
+                storage_keys: vec![],
+            },
+        ]);
+
+        assert_eq!(
+            intrinsic_gas(&[], false, &AccessList::default(), 0),
+            IntrinsicGas {
+                gas: 21_000,
+                floor: 21_000,
+            }
+        );
+        assert_eq!(
+            intrinsic_gas(&[0; 33], true, &AccessList::default(), 0),
+            IntrinsicGas {
+                gas: 21_000 + 33 * 4 + 32_000 + 2 * 2,
+                floor: 21_000 + 33 * 10,
+            }
+        );
+        assert_eq!(
+            intrinsic_gas(&[1, 0, 0], false, &access_list, 2),
+            IntrinsicGas {
+                gas: 21_000 + 16 + 8 + 2 * 2_400 + 2 * 1_900 + 2 * 25_000,
+                floor: 21_000 + 6 * 10,
+            }
+        );
+    }
+
+    #[test]
+    fn calldata_floor() {
+        let input = [0xff; 1000];
+        let intrinsic = intrinsic_gas(&input, false, &AccessList::default(), 0);
+        assert_eq!(intrinsic.gas, 21_000 + 16_000);
+        assert_eq!(intrinsic.floor, 21_000 + 40_000);
+        assert_eq!(intrinsic.min_gas_limit(), 61_000);
+    }
+
+    #[test]
+    fn transaction_intrinsic_gas() {
+        let create = Transaction::from(Eip1559Transaction {
+            input: vec![0xfe; 64],
+            ..Default::default()
+        });
+        assert_eq!(
+            create.intrinsic_gas().gas,
+            21_000 + 64 * 16 + 32_000 + 2 * 2
+        );
+
+        let set_code = Transaction::from(Eip7702Transaction {
+            authorization_list: vec![SignedAuthorization::default(); 3],
+            ..Default::default()
+        });
+        assert_eq!(set_code.intrinsic_gas().gas, 21_000 + 3 * 25_000);
+    }
+}
@@ -0,0 +1,264 @@
+//! BIP-39 mnemonic phrases.
+
+use super::{english::WORDS, DerivationPath, ExtendedPrivateKey};
+use crate::{signer::LocalSigner, Address};
+use sha2::{Digest as _, Sha256, Sha512};
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+use zeroize::Zeroizing;
+
+/// A [BIP-39] mnemonic phrase using the English wordlist.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{address, hd::Mnemonic};
+/// let mnemonic = "test test test test test test test test test test test junk"
+///     .parse::<Mnemonic>()
+///     .unwrap();
+/// assert_eq!(
+///     mnemonic.address(0),
+///     address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+/// );
+/// ```
+///
+/// [BIP-39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+#[derive(Clone, Eq, PartialEq)]
+pub struct Mnemonic {
+    phrase: Zeroizing<String>,
+}
+
+impl Mnemonic {
+    /// Creates a mnemonic phrase encoding the specified entropy. The entropy
+    /// must be 16, 20, 24, 28 or 32 bytes long, resulting in a phrase of 12,
+    /// 15, 18, 21 or 24 words respectively.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::hd::Mnemonic;
+    /// assert_eq!(
+    ///     Mnemonic::from_entropy(&[0; 16]).unwrap().phrase(),
+    ///     "abandon abandon abandon abandon abandon abandon \
+    ///      abandon abandon abandon abandon abandon about",
+    /// );
+    /// ```
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+            return Err(MnemonicError::InvalidEntropyLength(entropy.len()));
+        }
+
+        // The entropy is followed by a checksum of `len / 4` bits, taken from
+        // its SHA-256 hash, and split into 11-bit words.
+        let mut bits = Zeroizing::new([0_u8; 33]);
+        bits[..entropy.len()].copy_from_slice(entropy);
+        bits[entropy.len()] = Sha256::digest(entropy)[0];
+
+        let count = entropy.len() * 3 / 4;
+        let mut phrase = Zeroizing::new(String::new());
+        for i in 0..count {
+            if i > 0 {
+                phrase.push(' ');
+            }
+            phrase.push_str(WORDS[read_bits(&bits[..], i * 11, 11)]);
+        }
+        Ok(Self { phrase })
+    }
+
+    /// Returns the mnemonic phrase, with words separated by single spaces.
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// Computes the 64-byte seed for the mnemonic and an optional passphrase.
+    /// An empty passphrase is used when none is specified.
+    ///
+    /// Note that the passphrase is used as is, so it should already be NFKD
+    /// normalized if it contains non-ASCII characters.
+    pub fn to_seed(&self, passphrase: &str) -> Zeroizing<[u8; 64]> {
+        let salt = Zeroizing::new(format!("mnemonic{passphrase}"));
+        let mut seed = Zeroizing::new([0; 64]);
+        pbkdf2::pbkdf2_hmac::<Sha512>(self.phrase.as_bytes(), salt.as_bytes(), 2048, &mut seed[..]);
+        seed
+    }
+
+    /// Returns the signer for the account at the specified index, using the
+    /// standard Ethereum derivation path `m/44'/60'/0'/0/{index}` and an empty
+    /// passphrase. This matches the default accounts of wallets and
+    /// development nodes like Anvil and Hardhat.
+    pub fn signer(&self, index: u32) -> LocalSigner {
+        ExtendedPrivateKey::from_seed(&self.to_seed("")[..])
+            .and_then(|key| key.derive_path(&DerivationPath::ethereum(index)))
+            .expect("invalid BIP-32 keys are astronomically unlikely")
+            .signer()
+    }
+
+    /// Returns the address of the account at the specified index, using the
+    /// standard Ethereum derivation path `m/44'/60'/0'/0/{index}` and an empty
+    /// passphrase.
+    pub fn address(&self, index: u32) -> Address {
+        self.signer(index).address()
+    }
+}
+
+/// Reads a big-endian integer of `len` bits starting at bit `offset`.
+fn read_bits(bytes: &[u8], offset: usize, len: usize) -> usize {
+    (offset..offset + len).fold(0, |value, bit| {
+        (value << 1) | ((bytes[bit / 8] >> (7 - bit % 8)) & 1) as usize
+    })
+}
+
+/// Writes the `len` lowest bits of a value at bit `offset`.
+fn write_bits(bytes: &mut [u8], offset: usize, len: usize, value: usize) {
+    for i in 0..len {
+        let bit = offset + i;
+        if (value >> (len - 1 - i)) & 1 == 1 {
+            bytes[bit / 8] |= 0x80 >> (bit % 8);
+        }
+    }
+}
+
+impl Debug for Mnemonic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Mnemonic").finish_non_exhaustive()
+    }
+}
+
+impl Display for Mnemonic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(self.phrase())
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = MnemonicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let count = s.split_whitespace().count();
+        if !matches!(count, 12 | 15 | 18 | 21 | 24) {
+            return Err(MnemonicError::InvalidWordCount(count));
+        }
+
+        let mut bits = Zeroizing::new([0_u8; 33]);
+        for (i, word) in s.split_whitespace().enumerate() {
+            let index = WORDS
+                .binary_search(&word)
+                .map_err(|_| MnemonicError::InvalidWord(i))?;
+            write_bits(&mut bits[..], i * 11, 11, index);
+        }
+
+        let len = count * 4 / 3;
+        let checksum_bits = len / 4;
+        let checksum = read_bits(&bits[..], len * 8, checksum_bits);
+        if checksum != (Sha256::digest(&bits[..len])[0] >> (8 - checksum_bits)) as usize {
+            return Err(MnemonicError::InvalidChecksum);
+        }
+
+        Self::from_entropy(&bits[..len])
+    }
+}
+
+/// Represents an error creating or parsing a mnemonic phrase.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MnemonicError {
+    /// The entropy does not have a valid length.
+    InvalidEntropyLength(usize),
+    /// The phrase does not have a valid number of words.
+    InvalidWordCount(usize),
+    /// The word at the specified position is not in the wordlist.
+    InvalidWord(usize),
+    /// The phrase checksum does not match.
+    InvalidChecksum,
+}
+
+impl Display for MnemonicError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidEntropyLength(len) => write!(f, "invalid entropy length {len}"),
+            Self::InvalidWordCount(count) => write!(f, "invalid mnemonic word count {count}"),
+            Self::InvalidWord(index) => write!(f, "invalid mnemonic word at position {index}"),
+            Self::InvalidChecksum => write!(f, "invalid mnemonic checksum"),
+        }
+    }
+}
+
+impl std::error::Error for MnemonicError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, tests::hex};
+
+    #[test]
+    fn bip39_test_vectors() {
+        // Test vectors from the reference implementation, using the passphrase
+        // "TREZOR".
+        for (entropy, phrase, seed) in [
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+                "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+            ),
+        ] {
+            let mnemonic = Mnemonic::from_entropy(&hex(entropy)).unwrap();
+            assert_eq!(mnemonic.phrase(), phrase);
+            assert_eq!(phrase.parse::<Mnemonic>().unwrap(), mnemonic);
+            assert_eq!(mnemonic.to_seed("TREZOR")[..], hex(seed));
+        }
+    }
+
+    #[test]
+    fn anvil_accounts() {
+        let mnemonic = "test test test test test test test test test test test junk"
+            .parse::<Mnemonic>()
+            .unwrap();
+        for (index, address) in [
+            (0, address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")),
+            (1, address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8")),
+            (2, address!("0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC")),
+        ] {
+            assert_eq!(mnemonic.address(index), address);
+        }
+        assert_eq!(
+            mnemonic.signer(0).signing_key().to_bytes()[..],
+            hex("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"),
+        );
+    }
+
+    #[test]
+    fn invalid_mnemonics() {
+        for (phrase, err) in [
+            ("test test test", MnemonicError::InvalidWordCount(3)),
+            (
+                "test test test test test test test test test test test test",
+                MnemonicError::InvalidChecksum,
+            ),
+            (
+                "test test test test test test test test test test test Junk",
+                MnemonicError::InvalidWord(11),
+            ),
+        ] {
+            assert_eq!(phrase.parse::<Mnemonic>().unwrap_err(), err);
+        }
+        assert_eq!(
+            Mnemonic::from_entropy(&[0; 17]).unwrap_err(),
+            MnemonicError::InvalidEntropyLength(17),
+        );
+    }
+}
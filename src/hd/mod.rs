@@ -0,0 +1,286 @@
+//! Hierarchical deterministic wallets.
+//!
+//! This module implements [BIP-39] mnemonic phrases and [BIP-32] private key
+//! derivation, for deriving accounts along [BIP-44] paths like the standard
+//! Ethereum `m/44'/60'/0'/0/{index}` path.
+//!
+//! [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! [BIP-39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+//! [BIP-44]: https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki
+
+mod english;
+mod mnemonic;
+
+pub use self::mnemonic::{Mnemonic, MnemonicError};
+use crate::{signer::LocalSigner, Address};
+use hmac::{Hmac, Mac};
+use k256::{
+    ecdsa::SigningKey,
+    elliptic_curve::{ff::PrimeField, FieldBytes},
+    NonZeroScalar, Scalar, Secp256k1,
+};
+use sha2::Sha512;
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+use zeroize::Zeroizing;
+
+/// The offset of hardened child key indices.
+pub const HARDENED: u32 = 1 << 31;
+
+/// A [BIP-32] derivation path.
+///
+/// Paths are formatted and parsed as `m/44'/60'/0'/0/0`, where hardened
+/// indices are suffixed with a `'`. Parsing additionally accepts a `h` suffix
+/// for hardened indices.
+///
+/// [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DerivationPath(pub Vec<u32>);
+
+impl DerivationPath {
+    /// Returns the standard Ethereum [BIP-44] derivation path
+    /// `m/44'/60'/0'/0/{index}` for the account at the specified index.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::hd::DerivationPath;
+    /// assert_eq!(DerivationPath::ethereum(3).to_string(), "m/44'/60'/0'/0/3");
+    /// ```
+    ///
+    /// [BIP-44]: https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki
+    pub fn ethereum(index: u32) -> Self {
+        Self(vec![44 | HARDENED, 60 | HARDENED, HARDENED, 0, index])
+    }
+}
+
+impl Display for DerivationPath {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("m")?;
+        for index in &self.0 {
+            match index.checked_sub(HARDENED) {
+                Some(index) => write!(f, "/{index}'")?,
+                None => write!(f, "/{index}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = ParseDerivationPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.split('/');
+        if components.next() != Some("m") {
+            return Err(ParseDerivationPathError::MissingRoot);
+        }
+        components
+            .enumerate()
+            .map(|(i, component)| {
+                let (index, hardened) = match component
+                    .strip_suffix('\'')
+                    .or_else(|| component.strip_suffix('h'))
+                {
+                    Some(index) => (index, HARDENED),
+                    None => (component, 0),
+                };
+                index
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|index| index & HARDENED == 0)
+                    .map(|index| index | hardened)
+                    .ok_or(ParseDerivationPathError::InvalidIndex(i))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Represents an error parsing a derivation path.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseDerivationPathError {
+    /// The path does not start with the `m` root.
+    MissingRoot,
+    /// The path component at the specified position is not a valid index.
+    InvalidIndex(usize),
+}
+
+impl Display for ParseDerivationPathError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::MissingRoot => write!(f, "derivation path does not start with `m`"),
+            Self::InvalidIndex(i) => write!(f, "invalid derivation path index at position {i}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseDerivationPathError {}
+
+/// A [BIP-32] extended secp256k1 private key.
+///
+/// [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    key: SigningKey,
+    chain_code: Zeroizing<[u8; 32]>,
+}
+
+impl ExtendedPrivateKey {
+    /// Creates the master extended private key for a seed.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, InvalidKeyError> {
+        let mut mac = Hmac::<Sha512>::new_from_slice(b"Bitcoin seed").unwrap();
+        mac.update(seed);
+        Self::from_mac(mac, None)
+    }
+
+    /// Derives the child key at the specified index. Indices greater than or
+    /// equal to [`HARDENED`] derive hardened child keys.
+    pub fn derive_child(&self, index: u32) -> Result<Self, InvalidKeyError> {
+        let mut mac = Hmac::<Sha512>::new_from_slice(&self.chain_code[..]).unwrap();
+        if index & HARDENED != 0 {
+            let secret = Zeroizing::new(<[u8; 32]>::from(self.key.to_bytes()));
+            mac.update(&[0]);
+            mac.update(&secret[..]);
+        } else {
+            mac.update(self.key.verifying_key().to_encoded_point(true).as_bytes());
+        }
+        mac.update(&index.to_be_bytes());
+        Self::from_mac(mac, Some(self.key.as_nonzero_scalar()))
+    }
+
+    /// Derives the descendant key along the specified path.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, InvalidKeyError> {
+        path.0
+            .iter()
+            .try_fold(self.clone(), |key, &index| key.derive_child(index))
+    }
+
+    /// Returns the signing key.
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.key
+    }
+
+    /// Returns the chain code.
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Returns a signer for the extended private key.
+    pub fn signer(&self) -> LocalSigner {
+        LocalSigner::new(self.key.clone())
+    }
+
+    /// Returns the address of the extended private key.
+    pub fn address(&self) -> Address {
+        self.signer().address()
+    }
+
+    /// Computes an extended private key from the HMAC-SHA512 output of a
+    /// derivation, tweaking the parent key if there is one.
+    fn from_mac(
+        mac: Hmac<Sha512>,
+        parent: Option<&NonZeroScalar>,
+    ) -> Result<Self, InvalidKeyError> {
+        let output = Zeroizing::new(<[u8; 64]>::from(mac.finalize().into_bytes()));
+        let (tweak, chain_code) = output.split_at(32);
+
+        let tweak = FieldBytes::<Secp256k1>::from(<[u8; 32]>::try_from(tweak).unwrap());
+        let tweak = Option::<Scalar>::from(Scalar::from_repr(tweak)).ok_or(InvalidKeyError)?;
+        let scalar = match parent {
+            Some(parent) => tweak + parent.as_ref(),
+            None => tweak,
+        };
+        let key =
+            Option::<NonZeroScalar>::from(NonZeroScalar::new(scalar)).ok_or(InvalidKeyError)?;
+
+        Ok(Self {
+            key: SigningKey::from(key),
+            chain_code: Zeroizing::new(chain_code.try_into().unwrap()),
+        })
+    }
+}
+
+impl Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("address", &self.address())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An error indicating that a derived key is invalid. This happens with a
+/// probability lower than 1 in 2^127.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InvalidKeyError;
+
+impl Display for InvalidKeyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid derived key")
+    }
+}
+
+impl std::error::Error for InvalidKeyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::hex;
+
+    #[test]
+    fn bip32_test_vector() {
+        // Test vector 1 from BIP-32.
+        let master =
+            ExtendedPrivateKey::from_seed(&hex("000102030405060708090a0b0c0d0e0f")).unwrap();
+        for (path, chain_code, key) in [
+            (
+                "m",
+                "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            ),
+            (
+                "m/0'",
+                "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            ),
+            (
+                "m/0'/1",
+                "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            ),
+            (
+                "m/0h/1/2h/2/1000000000",
+                "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+                "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+            ),
+        ] {
+            let key_at_path = master.derive_path(&path.parse().unwrap()).unwrap();
+            assert_eq!(key_at_path.chain_code()[..], hex(chain_code));
+            assert_eq!(key_at_path.signing_key().to_bytes()[..], hex(key));
+        }
+    }
+
+    #[test]
+    fn parse_derivation_paths() {
+        assert_eq!(
+            "m/44'/60'/0'/0/7".parse::<DerivationPath>().unwrap(),
+            DerivationPath::ethereum(7),
+        );
+        assert_eq!(
+            "m".parse::<DerivationPath>().unwrap(),
+            DerivationPath::default()
+        );
+        for (path, err) in [
+            ("44'/60'", ParseDerivationPathError::MissingRoot),
+            ("m/44'/x", ParseDerivationPathError::InvalidIndex(1)),
+            ("m/2147483648", ParseDerivationPathError::InvalidIndex(0)),
+            ("m/", ParseDerivationPathError::InvalidIndex(0)),
+        ] {
+            assert_eq!(path.parse::<DerivationPath>().unwrap_err(), err);
+        }
+    }
+}
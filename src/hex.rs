@@ -0,0 +1 @@
+../shared/src/hex.rs
\ No newline at end of file
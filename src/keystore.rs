@@ -0,0 +1,736 @@
+//! Web3 Secret Storage keystores.
+//!
+//! This module implements version 3 of the [Web3 Secret Storage] format, used
+//! for storing encrypted private keys in JSON files by Ethereum clients and
+//! wallets. Both the scrypt and PBKDF2-SHA256 key derivation functions are
+//! supported, with AES-128-CTR encryption.
+//!
+//! [Web3 Secret Storage]: https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/
+
+use crate::{signer::LocalSigner, Address, Hasher};
+use aes::{
+    cipher::{KeyIvInit as _, StreamCipher as _},
+    Aes128,
+};
+use k256::elliptic_curve::rand_core::CryptoRngCore;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::Sha256;
+use std::fmt::{self, Display, Formatter};
+use subtle::ConstantTimeEq as _;
+use zeroize::Zeroizing;
+
+/// AES-128 in big-endian counter mode.
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+/// The length of the derived key: a 16-byte AES key followed by a 16-byte MAC
+/// key.
+const DKLEN: usize = 32;
+
+/// The maximum scrypt memory usage, `128 * r * n` bytes, of supported
+/// keystores. This is 4 times the memory needed by Geth's default parameters.
+const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+
+/// The maximum scrypt parallelization parameter of supported keystores.
+const MAX_SCRYPT_P: u32 = 16;
+
+/// The maximum PBKDF2 iteration count of supported keystores.
+const MAX_PBKDF2_C: u32 = 10_000_000;
+
+/// An encrypted private key.
+///
+/// Keystores are serialized and deserialized in the standard JSON format.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{keystore::{Kdf, Keystore}, signer::LocalSigner};
+/// # use rand_core::OsRng;
+/// let signer = "0x4646464646464646464646464646464646464646464646464646464646464646"
+///     .parse::<LocalSigner>()
+///     .unwrap();
+/// let kdf = Kdf::Scrypt { log_n: 10, r: 8, p: 1 };
+/// let keystore = Keystore::encrypt(&signer, "hunter2", kdf, &mut OsRng).unwrap();
+///
+/// let json = serde_json::to_string(&keystore).unwrap();
+/// let keystore = serde_json::from_str::<Keystore>(&json).unwrap();
+/// assert_eq!(keystore.decrypt("hunter2").unwrap().address(), signer.address());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Keystore {
+    id: String,
+    address: Option<Address>,
+    kdf: Kdf,
+    salt: Vec<u8>,
+    iv: [u8; 16],
+    ciphertext: Vec<u8>,
+    mac: [u8; 32],
+}
+
+/// A key derivation function, along with its parameters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kdf {
+    /// The scrypt key derivation function, with a CPU/memory cost of
+    /// `n = 2^log_n`, a block size `r` and a parallelization parameter `p`.
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    /// The PBKDF2-HMAC-SHA256 key derivation function, with `c` iterations.
+    Pbkdf2 { c: u32 },
+}
+
+impl Kdf {
+    /// Returns `true` if the parameters are within the limits of supported
+    /// keystores. This prevents keystores from requiring excessive amounts of
+    /// memory or computation in order to be decrypted.
+    fn is_within_limits(&self) -> bool {
+        match *self {
+            Self::Scrypt { log_n, r, p } => {
+                let memory = 1_u64
+                    .checked_shl(log_n.into())
+                    .and_then(|n| n.checked_mul(128 * u64::from(r)));
+                matches!(memory, Some(memory) if memory <= MAX_SCRYPT_MEMORY) && p <= MAX_SCRYPT_P
+            }
+            Self::Pbkdf2 { c } => c > 0 && c <= MAX_PBKDF2_C,
+        }
+    }
+}
+
+impl Default for Kdf {
+    /// Returns the scrypt parameters used by default by Geth.
+    fn default() -> Self {
+        Self::Scrypt {
+            log_n: 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl Keystore {
+    /// Encrypts the private key of a signer with a password, using the
+    /// specified random number generator for the salt, IV and keystore ID.
+    ///
+    /// Returns an error if the key derivation function parameters are invalid.
+    pub fn encrypt(
+        signer: &LocalSigner,
+        password: impl AsRef<[u8]>,
+        kdf: Kdf,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, KeystoreError> {
+        let mut salt = vec![0; 32];
+        let mut iv = [0; 16];
+        let mut id = [0; 16];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+        rng.fill_bytes(&mut id);
+
+        let mut keystore = Self {
+            id: uuid(id),
+            address: Some(signer.address()),
+            kdf,
+            salt,
+            iv,
+            ciphertext: signer.signing_key().to_bytes().to_vec(),
+            mac: [0; 32],
+        };
+        let key = keystore.derive_key(password.as_ref())?;
+        keystore.apply_keystream(&key);
+        keystore.mac = keystore.compute_mac(&key);
+        Ok(keystore)
+    }
+
+    /// Returns the keystore ID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the address of the encrypted private key, if it is included
+    /// in the keystore.
+    pub fn address(&self) -> Option<Address> {
+        self.address
+    }
+
+    /// Returns the key derivation function used by the keystore.
+    pub fn kdf(&self) -> Kdf {
+        self.kdf
+    }
+
+    /// Decrypts the private key with a password.
+    ///
+    /// This verifies the keystore MAC in constant time, and that the address
+    /// of the decrypted private key matches the keystore address, if there is
+    /// one.
+    pub fn decrypt(&self, password: impl AsRef<[u8]>) -> Result<LocalSigner, KeystoreError> {
+        let key = self.derive_key(password.as_ref())?;
+        if !bool::from(self.compute_mac(&key).ct_eq(&self.mac)) {
+            return Err(KeystoreError::InvalidMac);
+        }
+
+        let mut secret = Zeroizing::new(self.ciphertext.clone());
+        Aes128Ctr::new(key[..16].into(), &self.iv.into()).apply_keystream(&mut secret);
+        let signer = <&[u8; 32]>::try_from(&secret[..])
+            .ok()
+            .and_then(|secret| LocalSigner::from_bytes(secret).ok())
+            .ok_or(KeystoreError::InvalidPrivateKey)?;
+
+        if matches!(self.address, Some(address) if address != signer.address()) {
+            return Err(KeystoreError::AddressMismatch);
+        }
+        Ok(signer)
+    }
+
+    /// Derives the encryption key from a password.
+    fn derive_key(&self, password: &[u8]) -> Result<Zeroizing<Vec<u8>>, KeystoreError> {
+        if !self.kdf.is_within_limits() {
+            return Err(KeystoreError::InvalidKdfParams);
+        }
+        let mut key = Zeroizing::new(vec![0; DKLEN]);
+        match self.kdf {
+            Kdf::Scrypt { log_n, r, p } => {
+                let params = scrypt::Params::new(log_n, r, p, DKLEN)
+                    .map_err(|_| KeystoreError::InvalidKdfParams)?;
+                scrypt::scrypt(password, &self.salt, &params, &mut key)
+                    .map_err(|_| KeystoreError::InvalidKdfParams)?;
+            }
+            Kdf::Pbkdf2 { c } => pbkdf2::pbkdf2_hmac::<Sha256>(password, &self.salt, c, &mut key),
+        }
+        Ok(key)
+    }
+
+    /// Encrypts or decrypts the ciphertext in place.
+    fn apply_keystream(&mut self, key: &[u8]) {
+        Aes128Ctr::new(key[..16].into(), &self.iv.into()).apply_keystream(&mut self.ciphertext);
+    }
+
+    /// Computes the MAC of the ciphertext, the Keccak-256 digest of the second
+    /// 16 bytes of the derived key followed by the ciphertext.
+    fn compute_mac(&self, key: &[u8]) -> [u8; 32] {
+        let mut hasher = Hasher::new();
+        hasher.update(&key[16..32]);
+        hasher.update(&self.ciphertext);
+        hasher.finalize().0
+    }
+}
+
+/// Formats random bytes as a version 4 UUID.
+fn uuid(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = crate::bytes::encode(&bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[2..10],
+        &hex[10..14],
+        &hex[14..18],
+        &hex[18..22],
+        &hex[22..],
+    )
+}
+
+/// Represents an error encrypting or decrypting a keystore.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KeystoreError {
+    /// The key derivation function parameters are invalid.
+    InvalidKdfParams,
+    /// The MAC does not match, indicating an incorrect password.
+    InvalidMac,
+    /// The decrypted private key is invalid.
+    InvalidPrivateKey,
+    /// The address of the decrypted private key does not match the keystore
+    /// address.
+    AddressMismatch,
+}
+
+impl Display for KeystoreError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidKdfParams => write!(f, "invalid keystore KDF parameters"),
+            Self::InvalidMac => write!(f, "invalid keystore MAC"),
+            Self::InvalidPrivateKey => write!(f, "invalid keystore private key"),
+            Self::AddressMismatch => write!(f, "keystore address mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for KeystoreError {}
+
+/// The keystore JSON representation.
+#[derive(Deserialize, Serialize)]
+struct Json {
+    #[serde(alias = "Crypto")]
+    crypto: CryptoJson,
+    id: String,
+    #[serde(default, with = "address", skip_serializing_if = "Option::is_none")]
+    address: Option<Address>,
+    version: u32,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CryptoJson {
+    cipher: String,
+    cipherparams: CipherParamsJson,
+    #[serde(with = "hex")]
+    ciphertext: Vec<u8>,
+    kdf: String,
+    kdfparams: KdfParamsJson,
+    #[serde(with = "hex")]
+    mac: Vec<u8>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CipherParamsJson {
+    #[serde(with = "hex")]
+    iv: Vec<u8>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct KdfParamsJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    c: Option<u32>,
+    dklen: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    n: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    p: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prf: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    r: Option<u32>,
+    #[serde(with = "hex")]
+    salt: Vec<u8>,
+}
+
+impl Serialize for Keystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (kdf, kdfparams) = match self.kdf {
+            Kdf::Scrypt { log_n, r, p } => (
+                "scrypt",
+                KdfParamsJson {
+                    c: None,
+                    dklen: DKLEN,
+                    n: Some(1 << log_n),
+                    p: Some(p),
+                    prf: None,
+                    r: Some(r),
+                    salt: self.salt.clone(),
+                },
+            ),
+            Kdf::Pbkdf2 { c } => (
+                "pbkdf2",
+                KdfParamsJson {
+                    c: Some(c),
+                    dklen: DKLEN,
+                    n: None,
+                    p: None,
+                    prf: Some("hmac-sha256".to_owned()),
+                    r: None,
+                    salt: self.salt.clone(),
+                },
+            ),
+        };
+        Json {
+            crypto: CryptoJson {
+                cipher: "aes-128-ctr".to_owned(),
+                cipherparams: CipherParamsJson {
+                    iv: self.iv.to_vec(),
+                },
+                ciphertext: self.ciphertext.clone(),
+                kdf: kdf.to_owned(),
+                kdfparams,
+                mac: self.mac.to_vec(),
+            },
+            id: self.id.clone(),
+            address: self.address,
+            version: 3,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Keystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let json = Json::deserialize(deserializer)?;
+        if json.version != 3 {
+            return Err(de::Error::custom(format!(
+                "unsupported keystore version {}",
+                json.version,
+            )));
+        }
+
+        let crypto = json.crypto;
+        if crypto.cipher != "aes-128-ctr" {
+            return Err(de::Error::custom(format!(
+                "unsupported keystore cipher {}",
+                crypto.cipher,
+            )));
+        }
+
+        let params = crypto.kdfparams;
+        let kdf = match crypto.kdf.as_str() {
+            "scrypt" => {
+                let (Some(n), Some(r), Some(p)) = (params.n, params.r, params.p) else {
+                    return Err(de::Error::custom("missing scrypt parameters"));
+                };
+                if !n.is_power_of_two() {
+                    return Err(de::Error::custom("scrypt n is not a power of two"));
+                }
+                Kdf::Scrypt {
+                    log_n: n.trailing_zeros() as _,
+                    r,
+                    p,
+                }
+            }
+            "pbkdf2" => {
+                if params.prf.as_deref() != Some("hmac-sha256") {
+                    return Err(de::Error::custom("unsupported pbkdf2 prf"));
+                }
+                let c = params.c.ok_or_else(|| de::Error::missing_field("c"))?;
+                Kdf::Pbkdf2 { c }
+            }
+            kdf => return Err(de::Error::custom(format!("unsupported keystore kdf {kdf}"))),
+        };
+        if !kdf.is_within_limits() {
+            return Err(de::Error::custom("keystore kdf parameters exceed limits"));
+        }
+        // The derived key is only ever used as a 16-byte AES key followed by
+        // 16 bytes of MAC key, so reject other lengths before they can be used
+        // to allocate arbitrarily large buffers.
+        if params.dklen != DKLEN {
+            return Err(de::Error::custom("unsupported derived key length"));
+        }
+
+        Ok(Self {
+            id: json.id,
+            address: json.address,
+            kdf,
+            salt: params.salt,
+            iv: crypto
+                .cipherparams
+                .iv
+                .try_into()
+                .map_err(|_| de::Error::invalid_length(0, &"16-byte IV"))?,
+            ciphertext: crypto.ciphertext,
+            mac: crypto
+                .mac
+                .try_into()
+                .map_err(|_| de::Error::invalid_length(0, &"32-byte MAC"))?,
+        })
+    }
+}
+
+/// Serialization of bytes as hex strings without a `0x` prefix.
+mod hex {
+    use serde::{de, Deserialize as _, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let hex = crate::bytes::encode(bytes);
+        serializer.serialize_str(&hex[2..])
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        crate::bytes::decode(&s).map_err(de::Error::custom)
+    }
+}
+
+/// Serialization of addresses as hex strings without a `0x` prefix.
+mod address {
+    use crate::Address;
+    use serde::{de, Deserialize as _, Deserializer, Serializer};
+
+    pub fn serialize<S>(address: &Option<Address>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match address {
+            Some(address) => serializer.serialize_str(&format!("{address:x}")),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Address>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| s.parse().map_err(de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address;
+    use rand_core::{CryptoRng, RngCore};
+    use serde_json::json;
+
+    /// A deterministic "random" number generator for tests.
+    struct TestRng(u8);
+
+    impl RngCore for TestRng {
+        fn next_u32(&mut self) -> u32 {
+            rand_core::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core::impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                self.0 = self.0.wrapping_add(1);
+                *byte = self.0;
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for TestRng {}
+
+    #[test]
+    fn decrypt_pbkdf2_test_vector() {
+        // Test vector from the Web3 Secret Storage specification.
+        let keystore = serde_json::from_value::<Keystore>(json!({
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+                "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "c": 262144,
+                    "dklen": 32,
+                    "prf": "hmac-sha256",
+                    "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd",
+                },
+                "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2",
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3,
+        }))
+        .unwrap();
+
+        let signer = keystore.decrypt("testpassword").unwrap();
+        assert_eq!(
+            signer.signing_key().to_bytes()[..],
+            crate::hex::decode::<32>(
+                "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            keystore.decrypt("wrongpassword").unwrap_err(),
+            KeystoreError::InvalidMac,
+        );
+    }
+
+    #[test]
+    fn decrypt_scrypt_keystore() {
+        let value = json!({
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "15d105ea80eab152e5dd32d7ffd85751" },
+                "ciphertext": "b874448c313171bbe33eed57427c89314e4fab5abe0d9a1b20d95ed28a7d4ef7",
+                "kdf": "scrypt",
+                "kdfparams": {
+                    "dklen": 32,
+                    "n": 8192,
+                    "p": 1,
+                    "r": 8,
+                    "salt": "1eb079d52c476064160da60a9cd0340ee7a40b8380309b0b0b77f8eadd201827",
+                },
+                "mac": "0e8854820ea2d4aa992f09ce2fca7bd04bed148ae48c66c246b5660ded5e3a8c",
+            },
+            "id": "aa6258ce-1592-43d2-aeba-e3c4d3ae2490",
+            "address": "9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+            "version": 3,
+        });
+        let keystore = serde_json::from_value::<Keystore>(value.clone()).unwrap();
+
+        assert_eq!(
+            keystore.kdf(),
+            Kdf::Scrypt {
+                log_n: 13,
+                r: 8,
+                p: 1
+            }
+        );
+        assert_eq!(
+            keystore.decrypt("hunter2").unwrap().address(),
+            address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"),
+        );
+        assert_eq!(serde_json::to_value(&keystore).unwrap(), value);
+
+        let mut mismatched = keystore.clone();
+        mismatched.address = Some(Address([0xee; 20]));
+        assert_eq!(
+            mismatched.decrypt("hunter2").unwrap_err(),
+            KeystoreError::AddressMismatch,
+        );
+    }
+
+    #[test]
+    fn encrypt_roundtrip() {
+        let signer = LocalSigner::from_bytes(&[0x42; 32]).unwrap();
+        for kdf in [
+            Kdf::Scrypt {
+                log_n: 4,
+                r: 8,
+                p: 1,
+            },
+            Kdf::Pbkdf2 { c: 16 },
+        ] {
+            let keystore = Keystore::encrypt(&signer, "password", kdf, &mut TestRng(0)).unwrap();
+            assert_eq!(keystore.id(), "31323334-3536-4738-b93a-3b3c3d3e3f40");
+            assert_eq!(keystore.address(), Some(signer.address()));
+
+            let json = serde_json::to_string(&keystore).unwrap();
+            let keystore = serde_json::from_str::<Keystore>(&json).unwrap();
+            assert_eq!(
+                keystore.decrypt("password").unwrap().address(),
+                signer.address(),
+            );
+        }
+    }
+
+    #[test]
+    fn encrypt_invalid_kdf_params() {
+        let signer = LocalSigner::from_bytes(&[0x42; 32]).unwrap();
+        assert_eq!(
+            Keystore::encrypt(
+                &signer,
+                "password",
+                Kdf::Scrypt {
+                    log_n: 10,
+                    r: 0,
+                    p: 1,
+                },
+                &mut TestRng(0),
+            )
+            .unwrap_err(),
+            KeystoreError::InvalidKdfParams,
+        );
+    }
+
+    #[test]
+    fn unsupported_keystores() {
+        let keystore = |version: u32, cipher: &str, kdf: &str| {
+            json!({
+                "crypto": {
+                    "cipher": cipher,
+                    "cipherparams": { "iv": "00000000000000000000000000000000" },
+                    "ciphertext": "00",
+                    "kdf": kdf,
+                    "kdfparams": { "c": 1, "dklen": 32, "prf": "hmac-sha256", "salt": "" },
+                    "mac": "0000000000000000000000000000000000000000000000000000000000000000",
+                },
+                "id": "",
+                "version": version,
+            })
+        };
+
+        assert!(serde_json::from_value::<Keystore>(keystore(3, "aes-128-ctr", "pbkdf2")).is_ok());
+        for value in [
+            keystore(1, "aes-128-ctr", "pbkdf2"),
+            keystore(3, "aes-128-cbc", "pbkdf2"),
+            keystore(3, "aes-128-ctr", "argon2"),
+            keystore(3, "aes-128-ctr", "scrypt"),
+        ] {
+            assert!(serde_json::from_value::<Keystore>(value).is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_derived_key_lengths() {
+        for dklen in [json!(16), json!(64), json!(1_000_000_000_000_000_000_u64)] {
+            for (kdf, kdfparams) in [
+                (
+                    "pbkdf2",
+                    json!({ "c": 1, "dklen": dklen, "prf": "hmac-sha256", "salt": "" }),
+                ),
+                (
+                    "scrypt",
+                    json!({ "dklen": dklen, "n": 2, "p": 1, "r": 8, "salt": "" }),
+                ),
+            ] {
+                let value = json!({
+                    "crypto": {
+                        "cipher": "aes-128-ctr",
+                        "cipherparams": { "iv": "00000000000000000000000000000000" },
+                        "ciphertext": "00",
+                        "kdf": kdf,
+                        "kdfparams": kdfparams,
+                        "mac": "0000000000000000000000000000000000000000000000000000000000000000",
+                    },
+                    "id": "",
+                    "version": 3,
+                });
+                assert!(serde_json::from_value::<Keystore>(value).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn excessive_kdf_params() {
+        for (kdf, kdfparams) in [
+            (
+                "scrypt",
+                json!({ "dklen": 32, "n": 1_u64 << 40, "p": 1, "r": 8, "salt": "" }),
+            ),
+            (
+                "scrypt",
+                json!({ "dklen": 32, "n": 2, "p": 1, "r": u32::MAX, "salt": "" }),
+            ),
+            (
+                "scrypt",
+                json!({ "dklen": 32, "n": 2, "p": u32::MAX, "r": 8, "salt": "" }),
+            ),
+            (
+                "pbkdf2",
+                json!({ "c": u32::MAX, "dklen": 32, "prf": "hmac-sha256", "salt": "" }),
+            ),
+        ] {
+            let value = json!({
+                "crypto": {
+                    "cipher": "aes-128-ctr",
+                    "cipherparams": { "iv": "00000000000000000000000000000000" },
+                    "ciphertext": "00",
+                    "kdf": kdf,
+                    "kdfparams": kdfparams,
+                    "mac": "0000000000000000000000000000000000000000000000000000000000000000",
+                },
+                "id": "",
+                "version": 3,
+            });
+            assert!(serde_json::from_value::<Keystore>(value).is_err());
+        }
+
+        let signer = LocalSigner::from_bytes(&[0x42; 32]).unwrap();
+        assert_eq!(
+            Keystore::encrypt(
+                &signer,
+                "password",
+                Kdf::Pbkdf2 { c: u32::MAX },
+                &mut TestRng(0),
+            )
+            .unwrap_err(),
+            KeystoreError::InvalidKdfParams,
+        );
+    }
+}
@@ -4,12 +4,105 @@
 //! - [`ethaddr`]: Ethereum public address
 //! - [`ethdigest`]: Ethereum digest and hashing utilities
 //! - [`ethnum`]: 256-bit integers
+//!
+//! Additionally, it provides some common Ethereum utilities built on top of
+//! these primitive types:
+//! - [`abi`]: Solidity contract ABI descriptors
+//! - [`anvil`]: Well-known Anvil and Hardhat development accounts
+//! - [`block`]: Block headers, hashes and identifiers
+//! - [`filter`]: Log filters with `eth_getLogs` semantics
+//! - [`gas`]: EIP-1559 and EIP-4844 fees and intrinsic gas
+//! - `hd`: Hierarchical deterministic wallets from mnemonic phrases
+//! - `keystore`: Web3 Secret Storage encrypted keystores
+//! - [`message`]: EIP-191 and EIP-712 message hashing
+//! - [`proof`]: Merkle Patricia Trie account and storage proof verification
+//! - [`quantity`]: JSON-RPC integer quantities
+//! - [`receipt`]: Transaction receipts and logs
+//! - [`rlp`]: Recursive Length Prefix serialization
+//! - `signatures`: Embedded database of well-known function and event
+//!   signatures
+//! - `signer`: Local private key signer
+//! - [`storage`]: Solidity storage slot computation
+//! - [`trie`]: Merkle Patricia Trie root computation
+//! - [`tx`]: Typed Ethereum transactions
+//! - [`units`]: Ether and token unit conversion
+//!
+//! # Features
+//!
+//! - **_default_ `std`**: Additional integration with Rust standard library
+//!   types. This is required for all modules that make use of heap allocated
+//!   types, such as [`abi`].
+//! - **`derive`**: Derive macros for RLP encoding and decoding of structs.
+//! - **`hd`**: BIP-39 mnemonic phrases and BIP-32 key derivation. This
+//!   implies the `k256` and `std` features.
+//! - **`k256`**: Signature recovery and local private key signing using the
+//!   secp256k1 implementation provided by the [`k256`] crate.
+//! - **`keystore`**: Reading and writing of encrypted JSON keystores. This
+//!   implies the `k256`, `serde` and `std` features.
+//! - **`serde`**: Serialization traits for the [`serde`] crate. This also
+//!   enables deserializing [`abi::Abi`]s from the standard JSON ABI format.
+//! - **`signatures`**: Embed a curated table of well-known function and event
+//!   signatures for offline selector and topic lookups.
+//! - **`sha3`**: Use the Rust Crypto Keccak-256 implementation (provided by the
+//!   [`sha3`] crate) instead of the built-in one.
+//!
+//! [`k256`]: https://crates.io/crates/k256
+//! [`serde`]: https://crates.io/crates/serde
+//! [`sha3`]: https://crates.io/crates/sha3
 
-#![no_std]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+// Allow derive macros, which refer to items through `::ethprim`, to be used
+// within this crate.
+#[cfg(feature = "derive")]
+extern crate self as ethprim;
+
+#[cfg(feature = "std")]
+pub mod abi;
+pub mod anvil;
+#[cfg(feature = "std")]
+pub mod block;
+mod bloom;
+#[cfg(all(feature = "serde", feature = "std"))]
+mod bytes;
+#[cfg(feature = "std")]
+pub mod filter;
+#[cfg(feature = "std")]
+pub mod gas;
+#[cfg(feature = "hd")]
+pub mod hd;
+#[allow(dead_code)]
+mod hex;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+pub mod message;
+#[cfg(feature = "std")]
+pub mod proof;
+pub mod quantity;
+#[cfg(feature = "std")]
+pub mod receipt;
+#[cfg(feature = "std")]
+pub mod rlp;
+mod signature;
+#[cfg(feature = "signatures")]
+pub mod signatures;
+#[cfg(feature = "k256")]
+pub mod signer;
+pub mod storage;
+#[cfg(feature = "std")]
+pub mod trie;
+#[cfg(feature = "std")]
+pub mod tx;
+#[cfg(feature = "std")]
+pub mod units;
+
+pub use bloom::{Bloom, ParseBloomError};
 pub use ethaddr::{address, Address, ParseAddressError};
 pub use ethdigest::{digest, keccak, Digest, Hasher, ParseDigestError};
 pub use ethnum::{int, uint, AsI256, AsU256, I256, U256};
+#[cfg(feature = "k256")]
+pub use signature::RecoverSignatureError;
+pub use signature::{ParseSignatureError, Signature};
 
 /// Re-export of all included crates.
 pub mod meta {
@@ -31,3 +124,15 @@ pub mod prelude {
     pub use ethdigest::Digest;
     pub use ethnum::{AsI256, AsU256, I256, U256};
 }
+
+#[cfg(all(test, feature = "std"))]
+pub(crate) mod tests {
+    /// Decodes a hex string for test vectors, ignoring any whitespace.
+    pub fn hex(s: &str) -> Vec<u8> {
+        let s = s.split_whitespace().collect::<String>();
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+}
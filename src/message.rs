@@ -0,0 +1,204 @@
+//! Hashing of [EIP-191] signed messages and [EIP-712] typed structured data.
+//!
+//! [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+
+use crate::{Address, Digest, Hasher, U256};
+
+/// Computes the [EIP-191] "personal message" digest of a message, as signed by
+/// `personal_sign` and `eth_sign`. This is the Keccak-256 hash of
+/// `"\x19Ethereum Signed Message:\n" || len(message) || message`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{digest, message};
+/// assert_eq!(
+///     message::eip191_hash("Hello World"),
+///     digest!("0xa1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"),
+/// );
+/// ```
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+pub fn eip191_hash(message: impl AsRef<[u8]>) -> Digest {
+    let message = message.as_ref();
+
+    // Format the message length in decimal without allocating.
+    let mut len = [0; 20];
+    let mut i = len.len();
+    let mut n = message.len();
+    loop {
+        i -= 1;
+        len[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+
+    let mut hasher = Hasher::new();
+    hasher.update(b"\x19Ethereum Signed Message:\n");
+    hasher.update(&len[i..]);
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the [EIP-712] digest of typed structured data, given its domain
+/// separator and struct hash. This is the Keccak-256 hash of
+/// `"\x19\x01" || domain_separator || struct_hash`.
+///
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+pub fn eip712_hash(domain_separator: Digest, struct_hash: Digest) -> Digest {
+    let mut hasher = Hasher::new();
+    hasher.update(b"\x19\x01");
+    hasher.update(domain_separator);
+    hasher.update(struct_hash);
+    hasher.finalize()
+}
+
+/// An [EIP-712] signing domain. Only the fields that are set are included in
+/// the `EIP712Domain` type and its hash.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{address, digest, message::Eip712Domain, uint};
+/// let domain = Eip712Domain {
+///     name: Some("Ether Mail"),
+///     version: Some("1"),
+///     chain_id: Some(uint!("1")),
+///     verifying_contract: Some(address!("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC")),
+///     salt: None,
+/// };
+/// assert_eq!(
+///     domain.separator(),
+///     digest!("0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"),
+/// );
+/// ```
+///
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Eip712Domain<'a> {
+    /// The user readable name of the signing domain.
+    pub name: Option<&'a str>,
+    /// The current major version of the signing domain.
+    pub version: Option<&'a str>,
+    /// The chain ID of the network the signing domain is valid on.
+    pub chain_id: Option<U256>,
+    /// The address of the contract that will verify the signature.
+    pub verifying_contract: Option<Address>,
+    /// A disambiguating salt for the protocol.
+    pub salt: Option<Digest>,
+}
+
+impl Eip712Domain<'_> {
+    /// Computes the domain separator, the `hashStruct` of the domain.
+    pub fn separator(&self) -> Digest {
+        let mut fields = [None; 5];
+        let mut type_hasher = Hasher::new();
+        type_hasher.update("EIP712Domain(");
+        let mut field = |i: usize, declaration: &str, value: Option<[u8; 32]>| {
+            if value.is_some() {
+                if fields.iter().any(Option::is_some) {
+                    type_hasher.update(",");
+                }
+                type_hasher.update(declaration);
+                fields[i] = value;
+            }
+        };
+        field(0, "string name", self.name.map(|name| Digest::of(name).0));
+        field(
+            1,
+            "string version",
+            self.version.map(|version| Digest::of(version).0),
+        );
+        field(
+            2,
+            "uint256 chainId",
+            self.chain_id.map(|chain_id| chain_id.to_be_bytes()),
+        );
+        field(
+            3,
+            "address verifyingContract",
+            self.verifying_contract.map(|address| {
+                let mut word = [0; 32];
+                word[12..].copy_from_slice(address.as_ref());
+                word
+            }),
+        );
+        field(4, "bytes32 salt", self.salt.map(|salt| salt.0));
+        type_hasher.update(")");
+
+        let mut hasher = Hasher::new();
+        hasher.update(type_hasher.finalize());
+        for value in fields.iter().flatten() {
+            hasher.update(value);
+        }
+        hasher.finalize()
+    }
+
+    /// Computes the [EIP-712] digest of a struct in this signing domain.
+    ///
+    /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+    pub fn hash_struct(&self, struct_hash: Digest) -> Digest {
+        eip712_hash(self.separator(), struct_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest, uint};
+
+    #[test]
+    fn eip191_message_lengths() {
+        for message in [&b""[..], b"a", &[0xff; 1234]] {
+            let prefixed = [
+                format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes(),
+                message,
+            ]
+            .concat();
+            assert_eq!(eip191_hash(message), Digest::of(prefixed));
+        }
+    }
+
+    #[test]
+    fn eip712_mail_example() {
+        // Example from EIP-712.
+        let domain = Eip712Domain {
+            name: Some("Ether Mail"),
+            version: Some("1"),
+            chain_id: Some(uint!("1")),
+            verifying_contract: Some(address!("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC")),
+            salt: None,
+        };
+        let mail = digest!("0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e");
+
+        assert_eq!(
+            domain.hash_struct(mail),
+            digest!("0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"),
+        );
+    }
+
+    #[test]
+    fn eip712_domain_fields() {
+        let salt = Digest([0x42; 32]);
+        let domain = Eip712Domain {
+            chain_id: Some(uint!("100")),
+            salt: Some(salt),
+            ..Default::default()
+        };
+
+        let type_hash = Digest::of("EIP712Domain(uint256 chainId,bytes32 salt)");
+        let encoded = [type_hash.0, uint!("100").to_be_bytes(), salt.0].concat();
+        assert_eq!(domain.separator(), Digest::of(encoded));
+        assert_eq!(
+            Eip712Domain::default().separator(),
+            Digest::of(Digest::of("EIP712Domain()")),
+        );
+    }
+}
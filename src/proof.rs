@@ -0,0 +1,522 @@
+//! Merkle Patricia Trie proof verification.
+//!
+//! This module verifies account and storage proofs, as returned by the
+//! `eth_getProof` JSON RPC method, against a trusted state root. Proofs are
+//! lists of RLP encoded trie nodes along the path from the root to the proven
+//! key, and can either prove the inclusion of a value in the trie or the
+//! exclusion of a key from it.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::{address, proof, trie::EMPTY_ROOT_HASH};
+//! let account = address!("0x1111111111111111111111111111111111111111");
+//! let proof: &[&[u8]] = &[];
+//! assert_eq!(
+//!     proof::verify_account(EMPTY_ROOT_HASH, account, proof).unwrap(),
+//!     None,
+//! );
+//! ```
+
+use crate::{
+    rlp::{self, Decodable, DecodeError, Encodable, Header, Output},
+    trie::{self, EMPTY_ROOT_HASH, KECCAK_EMPTY},
+    Address, Digest, U256,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// An account in the state trie.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Account {
+    /// The account nonce.
+    pub nonce: u64,
+    /// The account balance in wei.
+    pub balance: U256,
+    /// The root hash of the account's storage trie.
+    pub storage_root: Digest,
+    /// The Keccak-256 digest of the account's code.
+    pub code_hash: Digest,
+}
+
+impl Account {
+    fn fields(&self) -> [&dyn Encodable; 4] {
+        [
+            &self.nonce,
+            &self.balance,
+            &self.storage_root,
+            &self.code_hash,
+        ]
+    }
+}
+
+impl Default for Account {
+    /// Returns an empty account, without any storage or code.
+    fn default() -> Self {
+        Self {
+            nonce: 0,
+            balance: U256::ZERO,
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        }
+    }
+}
+
+impl Encodable for Account {
+    fn encode(&self, out: &mut dyn Output) {
+        rlp::encode_fields(&self.fields(), &[], out)
+    }
+
+    fn length(&self) -> usize {
+        rlp::fields_length(&self.fields())
+    }
+}
+
+impl Decodable for Account {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        rlp::decode_fields(buf, |fields| {
+            Ok(Self {
+                nonce: Decodable::decode(fields)?,
+                balance: Decodable::decode(fields)?,
+                storage_root: Decodable::decode(fields)?,
+                code_hash: Decodable::decode(fields)?,
+            })
+        })
+    }
+}
+
+/// An account proof with storage proofs, as returned by `eth_getProof`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AccountProof {
+    /// The address of the account.
+    pub address: Address,
+    /// The RLP encoded trie nodes from the state root to the account.
+    pub account_proof: Vec<Vec<u8>>,
+    /// The proofs of the requested storage slots of the account.
+    pub storage_proof: Vec<StorageProof>,
+}
+
+/// A storage slot proof.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct StorageProof {
+    /// The storage slot.
+    pub key: Digest,
+    /// The RLP encoded trie nodes from the account's storage root to the slot.
+    pub proof: Vec<Vec<u8>>,
+}
+
+/// The verified contents of an account proof.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct VerifiedAccount {
+    /// The account, or `None` if the proof shows that it does not exist.
+    pub account: Option<Account>,
+    /// The values of the proven storage slots, in the same order as the
+    /// storage proofs.
+    pub storage: Vec<U256>,
+}
+
+impl AccountProof {
+    /// Verifies the account and storage proofs against a state root.
+    ///
+    /// Storage slots of accounts that do not exist are proven against the
+    /// empty trie, and are therefore required to have empty proofs.
+    pub fn verify(&self, state_root: Digest) -> Result<VerifiedAccount, ProofError> {
+        let account = verify_account(state_root, self.address, &self.account_proof)?;
+        let storage_root = account.unwrap_or_default().storage_root;
+        let storage = self
+            .storage_proof
+            .iter()
+            .map(|slot| verify_storage(storage_root, slot.key, &slot.proof))
+            .collect::<Result<_, _>>()?;
+        Ok(VerifiedAccount { account, storage })
+    }
+}
+
+/// Verifies an account proof against a state root, returning the account or
+/// `None` if the proof shows that the account does not exist.
+pub fn verify_account<P>(
+    state_root: Digest,
+    address: Address,
+    proof: &[P],
+) -> Result<Option<Account>, ProofError>
+where
+    P: AsRef<[u8]>,
+{
+    verify_proof(state_root, Digest::of(address), proof)?
+        .map(|value| rlp::decode(&value).map_err(|_| ProofError::InvalidValue))
+        .transpose()
+}
+
+/// Verifies a storage proof against an account's storage root, returning the
+/// value of the storage slot. Since storage slots with a value of zero are
+/// removed from the trie, a proof of exclusion proves a value of zero.
+pub fn verify_storage<P>(
+    storage_root: Digest,
+    slot: Digest,
+    proof: &[P],
+) -> Result<U256, ProofError>
+where
+    P: AsRef<[u8]>,
+{
+    match verify_proof(storage_root, Digest::of(slot), proof)? {
+        Some(value) => rlp::decode(&value).map_err(|_| ProofError::InvalidValue),
+        None => Ok(U256::ZERO),
+    }
+}
+
+/// Verifies a proof for a key against a trie root, returning the value for
+/// the key or `None` if the proof shows that the key is not in the trie.
+///
+/// Note that the key is used as is, so keys of secure tries like the state and
+/// storage tries must already be hashed.
+pub fn verify_proof<K, P>(root: Digest, key: K, proof: &[P]) -> Result<Option<Vec<u8>>, ProofError>
+where
+    K: AsRef<[u8]>,
+    P: AsRef<[u8]>,
+{
+    if proof.is_empty() && root == EMPTY_ROOT_HASH {
+        return Ok(None);
+    }
+
+    let path = trie::nibbles(key.as_ref());
+    let mut path = &path[..];
+    let mut nodes = proof.iter().map(AsRef::as_ref).enumerate();
+    let mut hash = root;
+    let value = 'proof: loop {
+        let (index, mut node) = nodes.next().ok_or(ProofError::MissingNode)?;
+        if Digest::of(node) != hash {
+            return Err(ProofError::HashMismatch(index));
+        }
+
+        // Walk the node and any child nodes embedded within it, until either
+        // the value is found, the key is shown not to be in the trie, or a
+        // child node referenced by hash needs to be taken from the proof.
+        loop {
+            let invalid = || ProofError::InvalidNode(index);
+            let items = decode_node(node).ok_or_else(invalid)?;
+            let child = match items.len() {
+                0 => break 'proof None,
+                17 => match path.split_first() {
+                    Some((&nibble, rest)) => {
+                        path = rest;
+                        items[nibble as usize]
+                    }
+                    None => {
+                        let value = decode_bytes(items[16]).ok_or_else(invalid)?;
+                        break 'proof (!value.is_empty()).then_some(value);
+                    }
+                },
+                2 => {
+                    let encoded_path = decode_bytes(items[0]).ok_or_else(invalid)?;
+                    let (leaf, prefix) = decode_hex_prefix(encoded_path).ok_or_else(invalid)?;
+                    match path.strip_prefix(&prefix[..]) {
+                        Some([]) if leaf => {
+                            break 'proof Some(decode_bytes(items[1]).ok_or_else(invalid)?);
+                        }
+                        Some(rest) if !leaf => {
+                            path = rest;
+                            items[1]
+                        }
+                        _ => break 'proof None,
+                    }
+                }
+                _ => return Err(invalid()),
+            };
+
+            if child.first().is_some_and(|&prefix| prefix >= 0xc0) {
+                node = child;
+                continue;
+            }
+            match decode_bytes(child).ok_or_else(invalid)? {
+                [] => break 'proof None,
+                child if child.len() == 32 => {
+                    hash = Digest::from_slice(child);
+                    continue 'proof;
+                }
+                _ => return Err(invalid()),
+            }
+        }
+    };
+
+    if nodes.next().is_some() {
+        return Err(ProofError::UnexpectedNode);
+    }
+    Ok(value.map(<[u8]>::to_vec))
+}
+
+/// Splits an RLP encoded trie node into its raw RLP encoded items. The empty
+/// trie node is returned as a node without any items.
+fn decode_node(mut node: &[u8]) -> Option<Vec<&[u8]>> {
+    if node == [0x80] {
+        return Some(Vec::new());
+    }
+
+    let mut payload = Header::decode_list(&mut node).ok()?;
+    if !node.is_empty() {
+        return None;
+    }
+    let mut items = Vec::with_capacity(17);
+    while !payload.is_empty() {
+        let item = payload;
+        let header = Header::decode(&mut payload).ok()?;
+        payload = &payload[header.payload_length..];
+        items.push(&item[..item.len() - payload.len()]);
+    }
+    Some(items)
+}
+
+/// Decodes a raw RLP encoded byte string item.
+fn decode_bytes(mut item: &[u8]) -> Option<&[u8]> {
+    Header::decode_bytes(&mut item).ok()
+}
+
+/// Decodes a hex-prefix encoded path, returning whether it belongs to a leaf
+/// node along with its nibbles.
+fn decode_hex_prefix(encoded: &[u8]) -> Option<(bool, Vec<u8>)> {
+    let (&first, rest) = encoded.split_first()?;
+    let (leaf, odd) = match first >> 4 {
+        0 => (false, false),
+        1 => (false, true),
+        2 => (true, false),
+        3 => (true, true),
+        _ => return None,
+    };
+    let mut path = Vec::with_capacity(rest.len() * 2 + 1);
+    if odd {
+        path.push(first & 0xf);
+    } else if first & 0xf != 0 {
+        return None;
+    }
+    path.extend(trie::nibbles(rest));
+    Some((leaf, path))
+}
+
+/// Represents an error verifying a proof.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProofError {
+    /// The proof node at the specified index does not match the hash that
+    /// references it.
+    HashMismatch(usize),
+    /// The proof node at the specified index is not a valid trie node.
+    InvalidNode(usize),
+    /// The proof ends before reaching the key.
+    MissingNode,
+    /// The proof contains additional nodes after reaching the key.
+    UnexpectedNode,
+    /// The proven value is not a valid account or storage value.
+    InvalidValue,
+}
+
+impl Display for ProofError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::HashMismatch(index) => write!(f, "proof node {index} hash mismatch"),
+            Self::InvalidNode(index) => write!(f, "proof node {index} is not a valid trie node"),
+            Self::MissingNode => write!(f, "proof is missing nodes"),
+            Self::UnexpectedNode => write!(f, "proof has unexpected trailing nodes"),
+            Self::InvalidValue => write!(f, "invalid proven value"),
+        }
+    }
+}
+
+impl std::error::Error for ProofError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{digest, tests::hex, trie::SecureTrieBuilder};
+
+    const STATE_ROOT: Digest =
+        digest!("0xed81529e24ee24d432681dd43f568df2339295fa37133356b307360d9bd8b686");
+    const STORAGE_ROOT: Digest =
+        digest!("0x64d51dc02612cd687fc8fd8267c3a4cff667c182c6471f133eb355974eb709a4");
+
+    // Root nodes of the state and storage tries, shared by all proofs.
+    const STATE_ROOT_NODE: &str = "f901d1a0ab4e1eb5ed9e88bd315fa132d5b376f147c63793218cfdcb479abfccb6c49abba019d84f1487f34503c9ca93b31fdab97368eacc008010a44bc4ee62031e4ad6d780a03a314c9acf84e717e68418cdc0687ae049a76b5a114bdb2e3714ec713568a66ca00edac58f46d7de4e1d77ef2f8ea8e53685d229655a2adbaa7262d2ccba94585da0206c0f09775c9387941e4048ce04a616ca63ab36c4afd209e71d6232daac7f37a07cbacb04b8d36fa97e73242cf722317e307022d731fa0bdcfd095f453898970d80a090f34a0bf3d68a2ae17fa53feb4f8b10e9f35c23bb1405c1d1aee0ebfb5bd05da0113d5f707f313d8fd5c413027de603d31fc0774905fbad547aa348d5eb236fe3a09ade81ee1895686bb8c7bef931562cdd12695357139438fb17a85a151b7ba8eaa0eac1aecd28a28ced8ee1f90f52edc36a23c121d963e64de731766136f9776b74a0b4a9857eaa21d27e2edc30378e95b4ee2c95505d9f4546cc6d7ae48e3650d722a0a0e504eb9bb373c5b87b85ad69935628694fe5735d028307869dec451296d5faa0e6fe5ec0415f4ed2f0996873fd77436fe93a2c004c59248dd945f3bd59056a5da09422911000ab63db455036e5a10e05c3c4ca0bf825419bca7a918ca8889ea43180";
+    const STORAGE_ROOT_NODE: &str = "f90191a08c5297778a3e603e0cdd6db6a24ed08471b7c01a671812a75deda3751991a87da043ee3dcf8ef96f8c0e0eee036a39f7ac049edf80200b802e31626a818622d030a04fc5f13ab2f9ba0c2da88b0151ab0e7cf4d85d08cca45ccd923c6ab76323eb28a0091bf1758cd4d66f2cf04a781582316c876746a8074cdfee28dec7f41599a286a0635739bf102b0734c1987a89bd21e51fa74f2aca0781e21864906f939f9467b580a0f21b8cb68a111e1a21e8f7b752c0d7d0932909d01261b3d51253baeb014236ca80a035fd06a114abede1042984bf7d43960e1b23d141b2095b5e3dbc6a94df8d823d80a074ec166b37465c71b1631fceb1858c3b71354ab78a37df9ede71b6854de7e121a024f592cb0ab1a79afc70bb238415e18120c3154cb47e4457b8ef4375cd4c8d7fa0b86df227034117bd98a8e3af3f6dcb5e9fb1af0901450d31d7c9a4ae18880beda04a16b520336c491d91f2cade491975b5221b8d6dcb725344b2efeae3ef40053f80a03f52a5690b7c350f545a9d3a3abf0b939ad7606244bafe7055eca002c481f39e80";
+
+    /// Returns the test state, where account `0x..07` has a storage trie with
+    /// slots `0..20` set to `slot * 1000 + 1`.
+    fn account(i: u8) -> (Address, Account) {
+        let mut address = Address::default();
+        address.0[19] = i;
+        let account = if i == 7 {
+            Account {
+                nonce: 1,
+                balance: U256::new(10_u128.pow(18)),
+                storage_root: STORAGE_ROOT,
+                code_hash: Digest::of("code"),
+            }
+        } else {
+            Account {
+                nonce: i.into(),
+                balance: U256::new(i as u128 * 1000),
+                ..Default::default()
+            }
+        };
+        (address, account)
+    }
+
+    fn account_proof() -> AccountProof {
+        AccountProof {
+            address: account(7).0,
+            account_proof: vec![
+                hex(STATE_ROOT_NODE),
+                hex("f851808080808080808080a0e84f0acacc58eb8f625908c2a010784647bd456301aa62288fa3e32d8c902ddb8080a0dc96b0761d2eb7345a5ae0850682870e977bb0a1e628d52e29fe0123728221c580808080"),
+                hex("f871a0203ab0970b73895b8c9959bae685c3a19f45eb5ad89d42b52a340ec4ac204d19b84ef84c01880de0b6b3a7640000a064d51dc02612cd687fc8fd8267c3a4cff667c182c6471f133eb355974eb709a4a02dc081a8d6d4714c79b5abd2e9b08c3a33b4ef1dcf946ef8b8cf6c495014f47b"),
+            ],
+            storage_proof: vec![
+                StorageProof {
+                    key: crate::storage::slot(U256::new(3)),
+                    proof: vec![
+                        hex(STORAGE_ROOT_NODE),
+                        hex("f8518080a0b40b1eee897a01a1d788e2cf3fb8acc46c1844a72ae9f16d3a85cdb21faf17c6808080a047924b946ec4569e3855122b2d0a50c56f9a4c05f76c6f605579405f813f9be080808080808080808080"),
+                        hex("e5a020575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85b83820bb9"),
+                    ],
+                },
+                StorageProof {
+                    key: crate::storage::slot(U256::new(100)),
+                    proof: vec![
+                        hex(STORAGE_ROOT_NODE),
+                        hex("e2a0390decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e56301"),
+                    ],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_state_roots() {
+        let mut state = SecureTrieBuilder::new();
+        for i in 1..=32 {
+            let (address, account) = account(i);
+            state.insert(address, rlp::encode(&account));
+        }
+        assert_eq!(state.root(), STATE_ROOT);
+
+        let mut storage = SecureTrieBuilder::new();
+        for i in 0..20 {
+            let value = U256::new(i * 1000 + 1);
+            storage.insert(crate::storage::slot(U256::new(i)), rlp::encode(&value));
+        }
+        assert_eq!(storage.root(), STORAGE_ROOT);
+    }
+
+    #[test]
+    fn account_roundtrip() {
+        let account = account(7).1;
+        assert_eq!(
+            rlp::decode::<Account>(&rlp::encode(&account)).unwrap(),
+            account
+        );
+    }
+
+    #[test]
+    fn inclusion_proofs() {
+        assert_eq!(
+            account_proof().verify(STATE_ROOT).unwrap(),
+            VerifiedAccount {
+                account: Some(account(7).1),
+                storage: vec![U256::new(3001), U256::ZERO],
+            },
+        );
+
+        let proof = [
+            hex(STATE_ROOT_NODE),
+            hex("f8718080808080a0be016206de9116cc3b050d1caed0e2918230bd5cc2f947bb7d0d21fdb63e4530808080a08d88e1f8da308343fc96a88bc9fb5313524bc8ffaa032b785a1f141db7e246b280808080a0cf31202bfb5564a12af6ed52333a6632d8f54a4ee32dfa5e54f4bba099f0dec28080"),
+            hex("f86ba0202688a8f926c816ca1e079067caba944f158e764817b83fc43594370ca9cf62b848f846028207d0a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+        ];
+        assert_eq!(
+            verify_account(STATE_ROOT, account(2).0, &proof).unwrap(),
+            Some(account(2).1),
+        );
+    }
+
+    #[test]
+    fn exclusion_proofs() {
+        // The proof ends at a leaf node for a different key.
+        let proof = [
+            hex(STATE_ROOT_NODE),
+            hex("f86ba03ffa0eae268038cfa984647a1d0635beb86eda9fb7b500688f3189520cfa9ee5b848f8460d8232c8a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+        ];
+        let missing = account(0xde).0;
+        assert_eq!(verify_account(STATE_ROOT, missing, &proof).unwrap(), None);
+
+        // The same proof does not exclude the account of the leaf node.
+        assert_eq!(
+            verify_account(STATE_ROOT, account(13).0, &proof).unwrap(),
+            Some(account(13).1),
+        );
+
+        // The proof ends at an empty branch child.
+        let key = Digest::of(missing);
+        let mut node = vec![0x80; 17];
+        node[16] = 0x01;
+        let node = [vec![0xd1], node].concat();
+        assert_eq!(
+            verify_proof(Digest::of(&node), key, &[&node]).unwrap(),
+            None
+        );
+
+        // The empty trie excludes everything.
+        let empty: [&[u8]; 1] = [&[0x80]];
+        assert_eq!(
+            verify_account(EMPTY_ROOT_HASH, missing, &empty).unwrap(),
+            None
+        );
+        assert_eq!(
+            AccountProof {
+                address: missing,
+                account_proof: Vec::new(),
+                storage_proof: vec![StorageProof::default()],
+            }
+            .verify(EMPTY_ROOT_HASH)
+            .unwrap(),
+            VerifiedAccount {
+                account: None,
+                storage: vec![U256::ZERO],
+            },
+        );
+    }
+
+    #[test]
+    fn invalid_proofs() {
+        let proof = account_proof();
+
+        let mut tampered = proof.clone();
+        tampered.account_proof[2][40] ^= 1;
+        assert_eq!(
+            tampered.verify(STATE_ROOT),
+            Err(ProofError::HashMismatch(2))
+        );
+
+        let mut tampered = proof.clone();
+        tampered.storage_proof[0].proof[2][30] ^= 1;
+        assert_eq!(
+            tampered.verify(STATE_ROOT),
+            Err(ProofError::HashMismatch(2))
+        );
+
+        let mut truncated = proof.clone();
+        truncated.account_proof.pop();
+        assert_eq!(truncated.verify(STATE_ROOT), Err(ProofError::MissingNode));
+
+        let mut extended = proof.clone();
+        extended.account_proof.push(vec![0x80]);
+        assert_eq!(extended.verify(STATE_ROOT), Err(ProofError::UnexpectedNode));
+
+        let mut wrong_storage = proof.clone();
+        wrong_storage.storage_proof[0].proof.remove(0);
+        assert_eq!(
+            wrong_storage.verify(STATE_ROOT),
+            Err(ProofError::HashMismatch(0))
+        );
+
+        let node = rlp::encode(&vec![vec![0x42_u8]; 3]);
+        assert_eq!(
+            verify_proof(Digest::of(&node), [0; 32], &[&node]),
+            Err(ProofError::InvalidNode(0)),
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! JSON-RPC integer quantities.
+//!
+//! The JSON-RPC API encodes integer quantities as `0x`-prefixed hex strings
+//! without leading zeros, so `0` is encoded as `"0x0"` and `1024` as
+//! `"0x400"`. Since not all nodes and clients follow this convention, the
+//! [`U64`] type formats quantities canonically but accepts leading zeros when
+//! parsing and deserializing. Strict parsing is available with
+//! [`U64::from_str_strict`], as well as strict serialization with the
+//! `strict` serde helper module.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::quantity::{ParseQuantityError, U64};
+//! let quantity = "0x0400".parse::<U64>().unwrap();
+//! assert_eq!(quantity, U64(1024));
+//! assert_eq!(quantity.to_string(), "0x400");
+//! assert_eq!(
+//!     U64::from_str_strict("0x0400"),
+//!     Err(ParseQuantityError::LeadingZero),
+//! );
+//! ```
+
+#[cfg(feature = "serde")]
+mod serde;
+
+#[cfg(feature = "serde")]
+pub use self::serde::strict;
+use core::{
+    fmt::{self, Display, Formatter, LowerHex, UpperHex},
+    str::FromStr,
+};
+
+/// A 64-bit JSON-RPC integer quantity.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct U64(pub u64);
+
+impl U64 {
+    /// Parses a quantity, rejecting leading zeros.
+    pub fn from_str_strict(s: &str) -> Result<Self, ParseQuantityError> {
+        parse(s, true).map(Self)
+    }
+}
+
+impl Display for U64 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+impl LowerHex for U64 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl UpperHex for U64 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for U64 {
+    type Err = ParseQuantityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, false).map(Self)
+    }
+}
+
+impl From<u64> for U64 {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<U64> for u64 {
+    fn from(value: U64) -> Self {
+        value.0
+    }
+}
+
+/// Parses a `0x`-prefixed hex quantity, optionally rejecting leading zeros.
+fn parse(s: &str, strict: bool) -> Result<u64, ParseQuantityError> {
+    let digits = s
+        .strip_prefix("0x")
+        .ok_or(ParseQuantityError::MissingPrefix)?;
+    if digits.is_empty() {
+        return Err(ParseQuantityError::Empty);
+    }
+    if strict && digits.len() > 1 && digits.starts_with('0') {
+        return Err(ParseQuantityError::LeadingZero);
+    }
+
+    let mut value = 0_u64;
+    for (i, c) in digits.char_indices() {
+        let digit = c
+            .to_digit(16)
+            .ok_or(ParseQuantityError::InvalidHexCharacter { c, index: i + 2 })?;
+        value = value
+            .checked_mul(16)
+            .map(|value| value | u64::from(digit))
+            .ok_or(ParseQuantityError::Overflow)?;
+    }
+    Ok(value)
+}
+
+/// Represents an error parsing a quantity.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseQuantityError {
+    /// The quantity is missing its `0x` prefix.
+    MissingPrefix,
+    /// The quantity has no digits.
+    Empty,
+    /// The quantity has leading zeros.
+    LeadingZero,
+    /// An invalid character was found.
+    InvalidHexCharacter { c: char, index: usize },
+    /// The quantity does not fit in 64 bits.
+    Overflow,
+}
+
+impl Display for ParseQuantityError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::MissingPrefix => write!(f, "quantity missing `0x` prefix"),
+            Self::Empty => write!(f, "empty quantity"),
+            Self::LeadingZero => write!(f, "quantity with leading zeros"),
+            Self::InvalidHexCharacter { c, index } => {
+                write!(f, "invalid character {c:?} at position {index}")
+            }
+            Self::Overflow => write!(f, "quantity overflows 64 bits"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseQuantityError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_quantities() {
+        for (s, value) in [
+            ("0x0", 0),
+            ("0x400", 1024),
+            ("0xDeadBeef", 0xdeadbeef),
+            ("0xffffffffffffffff", u64::MAX),
+        ] {
+            assert_eq!(U64::from_str_strict(s).unwrap(), U64(value));
+            assert_eq!(s.parse::<U64>().unwrap(), U64(value));
+        }
+    }
+
+    #[test]
+    fn leading_zeros() {
+        for s in ["0x00", "0x0400", "0x0000000000000000ffffffffffffffff"] {
+            assert!(s.parse::<U64>().is_ok());
+            assert_eq!(
+                U64::from_str_strict(s),
+                Err(ParseQuantityError::LeadingZero)
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_quantities() {
+        for (s, err) in [
+            ("400", ParseQuantityError::MissingPrefix),
+            ("0x", ParseQuantityError::Empty),
+            (
+                "0x4g0",
+                ParseQuantityError::InvalidHexCharacter { c: 'g', index: 3 },
+            ),
+            ("0x10000000000000000", ParseQuantityError::Overflow),
+        ] {
+            assert_eq!(s.parse::<U64>(), Err(err.clone()));
+            assert_eq!(U64::from_str_strict(s), Err(err));
+        }
+    }
+
+    #[test]
+    fn format_quantities() {
+        assert_eq!(U64(0).to_string(), "0x0");
+        assert_eq!(U64(1024).to_string(), "0x400");
+        assert_eq!(format!("{:X}", U64(0xdeadbeef)), "DEADBEEF");
+    }
+}
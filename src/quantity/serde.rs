@@ -0,0 +1,265 @@
+//! Serde serialization implementation for quantities.
+
+use super::{parse, U64};
+use core::fmt::{self, Formatter};
+use serde::{
+    de::{self, Deserializer, Visitor},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
+
+impl<'de> Deserialize<'de> for U64 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(QuantityVisitor { strict: false })
+    }
+}
+
+impl Serialize for U64 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+struct QuantityVisitor {
+    strict: bool,
+}
+
+impl<'de> Visitor<'de> for QuantityVisitor {
+    type Value = U64;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a `0x`-prefixed hex quantity")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        parse(s, self.strict).map(U64).map_err(de::Error::custom)
+    }
+}
+
+struct OptionVisitor {
+    strict: bool,
+}
+
+impl<'de> Visitor<'de> for OptionVisitor {
+    type Value = Option<U64>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("an optional `0x`-prefixed hex quantity")
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_str(QuantityVisitor {
+                strict: self.strict,
+            })
+            .map(Some)
+    }
+}
+
+/// Strict serialization of quantities, rejecting leading zeros when
+/// deserializing.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::quantity::U64;
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Block {
+///     #[serde(with = "ethprim::quantity::strict")]
+///     number: u64,
+///     #[serde(default, with = "ethprim::quantity::strict")]
+///     base_fee: Option<U64>,
+/// }
+///
+/// let block = serde_json::from_str::<Block>(r#"{"number":"0x400"}"#).unwrap();
+/// assert_eq!(block.number, 1024);
+/// assert_eq!(block.base_fee, None);
+///
+/// assert!(serde_json::from_str::<Block>(r#"{"number":"0x0400"}"#).is_err());
+/// ```
+pub mod strict {
+    use super::{OptionVisitor, QuantityVisitor};
+    use crate::quantity::U64;
+    use serde::{Deserializer, Serializer};
+
+    /// A type that can be serialized as a quantity.
+    pub trait Quantity: Sized {
+        /// Serializes the quantity.
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer;
+
+        /// Deserializes the quantity, rejecting leading zeros.
+        fn deserialize<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>;
+    }
+
+    impl Quantity for U64 {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serde::Serialize::serialize(self, serializer)
+        }
+
+        fn deserialize<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_str(QuantityVisitor { strict: true })
+        }
+    }
+
+    impl Quantity for u64 {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serde::Serialize::serialize(&U64(*self), serializer)
+        }
+
+        fn deserialize<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            <U64 as Quantity>::deserialize(deserializer).map(u64::from)
+        }
+    }
+
+    impl Quantity for Option<U64> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match self {
+                Some(value) => serde::Serialize::serialize(value, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        fn deserialize<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_option(OptionVisitor { strict: true })
+        }
+    }
+
+    impl Quantity for Option<u64> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            Quantity::serialize(&self.map(U64), serializer)
+        }
+
+        fn deserialize<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            <Option<U64> as Quantity>::deserialize(deserializer).map(|value| value.map(u64::from))
+        }
+    }
+
+    /// Serializes a quantity.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Quantity,
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    /// Deserializes a quantity, rejecting leading zeros.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Quantity,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serde_roundtrip() {
+        for value in [0, 1024, u64::MAX] {
+            let json = serde_json::to_value(U64(value)).unwrap();
+            assert_eq!(json, json!(format!("{value:#x}")));
+            assert_eq!(serde_json::from_value::<U64>(json).unwrap(), U64(value));
+        }
+    }
+
+    #[test]
+    fn lenient_and_strict_deserialization() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Strict {
+            #[serde(with = "strict")]
+            value: u64,
+            #[serde(default, with = "strict", skip_serializing_if = "Option::is_none")]
+            optional: Option<U64>,
+        }
+
+        assert_eq!(
+            serde_json::from_value::<U64>(json!("0x0400")).unwrap(),
+            U64(1024)
+        );
+        assert!(serde_json::from_value::<U64>(json!("400")).is_err());
+        assert!(serde_json::from_value::<U64>(json!(1024)).is_err());
+
+        let strict = Strict {
+            value: 1024,
+            optional: Some(U64(0)),
+        };
+        let json = json!({ "value": "0x400", "optional": "0x0" });
+        assert_eq!(serde_json::to_value(&strict).unwrap(), json);
+        assert_eq!(serde_json::from_value::<Strict>(json).unwrap(), strict);
+        assert_eq!(
+            serde_json::from_value::<Strict>(json!({ "value": "0x0", "optional": null })).unwrap(),
+            Strict {
+                value: 0,
+                optional: None
+            },
+        );
+        for json in [
+            json!({ "value": "0x0400" }),
+            json!({ "value": "0x400", "optional": "0x00" }),
+        ] {
+            assert!(serde_json::from_value::<Strict>(json).is_err());
+        }
+    }
+}
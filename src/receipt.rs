@@ -0,0 +1,312 @@
+//! Transaction receipts and logs.
+//!
+//! This module provides the consensus representation of transaction receipts,
+//! along with their [EIP-2718] encoding. Encoded receipts are the values of
+//! the receipts trie, so that a block's receipts can be checked against the
+//! `receipts_root` of its header with [`receipts_root`].
+//!
+//! Note that only post-[Byzantium] receipts, with a status code instead of an
+//! intermediate state root, are supported.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::receipt::{self, Receipt};
+//! # use ethprim::tx::Eip1559Transaction;
+//! let receipt = Receipt::new(Eip1559Transaction::TYPE, true, 21_000, Vec::new());
+//! let encoded = receipt.encode();
+//! assert_eq!(encoded[0], Eip1559Transaction::TYPE);
+//! assert_eq!(Receipt::decode(&encoded).unwrap(), receipt);
+//! assert_eq!(
+//!     receipt::receipts_root(&[receipt]),
+//!     ethprim::trie::ordered_trie_root([encoded]),
+//! );
+//! ```
+//!
+//! [Byzantium]: https://eips.ethereum.org/EIPS/eip-658
+//! [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+
+use crate::{
+    rlp::{self, Decodable, Encodable, Output},
+    trie,
+    tx::{Eip1559Transaction, Eip2930Transaction, Eip4844Transaction, Eip7702Transaction},
+    Address, Bloom, Digest,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// A log emitted by a contract.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Log {
+    /// The address of the contract that emitted the log.
+    pub address: Address,
+    /// The log topics.
+    pub topics: Vec<Digest>,
+    /// The log data.
+    pub data: Vec<u8>,
+}
+
+impl Log {
+    /// Returns the bloom of the log's address and topics.
+    pub fn bloom(&self) -> Bloom {
+        let mut bloom = Bloom::default();
+        bloom.accrue_log(&self.address, &self.topics);
+        bloom
+    }
+
+    fn fields(&self) -> [&dyn Encodable; 3] {
+        [&self.address, &self.topics, &self.data]
+    }
+}
+
+impl Encodable for Log {
+    fn encode(&self, out: &mut dyn Output) {
+        rlp::encode_fields(&self.fields(), &[], out);
+    }
+
+    fn length(&self) -> usize {
+        rlp::fields_length(&self.fields())
+    }
+}
+
+impl Decodable for Log {
+    fn decode(buf: &mut &[u8]) -> Result<Self, rlp::DecodeError> {
+        rlp::decode_fields(buf, |fields| {
+            Ok(Self {
+                address: Decodable::decode(fields)?,
+                topics: Decodable::decode(fields)?,
+                data: Decodable::decode(fields)?,
+            })
+        })
+    }
+}
+
+/// A transaction receipt.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Receipt {
+    /// The EIP-2718 type of the transaction. Legacy transactions have a type
+    /// of `0`.
+    pub tx_type: u8,
+    /// Whether or not the transaction executed successfully.
+    pub status: bool,
+    /// The total gas used in the block up to and including the transaction.
+    pub cumulative_gas_used: u64,
+    /// The bloom of the logs emitted by the transaction.
+    pub logs_bloom: Bloom,
+    /// The logs emitted by the transaction.
+    pub logs: Vec<Log>,
+}
+
+impl Receipt {
+    /// Creates a new receipt, computing the bloom of its logs.
+    pub fn new(tx_type: u8, status: bool, cumulative_gas_used: u64, logs: Vec<Log>) -> Self {
+        let mut logs_bloom = Bloom::default();
+        for log in &logs {
+            logs_bloom.accrue_log(&log.address, &log.topics);
+        }
+        Self {
+            tx_type,
+            status,
+            cumulative_gas_used,
+            logs_bloom,
+            logs,
+        }
+    }
+
+    /// Returns the EIP-2718 encoding of the receipt, `type || rlp([status,
+    /// cumulative_gas_used, logs_bloom, logs])`. Receipts of legacy
+    /// transactions are encoded without a type prefix.
+    pub fn encode(&self) -> Vec<u8> {
+        let fields = self.fields();
+        let mut buffer = Vec::with_capacity(1 + rlp::fields_length(&fields));
+        if self.tx_type != 0 {
+            buffer.push(self.tx_type);
+        }
+        rlp::encode_fields(&fields, &[], &mut buffer);
+        buffer
+    }
+
+    /// Decodes a receipt from its EIP-2718 encoding.
+    pub fn decode(raw: &[u8]) -> Result<Self, DecodeError> {
+        let (tx_type, mut payload) = match raw.first() {
+            Some(0xc0..=0xff) => (0, raw),
+            Some(
+                &tx_type @ (Eip2930Transaction::TYPE
+                | Eip1559Transaction::TYPE
+                | Eip4844Transaction::TYPE
+                | Eip7702Transaction::TYPE),
+            ) => (tx_type, &raw[1..]),
+            Some(&tx_type) => return Err(DecodeError::UnsupportedType(tx_type)),
+            None => return Err(rlp::DecodeError::UnexpectedEof.into()),
+        };
+        let receipt = rlp::decode_fields::<_, rlp::DecodeError>(&mut payload, |fields| {
+            Ok(Self {
+                tx_type,
+                status: Decodable::decode(fields)?,
+                cumulative_gas_used: Decodable::decode(fields)?,
+                logs_bloom: Decodable::decode(fields)?,
+                logs: Decodable::decode(fields)?,
+            })
+        })?;
+        if !payload.is_empty() {
+            return Err(rlp::DecodeError::TrailingBytes.into());
+        }
+        Ok(receipt)
+    }
+
+    fn fields(&self) -> [&dyn Encodable; 4] {
+        [
+            &self.status,
+            &self.cumulative_gas_used,
+            &self.logs_bloom,
+            &self.logs,
+        ]
+    }
+}
+
+/// Computes the receipts root of a block from its receipts, in transaction
+/// order.
+pub fn receipts_root(receipts: &[Receipt]) -> Digest {
+    trie::ordered_trie_root(receipts.iter().map(Receipt::encode))
+}
+
+/// Represents an error decoding a receipt.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The receipt is not correctly RLP encoded.
+    Rlp(rlp::DecodeError),
+    /// The EIP-2718 transaction type is not supported.
+    UnsupportedType(u8),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Rlp(err) => write!(f, "invalid receipt encoding: {err}"),
+            Self::UnsupportedType(tx_type) => {
+                write!(f, "unsupported receipt type {tx_type:#04x}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<rlp::DecodeError> for DecodeError {
+    fn from(err: rlp::DecodeError) -> Self {
+        Self::Rlp(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest};
+
+    fn transfer() -> Log {
+        Log {
+            address: address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+            topics: vec![
+                digest!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+                digest!("0x0000000000000000000000001111111111111111111111111111111111111111"),
+                digest!("0x0000000000000000000000002222222222222222222222222222222222222222"),
+            ],
+            data: digest!("0x0000000000000000000000000000000000000000000000000de0b6b3a7640000")
+                .to_vec(),
+        }
+    }
+
+    fn receipts() -> Vec<Receipt> {
+        vec![
+            Receipt::new(0, true, 21_000, vec![]),
+            Receipt::new(Eip1559Transaction::TYPE, true, 72_000, vec![transfer()]),
+            Receipt::new(Eip2930Transaction::TYPE, false, 100_000, vec![]),
+            Receipt::new(
+                Eip4844Transaction::TYPE,
+                true,
+                150_000,
+                vec![transfer(), transfer()],
+            ),
+            Receipt::new(Eip7702Transaction::TYPE, true, 200_000, vec![]),
+        ]
+    }
+
+    #[test]
+    fn log_encoding() {
+        let log = transfer();
+        let encoded = rlp::encode(&log);
+        assert_eq!(encoded.len(), log.length());
+        assert_eq!(
+            Digest::of(&encoded),
+            Digest::of(
+                [
+                    &[0xf8, 0x9b, 0x94][..],
+                    &log.address[..],
+                    &[0xf8, 0x63, 0xa0],
+                    &log.topics[0][..],
+                    &[0xa0],
+                    &log.topics[1][..],
+                    &[0xa0],
+                    &log.topics[2][..],
+                    &[0xa0],
+                    &log.data,
+                ]
+                .concat()
+            ),
+        );
+        assert_eq!(rlp::decode::<Log>(&encoded).unwrap(), log);
+    }
+
+    #[test]
+    fn receipt_encoding() {
+        for (receipt, hash) in receipts().into_iter().zip([
+            digest!("0xe38e5532717f12f769b07ea016014bd39b74fb72def4de8442114cc2728609f2"),
+            digest!("0x44fd1621f3d6b3e7af2bd2ee99b8019c3b4d834f37dc6b5aeae996d0ecf96328"),
+            digest!("0xf7cf6c281f942bef0a20e9a54d42f7a028d78da5b629d8d2aabc39a11d684f45"),
+            digest!("0x5f06738f74077410f094ddc0b31a587874783d79a8584ca0f2e8e3401d62be5c"),
+            digest!("0x1623a49d98f515fba19a1e34a6b0c510d1fa60b315593cfc1845408cdaf013f6"),
+        ]) {
+            let encoded = receipt.encode();
+            assert_eq!(Digest::of(&encoded), hash);
+            assert_eq!(Receipt::decode(&encoded).unwrap(), receipt);
+        }
+    }
+
+    #[test]
+    fn receipts_root_of_block() {
+        assert_eq!(
+            receipts_root(&receipts()),
+            digest!("0x24ffa0124d65c9c544835de207abd101485cbfdba84b8c36fda633b80f7885e0"),
+        );
+        assert_eq!(receipts_root(&[]), trie::EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn receipt_bloom() {
+        let receipt = &receipts()[3];
+        assert_eq!(receipt.logs_bloom, transfer().bloom());
+        assert!(receipt.logs_bloom.contains_address(&transfer().address));
+    }
+
+    #[test]
+    fn invalid_receipts() {
+        let mut encoded = receipts()[1].encode();
+        encoded[0] = 0x05;
+        assert_eq!(
+            Receipt::decode(&encoded),
+            Err(DecodeError::UnsupportedType(0x05))
+        );
+        assert_eq!(
+            Receipt::decode(&[]),
+            Err(DecodeError::Rlp(rlp::DecodeError::UnexpectedEof))
+        );
+
+        let mut encoded = Receipt::new(0, true, 0, vec![]).encode();
+        encoded.push(0);
+        assert_eq!(
+            Receipt::decode(&encoded),
+            Err(DecodeError::Rlp(rlp::DecodeError::TrailingBytes))
+        );
+    }
+}
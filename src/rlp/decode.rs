@@ -0,0 +1,197 @@
+//! RLP decoding.
+
+use super::{DecodeError, Header};
+use crate::{Address, Bloom, Digest, U256};
+
+/// A type that can be decoded from its canonical RLP encoding.
+pub trait Decodable: Sized {
+    /// Decodes a value from the start of the buffer, advancing it past the
+    /// decoded item.
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError>;
+
+    /// Decodes a vector of values from the start of the buffer. Vectors are
+    /// decoded from lists by default; this is overridden for bytes so that
+    /// byte vectors are decoded from byte strings.
+    fn decode_vec(buf: &mut &[u8]) -> Result<Vec<Self>, DecodeError> {
+        let mut payload = Header::decode_list(buf)?;
+        let mut items = Vec::new();
+        while !payload.is_empty() {
+            items.push(Self::decode(&mut payload)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T> Decodable for Box<T>
+where
+    T: Decodable,
+{
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        T::decode(buf).map(Box::new)
+    }
+}
+
+impl<const N: usize> Decodable for [u8; N] {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        Header::decode_bytes(buf)?
+            .try_into()
+            .map_err(|_| DecodeError::InvalidLength)
+    }
+}
+
+impl Decodable for String {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let bytes = Header::decode_bytes(buf)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidValue)
+    }
+}
+
+impl Decodable for Address {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        <[u8; 20]>::decode(buf).map(Self)
+    }
+}
+
+impl Decodable for Option<Address> {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        match Header::decode_bytes(buf)? {
+            [] => Ok(None),
+            bytes => bytes
+                .try_into()
+                .map(|bytes| Some(Address(bytes)))
+                .map_err(|_| DecodeError::InvalidLength),
+        }
+    }
+}
+
+impl Decodable for Digest {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        <[u8; 32]>::decode(buf).map(Self)
+    }
+}
+
+impl Decodable for Bloom {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        <[u8; 256]>::decode(buf).map(Self)
+    }
+}
+
+impl Decodable for bool {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u64::decode(buf)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DecodeError::InvalidValue),
+        }
+    }
+}
+
+/// Decodes the big-endian bytes of a canonically encoded integer, right
+/// aligned into a buffer of the integer's size.
+fn decode_uint<const N: usize>(buf: &mut &[u8]) -> Result<[u8; N], DecodeError> {
+    let bytes = Header::decode_bytes(buf)?;
+    if bytes.len() > N {
+        return Err(DecodeError::Overflow);
+    }
+    if bytes.first() == Some(&0) {
+        return Err(DecodeError::LeadingZero);
+    }
+    let mut value = [0; N];
+    value[N - bytes.len()..].copy_from_slice(bytes);
+    Ok(value)
+}
+
+macro_rules! impl_uint {
+    ($($t:ty),*) => {$(
+        impl Decodable for $t {
+            fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+                decode_uint(buf).map(<$t>::from_be_bytes)
+            }
+        }
+    )*};
+}
+
+impl_uint!(u16, u32, u64, u128, usize, U256);
+
+impl Decodable for u8 {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        decode_uint(buf).map(u8::from_be_bytes)
+    }
+
+    fn decode_vec(buf: &mut &[u8]) -> Result<Vec<Self>, DecodeError> {
+        Header::decode_bytes(buf).map(<[u8]>::to_vec)
+    }
+}
+
+impl<T> Decodable for Vec<T>
+where
+    T: Decodable,
+{
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        T::decode_vec(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, rlp, uint};
+
+    #[test]
+    fn roundtrip() {
+        let value = vec![uint!("0"), uint!("127"), uint!("128"), U256::MAX];
+        assert_eq!(
+            rlp::decode::<Vec<U256>>(&rlp::encode(&value)).unwrap(),
+            value
+        );
+
+        let address = address!("0x1111111111111111111111111111111111111111");
+        assert_eq!(
+            rlp::decode::<Address>(&rlp::encode(&address)).unwrap(),
+            address
+        );
+
+        let long = vec![0xff_u8; 1024];
+        assert_eq!(rlp::decode::<Vec<u8>>(&rlp::encode(&long)).unwrap(), long);
+
+        for value in [0, 1, 0x7f, 0x80, 0x100, u64::MAX] {
+            assert_eq!(rlp::decode::<u64>(&rlp::encode(&value)).unwrap(), value);
+        }
+        for value in [0, 1, 0x7f, 0x80, u8::MAX] {
+            assert_eq!(rlp::decode::<u8>(&rlp::encode(&value)).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn non_canonical_integers() {
+        for (data, err) in [
+            (&[0x00][..], DecodeError::LeadingZero),
+            (&[0x82, 0x00, 0x01], DecodeError::LeadingZero),
+            (&[0x81, 0x05], DecodeError::NonCanonicalSingleByte),
+            (&[0x83, 0x01, 0x00, 0x00], DecodeError::Overflow),
+            (&[0xc0], DecodeError::UnexpectedList),
+        ] {
+            assert_eq!(rlp::decode::<u16>(data).unwrap_err(), err);
+        }
+        assert_eq!(
+            rlp::decode::<u8>(&[0x82, 0x01, 0x00]).unwrap_err(),
+            DecodeError::Overflow
+        );
+        assert_eq!(
+            rlp::decode::<bool>(&[0x02]).unwrap_err(),
+            DecodeError::InvalidValue
+        );
+    }
+
+    #[test]
+    fn invalid_fixed_length() {
+        assert_eq!(
+            rlp::decode::<Address>(&rlp::encode(&[0_u8; 19])).unwrap_err(),
+            DecodeError::InvalidLength
+        );
+        assert_eq!(
+            rlp::decode::<Vec<u64>>(&[0xc2, 0x01]).unwrap_err(),
+            DecodeError::UnexpectedEof
+        );
+    }
+}
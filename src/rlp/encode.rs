@@ -0,0 +1,296 @@
+//! RLP encoding.
+
+use super::{Header, Output};
+use crate::{Address, Bloom, Digest, U256};
+
+/// A type that can be RLP encoded.
+pub trait Encodable {
+    /// Encodes the value to the specified output.
+    fn encode(&self, out: &mut dyn Output);
+
+    /// Returns the length of the encoded value in bytes.
+    fn length(&self) -> usize;
+
+    /// Encodes a slice of values to the specified output. Slices are encoded
+    /// as lists by default; this is overridden for bytes so that byte slices
+    /// are encoded as byte strings.
+    fn encode_slice(items: &[Self], out: &mut dyn Output)
+    where
+        Self: Sized,
+    {
+        Header::list(items.iter().map(Self::length).sum()).encode(out);
+        for item in items {
+            item.encode(out);
+        }
+    }
+
+    /// Returns the length of an encoded slice of values in bytes.
+    fn slice_length(items: &[Self]) -> usize
+    where
+        Self: Sized,
+    {
+        Header::list(items.iter().map(Self::length).sum()).length_with_payload()
+    }
+}
+
+impl<T> Encodable for &T
+where
+    T: Encodable + ?Sized,
+{
+    fn encode(&self, out: &mut dyn Output) {
+        (**self).encode(out)
+    }
+
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+impl<T> Encodable for Box<T>
+where
+    T: Encodable + ?Sized,
+{
+    fn encode(&self, out: &mut dyn Output) {
+        (**self).encode(out)
+    }
+
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+impl<const N: usize> Encodable for [u8; N] {
+    fn encode(&self, out: &mut dyn Output) {
+        self.as_slice().encode(out)
+    }
+
+    fn length(&self) -> usize {
+        self.as_slice().length()
+    }
+}
+
+impl Encodable for str {
+    fn encode(&self, out: &mut dyn Output) {
+        self.as_bytes().encode(out)
+    }
+
+    fn length(&self) -> usize {
+        self.as_bytes().length()
+    }
+}
+
+impl Encodable for String {
+    fn encode(&self, out: &mut dyn Output) {
+        self.as_bytes().encode(out)
+    }
+
+    fn length(&self) -> usize {
+        self.as_bytes().length()
+    }
+}
+
+impl Encodable for Address {
+    fn encode(&self, out: &mut dyn Output) {
+        self.0.encode(out)
+    }
+
+    fn length(&self) -> usize {
+        21
+    }
+}
+
+/// Optional addresses are encoded as an empty byte string when absent, as is
+/// the case for the recipient of contract creation transactions.
+impl Encodable for Option<Address> {
+    fn encode(&self, out: &mut dyn Output) {
+        match self {
+            Some(address) => address.encode(out),
+            None => [].encode(out),
+        }
+    }
+
+    fn length(&self) -> usize {
+        match self {
+            Some(address) => address.length(),
+            None => 1,
+        }
+    }
+}
+
+impl Encodable for Digest {
+    fn encode(&self, out: &mut dyn Output) {
+        self.0.encode(out)
+    }
+
+    fn length(&self) -> usize {
+        33
+    }
+}
+
+impl Encodable for Bloom {
+    fn encode(&self, out: &mut dyn Output) {
+        self.0.encode(out)
+    }
+
+    fn length(&self) -> usize {
+        259
+    }
+}
+
+impl Encodable for bool {
+    fn encode(&self, out: &mut dyn Output) {
+        out.write(&[if *self { 0x01 } else { 0x80 }])
+    }
+
+    fn length(&self) -> usize {
+        1
+    }
+}
+
+/// Returns the big-endian bytes of an integer without leading zeros.
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+macro_rules! impl_uint {
+    ($($t:ty),*) => {$(
+        impl Encodable for $t {
+            fn encode(&self, out: &mut dyn Output) {
+                trim_leading_zeros(&self.to_be_bytes()).encode(out)
+            }
+
+            fn length(&self) -> usize {
+                trim_leading_zeros(&self.to_be_bytes()).length()
+            }
+        }
+    )*};
+}
+
+impl_uint!(u16, u32, u64, u128, usize, U256);
+
+impl Encodable for u8 {
+    fn encode(&self, out: &mut dyn Output) {
+        trim_leading_zeros(&[*self]).encode(out)
+    }
+
+    fn length(&self) -> usize {
+        trim_leading_zeros(&[*self]).length()
+    }
+
+    fn encode_slice(bytes: &[Self], out: &mut dyn Output) {
+        match bytes {
+            [byte] if *byte < 0x80 => out.write(bytes),
+            _ => {
+                Header::string(bytes.len()).encode(out);
+                out.write(bytes);
+            }
+        }
+    }
+
+    fn slice_length(bytes: &[Self]) -> usize {
+        match bytes {
+            [byte] if *byte < 0x80 => 1,
+            _ => Header::string(bytes.len()).length_with_payload(),
+        }
+    }
+}
+
+impl<T> Encodable for [T]
+where
+    T: Encodable,
+{
+    fn encode(&self, out: &mut dyn Output) {
+        T::encode_slice(self, out)
+    }
+
+    fn length(&self) -> usize {
+        T::slice_length(self)
+    }
+}
+
+impl<T> Encodable for Vec<T>
+where
+    T: Encodable,
+{
+    fn encode(&self, out: &mut dyn Output) {
+        self.as_slice().encode(out)
+    }
+
+    fn length(&self) -> usize {
+        self.as_slice().length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, rlp, uint};
+
+    #[test]
+    fn encode_byte_strings() {
+        assert_eq!(rlp::encode(""), [0x80]);
+        assert_eq!(rlp::encode(&[0x00_u8][..]), [0x00]);
+        assert_eq!(rlp::encode(&[0x7f_u8][..]), [0x7f]);
+        assert_eq!(rlp::encode(&[0x80_u8][..]), [0x81, 0x80]);
+        assert_eq!(rlp::encode("dog"), b"\x83dog");
+
+        let long = "Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+        assert_eq!(rlp::encode(long), [b"\xb8\x38", long.as_bytes()].concat());
+    }
+
+    #[test]
+    fn encode_integers() {
+        assert_eq!(rlp::encode(&0_u64), [0x80]);
+        assert_eq!(rlp::encode(&15_u64), [0x0f]);
+        assert_eq!(rlp::encode(&0x80_u16), [0x81, 0x80]);
+        assert_eq!(rlp::encode(&1024_u32), [0x82, 0x04, 0x00]);
+        assert_eq!(rlp::encode(&U256::ZERO), [0x80]);
+        assert_eq!(
+            rlp::encode(&uint!("0x0100000000000000000000000000000000")),
+            [&[0x91, 0x01][..], &[0; 16]].concat(),
+        );
+        assert_eq!(rlp::encode(&true), [0x01]);
+        assert_eq!(rlp::encode(&false), [0x80]);
+        assert_eq!(rlp::encode(&0_u8), [0x80]);
+        assert_eq!(rlp::encode(&0x7f_u8), [0x7f]);
+        assert_eq!(rlp::encode(&0x80_u8), [0x81, 0x80]);
+    }
+
+    #[test]
+    fn encode_lists() {
+        assert_eq!(rlp::encode(&Vec::<u64>::new()), [0xc0]);
+        // The set theoretical representation of three.
+        let three = vec![vec![], vec![vec![]], vec![vec![], vec![Vec::<u64>::new()]]];
+        assert_eq!(
+            rlp::encode(&three),
+            [0xc7, 0xc0, 0xc1, 0xc0, 0xc3, 0xc0, 0xc1, 0xc0]
+        );
+
+        let items: [&dyn Encodable; 2] = [
+            &address!("0x1111111111111111111111111111111111111111"),
+            &1_u64,
+        ];
+        let encoded = rlp::encode(&items[..]);
+        assert_eq!(encoded[..2], [0xd6, 0x94]);
+        assert_eq!(encoded.len(), items[..].length());
+    }
+
+    #[test]
+    fn encoded_lengths() {
+        let long = vec![U256::MAX; 4];
+        assert_eq!(rlp::encode(&long).len(), long.length());
+        assert_eq!(
+            rlp::encode(&Digest::default()).len(),
+            Digest::default().length()
+        );
+        assert_eq!(
+            rlp::encode(&Address::default()).len(),
+            Address::default().length()
+        );
+        assert_eq!(
+            rlp::encode(&Bloom::default()).len(),
+            Bloom::default().length()
+        );
+    }
+}
@@ -0,0 +1,404 @@
+//! Recursive Length Prefix (RLP) serialization.
+//!
+//! This module implements the [RLP] encoding used by Ethereum for
+//! transactions, blocks, receipts and trie nodes. Values are encoded with the
+//! [`Encodable`] trait and decoded with the [`Decodable`] trait.
+//!
+//! Encoding writes to an [`Output`], which is implemented both for `Vec<u8>`
+//! buffers and for [`Hasher`], so that values can be hashed without first
+//! allocating their encoded representation.
+//!
+//! Decoding is strict and only accepts the canonical encoding of a value: byte
+//! strings and lists must use the shortest possible length prefix, single
+//! bytes below `0x80` must be encoded as themselves, and integers must not
+//! have leading zero bytes.
+//!
+//! Note that `Vec<T>` and `[T]` are encoded as RLP lists, with the exception
+//! of `Vec<u8>` and `[u8]` which are encoded as byte strings. A single `u8` is
+//! encoded as an integer, like the other unsigned integer types.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::{rlp, Digest};
+//! let value = vec!["cat", "dog"];
+//! let encoded = rlp::encode(&value);
+//! assert_eq!(encoded, b"\xc8\x83cat\x83dog");
+//! assert_eq!(rlp::digest(&value), Digest::of(&encoded));
+//! assert_eq!(rlp::decode::<Vec<String>>(&encoded).unwrap(), value);
+//! ```
+//!
+//! [RLP]: https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp/
+
+mod decode;
+mod encode;
+
+pub use self::{decode::Decodable, encode::Encodable};
+use crate::{Digest, Hasher};
+#[cfg(feature = "derive")]
+pub use ethprim_derive::{RlpDecodable, RlpEncodable};
+use std::fmt::{self, Display, Formatter};
+
+/// A destination for RLP encoded bytes.
+pub trait Output {
+    /// Writes bytes to the output.
+    fn write(&mut self, bytes: &[u8]);
+}
+
+impl Output for Vec<u8> {
+    fn write(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+impl Output for Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+/// RLP encodes a value.
+pub fn encode<T>(value: &T) -> Vec<u8>
+where
+    T: Encodable + ?Sized,
+{
+    let mut buffer = Vec::with_capacity(value.length());
+    value.encode(&mut buffer);
+    buffer
+}
+
+/// Computes the Keccak-256 digest of the RLP encoding of a value. The value is
+/// encoded directly into a [`Hasher`] without any intermediate buffers.
+pub fn digest<T>(value: &T) -> Digest
+where
+    T: Encodable + ?Sized,
+{
+    let mut hasher = Hasher::new();
+    value.encode(&mut hasher);
+    hasher.finalize()
+}
+
+/// Decodes a value from its RLP encoding. Returns an error if the encoding is
+/// not canonical or if there are trailing bytes.
+pub fn decode<T>(mut data: &[u8]) -> Result<T, DecodeError>
+where
+    T: Decodable,
+{
+    let value = T::decode(&mut data)?;
+    if !data.is_empty() {
+        return Err(DecodeError::TrailingBytes);
+    }
+    Ok(value)
+}
+
+/// Returns the length of the RLP encoding of a list of fields.
+pub fn fields_length(fields: &[&dyn Encodable]) -> usize {
+    Header::list(fields.iter().map(|f| f.length()).sum()).length_with_payload()
+}
+
+/// Encodes a list of fields followed by additional trailing fields. This
+/// allows appending fields, such as a signature, to a list without copying.
+pub fn encode_fields(fields: &[&dyn Encodable], trailer: &[&dyn Encodable], out: &mut dyn Output) {
+    let payload_length = fields.iter().chain(trailer).map(|f| f.length()).sum();
+    Header::list(payload_length).encode(out);
+    for field in fields.iter().chain(trailer) {
+        field.encode(out);
+    }
+}
+
+/// Decodes an RLP list of fields, ensuring that all of the list's items were
+/// consumed by the decoding function.
+pub fn decode_fields<T, E>(
+    buf: &mut &[u8],
+    f: impl FnOnce(&mut &[u8]) -> Result<T, E>,
+) -> Result<T, E>
+where
+    E: From<DecodeError>,
+{
+    let mut fields = Header::decode_list(buf)?;
+    let value = f(&mut fields)?;
+    if !fields.is_empty() {
+        return Err(DecodeError::ListLengthMismatch.into());
+    }
+    Ok(value)
+}
+
+/// An RLP item header, specifying whether the item is a list or a byte string
+/// and the length of its payload.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Header {
+    /// Whether or not the item is a list.
+    pub list: bool,
+    /// The length of the item payload in bytes.
+    pub payload_length: usize,
+}
+
+impl Header {
+    /// Creates a new byte string header.
+    pub fn string(payload_length: usize) -> Self {
+        Self {
+            list: false,
+            payload_length,
+        }
+    }
+
+    /// Creates a new list header.
+    pub fn list(payload_length: usize) -> Self {
+        Self {
+            list: true,
+            payload_length,
+        }
+    }
+
+    /// Returns the length of the encoded header in bytes.
+    pub fn length(&self) -> usize {
+        if self.payload_length < 56 {
+            1
+        } else {
+            1 + length_of_length(self.payload_length)
+        }
+    }
+
+    /// Returns the total length of the encoded item, including both the header
+    /// and the payload.
+    pub fn length_with_payload(&self) -> usize {
+        self.length() + self.payload_length
+    }
+
+    /// Encodes the header to the specified output.
+    pub fn encode(&self, out: &mut dyn Output) {
+        let offset = if self.list { 0xc0 } else { 0x80 };
+        if self.payload_length < 56 {
+            out.write(&[offset + self.payload_length as u8]);
+        } else {
+            let bytes = self.payload_length.to_be_bytes();
+            let len = length_of_length(self.payload_length);
+            out.write(&[offset + 55 + len as u8]);
+            out.write(&bytes[bytes.len() - len..]);
+        }
+    }
+
+    /// Decodes a header, advancing the buffer to the start of the payload.
+    ///
+    /// Note that single bytes below `0x80` are their own encoding and have no
+    /// header. In this case, a byte string header with a payload length of 1
+    /// is returned, and the buffer is left unchanged.
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let (&prefix, rest) = buf.split_first().ok_or(DecodeError::UnexpectedEof)?;
+        let (header, rest) = match prefix {
+            0x00..=0x7f => (Self::string(1), *buf),
+            0x80..=0xb7 => (Self::string((prefix - 0x80) as usize), rest),
+            0xb8..=0xbf => {
+                let (len, rest) = decode_length(rest, (prefix - 0xb7) as usize)?;
+                (Self::string(len), rest)
+            }
+            0xc0..=0xf7 => (Self::list((prefix - 0xc0) as usize), rest),
+            0xf8..=0xff => {
+                let (len, rest) = decode_length(rest, (prefix - 0xf7) as usize)?;
+                (Self::list(len), rest)
+            }
+        };
+
+        if rest.len() < header.payload_length {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        if prefix == 0x81 && rest[0] < 0x80 {
+            return Err(DecodeError::NonCanonicalSingleByte);
+        }
+
+        *buf = rest;
+        Ok(header)
+    }
+
+    /// Decodes a byte string header and returns its payload, advancing the
+    /// buffer past the item.
+    pub fn decode_bytes<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], DecodeError> {
+        Self::decode_payload(buf, false)
+    }
+
+    /// Decodes a list header and returns its payload, advancing the buffer
+    /// past the item.
+    pub fn decode_list<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], DecodeError> {
+        Self::decode_payload(buf, true)
+    }
+
+    fn decode_payload<'a>(buf: &mut &'a [u8], list: bool) -> Result<&'a [u8], DecodeError> {
+        let header = Self::decode(buf)?;
+        match (header.list, list) {
+            (true, false) => return Err(DecodeError::UnexpectedList),
+            (false, true) => return Err(DecodeError::UnexpectedString),
+            _ => {}
+        }
+        let (payload, rest) = buf.split_at(header.payload_length);
+        *buf = rest;
+        Ok(payload)
+    }
+}
+
+/// Returns the number of bytes needed to represent a length.
+fn length_of_length(len: usize) -> usize {
+    (usize::BITS - len.leading_zeros()).div_ceil(8) as usize
+}
+
+/// Decodes a long-form big-endian length prefix.
+fn decode_length(buf: &[u8], len: usize) -> Result<(usize, &[u8]), DecodeError> {
+    if buf.len() < len {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    let (bytes, rest) = buf.split_at(len);
+    if bytes[0] == 0 {
+        return Err(DecodeError::LeadingZero);
+    }
+    if len > size_of::<usize>() {
+        return Err(DecodeError::Overflow);
+    }
+    let length = bytes
+        .iter()
+        .fold(0_usize, |acc, byte| (acc << 8) | *byte as usize);
+    if length < 56 {
+        return Err(DecodeError::NonCanonicalSize);
+    }
+    Ok((length, rest))
+}
+
+/// Represents an error RLP decoding data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The data ended unexpectedly.
+    UnexpectedEof,
+    /// An integer or length prefix has leading zero bytes.
+    LeadingZero,
+    /// A length prefix uses the long form for a short payload.
+    NonCanonicalSize,
+    /// A single byte below `0x80` is encoded as a byte string.
+    NonCanonicalSingleByte,
+    /// An integer or length does not fit in its type.
+    Overflow,
+    /// A list was found where a byte string was expected.
+    UnexpectedList,
+    /// A byte string was found where a list was expected.
+    UnexpectedString,
+    /// A byte string does not have the expected length.
+    InvalidLength,
+    /// A value is not valid for its type.
+    InvalidValue,
+    /// A list has more items than expected.
+    ListLengthMismatch,
+    /// The data has trailing bytes after the encoded value.
+    TrailingBytes,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => write!(f, "unexpected end of RLP encoded data"),
+            Self::LeadingZero => write!(f, "leading zero bytes in RLP encoded integer"),
+            Self::NonCanonicalSize => write!(f, "non-canonical RLP length prefix"),
+            Self::NonCanonicalSingleByte => write!(f, "non-canonical RLP single byte encoding"),
+            Self::Overflow => write!(f, "RLP encoded integer overflow"),
+            Self::UnexpectedList => write!(f, "unexpected RLP list"),
+            Self::UnexpectedString => write!(f, "unexpected RLP byte string"),
+            Self::InvalidLength => write!(f, "invalid RLP byte string length"),
+            Self::InvalidValue => write!(f, "invalid value in RLP encoded data"),
+            Self::ListLengthMismatch => write!(f, "RLP list has unexpected items"),
+            Self::TrailingBytes => write!(f, "trailing bytes after RLP encoded data"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_lengths() {
+        for (payload_length, encoded) in [
+            (0, &[0x80][..]),
+            (55, &[0xb7]),
+            (56, &[0xb8, 56]),
+            (255, &[0xb8, 0xff]),
+            (256, &[0xb9, 0x01, 0x00]),
+            (0x10000, &[0xba, 0x01, 0x00, 0x00]),
+        ] {
+            let header = Header::string(payload_length);
+            let mut buffer = Vec::new();
+            header.encode(&mut buffer);
+            assert_eq!(buffer, encoded);
+            assert_eq!(header.length(), encoded.len());
+        }
+    }
+
+    #[test]
+    fn non_canonical_headers() {
+        for (data, err) in [
+            (&[][..], DecodeError::UnexpectedEof),
+            (&[0x82, 0x01], DecodeError::UnexpectedEof),
+            (&[0x81, 0x7f], DecodeError::NonCanonicalSingleByte),
+            (&[0xb8, 0x01, 0x00], DecodeError::NonCanonicalSize),
+            (&[0xb9, 0x00, 0x38], DecodeError::LeadingZero),
+            (&[0xf8, 0x37], DecodeError::NonCanonicalSize),
+            (
+                &[0xbf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+                DecodeError::UnexpectedEof,
+            ),
+        ] {
+            assert_eq!(Header::decode(&mut &data[..]).unwrap_err(), err);
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derive_structs() {
+        use crate::{address, Address, U256};
+
+        #[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+        struct Account {
+            nonce: u64,
+            balance: U256,
+            owner: Address,
+            code: Vec<u8>,
+        }
+
+        #[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+        struct Wrapper<T>(T, Vec<T>);
+
+        let account = Account {
+            nonce: 1,
+            balance: U256::new(1000),
+            owner: address!("0x1111111111111111111111111111111111111111"),
+            code: vec![0x60, 0x00],
+        };
+        let encoded = encode(&account);
+        assert_eq!(
+            encoded,
+            [
+                &[0xdc, 0x01, 0x82, 0x03, 0xe8, 0x94][..],
+                &[0x11; 20],
+                &[0x82, 0x60, 0x00],
+            ]
+            .concat(),
+        );
+        assert_eq!(account.length(), encoded.len());
+        assert_eq!(decode::<Account>(&encoded).unwrap(), account);
+
+        let wrapper = Wrapper(1_u64, vec![2, 3]);
+        assert_eq!(encode(&wrapper), [0xc4, 0x01, 0xc2, 0x02, 0x03]);
+        assert_eq!(decode::<Wrapper<u64>>(&encode(&wrapper)).unwrap(), wrapper);
+        assert_eq!(
+            decode::<Wrapper<u64>>(&[0xc5, 0x01, 0xc2, 0x02, 0x03, 0x04]).unwrap_err(),
+            DecodeError::ListLengthMismatch,
+        );
+    }
+
+    #[test]
+    fn decode_trailing_bytes() {
+        assert_eq!(
+            decode::<u64>(&[0x01, 0x02]).unwrap_err(),
+            DecodeError::TrailingBytes
+        );
+    }
+}
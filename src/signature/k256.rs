@@ -0,0 +1,146 @@
+//! Signature recovery and verification using the `k256` crate.
+
+use super::Signature;
+use crate::{Address, Digest};
+use core::fmt::{self, Display, Formatter};
+use k256::ecdsa::{self, RecoveryId, VerifyingKey};
+
+impl Signature {
+    /// Recovers the public key of the signer of a prehashed message.
+    ///
+    /// Signatures with a high `s` value are rejected, as per [EIP-2]. Use
+    /// [`Signature::normalize_s()`] first in order to accept them.
+    ///
+    /// [EIP-2]: https://eips.ethereum.org/EIPS/eip-2
+    pub fn recover_public_key(
+        &self,
+        digest: Digest,
+    ) -> Result<VerifyingKey, RecoverSignatureError> {
+        if self.is_high_s() {
+            return Err(RecoverSignatureError::HighS);
+        }
+        let signature = ecdsa::Signature::from_scalars(self.r.to_be_bytes(), self.s.to_be_bytes())
+            .map_err(|_| RecoverSignatureError::InvalidSignature)?;
+        let recovery_id = RecoveryId::new(self.y_parity, false);
+        VerifyingKey::recover_from_prehash(&digest.0, &signature, recovery_id)
+            .map_err(|_| RecoverSignatureError::InvalidSignature)
+    }
+
+    /// Recovers the address of the signer of a prehashed message, like the
+    /// EVM `ecrecover` precompile.
+    ///
+    /// Signatures with a high `s` value are rejected, as per [EIP-2]. Use
+    /// [`Signature::normalize_s()`] first in order to accept them.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::{address, message, Signature};
+    /// let signature = "0x\
+    ///     68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+    ///     7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"
+    ///     .parse::<Signature>()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     signature.recover_address(message::eip191_hash("Hello World")).unwrap(),
+    ///     address!("0x2e988A386a799F506693793c6A5AF6B54dfAaBfB"),
+    /// );
+    /// ```
+    ///
+    /// [EIP-2]: https://eips.ethereum.org/EIPS/eip-2
+    pub fn recover_address(&self, digest: Digest) -> Result<Address, RecoverSignatureError> {
+        let key = self.recover_public_key(digest)?;
+        Ok(Address::from_public_key(&key))
+    }
+
+    /// Returns `true` if the signature over a prehashed message is valid and
+    /// was produced by the specified address.
+    pub fn verify(&self, address: Address, digest: Digest) -> bool {
+        matches!(self.recover_address(digest), Ok(signer) if signer == address)
+    }
+}
+
+/// Represents an error recovering the signer of a signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecoverSignatureError {
+    /// The signature has a malleable high `s` value.
+    HighS,
+    /// The signature is invalid and no signer can be recovered.
+    InvalidSignature,
+}
+
+impl Display for RecoverSignatureError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::HighS => write!(f, "signature has a high s value"),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RecoverSignatureError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest, uint};
+
+    #[test]
+    fn recover_eip712_signer() {
+        // Example from EIP-712.
+        let signature = Signature::new(
+            uint!("0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d"),
+            uint!("0x07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562"),
+            true,
+        );
+        let digest = digest!("0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2");
+        let signer = address!("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826");
+
+        assert_eq!(signature.recover_address(digest).unwrap(), signer);
+        assert_eq!(
+            signature
+                .recover_public_key(digest)
+                .unwrap()
+                .to_encoded_point(false)
+                .as_bytes(),
+            crate::hex::decode::<65>(
+                "0x040947751e3022ecf3016be03ec77ab0ce3c2662b4843898cb068d74f698ccc8ad\
+                   75aa17564ae80a20bb044ee7a6d903e8e8df624b089c95d66a0570f051e5a05b"
+            )
+            .unwrap(),
+        );
+        assert!(signature.verify(signer, digest));
+        assert!(!signature.verify(Address::default(), digest));
+    }
+
+    #[test]
+    fn recover_invalid_signatures() {
+        let digest = Digest::of("hello");
+        let valid = Signature::new(
+            uint!("0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d"),
+            uint!("0x07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562"),
+            true,
+        );
+        let malleable = Signature::new(
+            valid.r,
+            super::super::SECP256K1_N - valid.s,
+            !valid.y_parity,
+        );
+
+        assert_eq!(
+            malleable.recover_address(digest).unwrap_err(),
+            RecoverSignatureError::HighS,
+        );
+        assert_eq!(
+            malleable.normalize_s().recover_address(digest),
+            valid.recover_address(digest),
+        );
+        assert_eq!(
+            Signature::default().recover_address(digest).unwrap_err(),
+            RecoverSignatureError::InvalidSignature,
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Recoverable ECDSA signatures.
+
+#[cfg(feature = "k256")]
+mod k256;
+#[cfg(feature = "serde")]
+mod serde;
+
+#[cfg(feature = "k256")]
+pub use self::k256::RecoverSignatureError;
+
+use crate::{
+    hex::{self, Alphabet, FormattingBuffer, ParseHexError},
+    U256,
+};
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+
+/// The order of the secp256k1 curve.
+const SECP256K1_N: U256 = U256::from_words(
+    0xfffffffffffffffffffffffffffffffe,
+    0xbaaedce6af48a03bbfd25e8cd0364141,
+);
+
+/// Half the order of the secp256k1 curve, the largest `s` value of a
+/// non-malleable signature.
+const SECP256K1_HALF_N: U256 = U256::from_words(
+    0x7fffffffffffffffffffffffffffffff,
+    0x5d576e7357a4501ddfe92f46681b20a0,
+);
+
+/// A recoverable secp256k1 ECDSA signature.
+///
+/// Signatures are formatted and parsed as 65-byte `r || s || v` hex strings,
+/// where `v` is `27` or `28`. Parsing additionally accepts `v` values of `0`
+/// or `1`, as well as 64-byte [EIP-2098] compact signatures.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{uint, Signature};
+/// let signature = "0x\
+///     0000000000000000000000000000000000000000000000000000000000000001\
+///     0000000000000000000000000000000000000000000000000000000000000002\
+///     1c"
+///     .parse::<Signature>()
+///     .unwrap();
+/// assert_eq!(signature, Signature::new(uint!("1"), uint!("2"), true));
+/// ```
+///
+/// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct Signature {
+    /// The `r` component of the signature.
+    pub r: U256,
+    /// The `s` component of the signature.
+    pub s: U256,
+    /// The parity of the `y` coordinate of the signature's curve point `R`,
+    /// used for public key recovery.
+    pub y_parity: bool,
+}
+
+impl Signature {
+    /// Creates a new signature from its components.
+    pub const fn new(r: U256, s: U256, y_parity: bool) -> Self {
+        Self { r, s, y_parity }
+    }
+
+    /// Creates a signature from its `v`, `r` and `s` components. The `v` value
+    /// can either be a `y_parity` of `0` or `1`, a legacy value of `27` or
+    /// `28`, or an [EIP-155] value encoding a chain ID.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::{uint, Signature};
+    /// let (r, s) = (uint!("1"), uint!("2"));
+    /// for v in [1, 28, 38] {
+    ///     assert_eq!(Signature::from_vrs(v, r, s).unwrap(), Signature::new(r, s, true));
+    /// }
+    /// assert!(Signature::from_vrs(29, r, s).is_err());
+    ///
+    /// let signature = Signature::new(r, s, false);
+    /// let v = signature.v(Some(u64::MAX));
+    /// assert_eq!(Signature::from_vrs(v, r, s).unwrap(), signature);
+    /// ```
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    pub fn from_vrs(v: u128, r: U256, s: U256) -> Result<Self, ParseSignatureError> {
+        let y_parity = match v {
+            0 | 1 => v == 1,
+            27 | 28 => v == 28,
+            35.. => (v - 35) % 2 == 1,
+            _ => return Err(ParseSignatureError::InvalidV(v)),
+        };
+        Ok(Self::new(r, s, y_parity))
+    }
+
+    /// Returns the legacy `v` value of the signature. This is `27 + y_parity`
+    /// without a chain ID, and `35 + 2 * chain_id + y_parity` with an [EIP-155]
+    /// chain ID.
+    ///
+    /// The value is returned as a `u128` so that it can represent `v` values
+    /// for all possible 64-bit chain IDs.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    pub fn v(&self, chain_id: Option<u64>) -> u128 {
+        let y_parity = self.y_parity as u128;
+        match chain_id {
+            Some(chain_id) => 35 + 2 * chain_id as u128 + y_parity,
+            None => 27 + y_parity,
+        }
+    }
+
+    /// Creates a signature from its 65-byte `r || s || v` representation. The
+    /// `v` byte must be one of `0`, `1`, `27` or `28`.
+    pub fn from_bytes(bytes: &[u8; 65]) -> Result<Self, ParseSignatureError> {
+        let (r, s) = split_words(bytes[..64].try_into().unwrap());
+        match bytes[64] {
+            v @ (0 | 1 | 27 | 28) => Self::from_vrs(v as _, r, s),
+            v => Err(ParseSignatureError::InvalidV(v as _)),
+        }
+    }
+
+    /// Returns the 65-byte `r || s || v` representation of the signature,
+    /// where `v` is `27` or `28`.
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0; 65];
+        bytes[..32].copy_from_slice(&self.r.to_be_bytes());
+        bytes[32..64].copy_from_slice(&self.s.to_be_bytes());
+        bytes[64] = self.v(None) as _;
+        bytes
+    }
+
+    /// Creates a signature from its 64-byte [EIP-2098] compact `r || yParityAndS`
+    /// representation, where the `y_parity` is stored in the highest bit of
+    /// `s`.
+    ///
+    /// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+    pub fn from_compact(bytes: &[u8; 64]) -> Self {
+        let (r, vs) = split_words(bytes);
+        let y_parity = vs.high() >> 127 == 1;
+        Self::new(r, vs & (U256::MAX >> 1), y_parity)
+    }
+
+    /// Returns the 64-byte [EIP-2098] compact representation of the
+    /// signature. Note that compact signatures can only represent signatures
+    /// with a low `s` value, so the signature is normalized first.
+    ///
+    /// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+    pub fn to_compact(&self) -> [u8; 64] {
+        let signature = self.normalize_s();
+        let vs = signature.s | U256::from_words((signature.y_parity as u128) << 127, 0);
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&signature.r.to_be_bytes());
+        bytes[32..].copy_from_slice(&vs.to_be_bytes());
+        bytes
+    }
+
+    /// Returns `true` if the signature has a high `s` value. For every valid
+    /// signature `(r, s)`, the signature `(r, n - s)` with the opposite
+    /// `y_parity` is also valid, and [EIP-2] requires transaction signatures to
+    /// use the low `s` value to prevent this malleability.
+    ///
+    /// [EIP-2]: https://eips.ethereum.org/EIPS/eip-2
+    pub fn is_high_s(&self) -> bool {
+        self.s > SECP256K1_HALF_N
+    }
+
+    /// Returns the equivalent signature with a low `s` value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::{uint, Signature};
+    /// let high = Signature::new(
+    ///     uint!("1"),
+    ///     uint!("0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140"),
+    ///     false,
+    /// );
+    /// assert!(high.is_high_s());
+    /// assert_eq!(high.normalize_s(), Signature::new(uint!("1"), uint!("1"), true));
+    /// ```
+    pub fn normalize_s(&self) -> Self {
+        if self.is_high_s() {
+            Self::new(self.r, SECP256K1_N - self.s, !self.y_parity)
+        } else {
+            *self
+        }
+    }
+
+    /// Returns a stack-allocated formatted string of the 65-byte signature.
+    fn fmt_buffer(&self) -> FormattingBuffer<132> {
+        hex::encode::<65, 132>(&self.to_bytes(), Alphabet::Lower)
+    }
+}
+
+/// Splits 64 bytes into two big-endian 256-bit words.
+fn split_words(bytes: &[u8; 64]) -> (U256, U256) {
+    let (a, b) = bytes.split_at(32);
+    (
+        U256::from_be_bytes(a.try_into().unwrap()),
+        U256::from_be_bytes(b.try_into().unwrap()),
+    )
+}
+
+impl Debug for Signature {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Signature")
+            .field("r", &format_args!("{:#x}", self.r))
+            .field("s", &format_args!("{:#x}", self.s))
+            .field("y_parity", &self.y_parity)
+            .finish()
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(self.fmt_buffer().as_str())
+    }
+}
+
+impl FromStr for Signature {
+    type Err = ParseSignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match hex::decode::<65>(s) {
+            Ok(bytes) => Self::from_bytes(&bytes),
+            Err(ParseHexError::InvalidLength) => Ok(Self::from_compact(&hex::decode::<64>(s)?)),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Represents an error parsing a signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseSignatureError {
+    /// The signature does not have the correct length.
+    InvalidLength,
+    /// An invalid character was found.
+    InvalidHexCharacter { c: char, index: usize },
+    /// The signature has an invalid `v` value.
+    InvalidV(u128),
+}
+
+impl Display for ParseSignatureError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "invalid signature length"),
+            Self::InvalidHexCharacter { c, index } => {
+                let (c, index) = (*c, *index);
+                write!(f, "{}", ParseHexError::InvalidHexCharacter { c, index })
+            }
+            Self::InvalidV(v) => write!(f, "invalid signature v value {v}"),
+        }
+    }
+}
+
+impl From<ParseHexError> for ParseSignatureError {
+    fn from(err: ParseHexError) -> Self {
+        match err {
+            ParseHexError::InvalidLength => Self::InvalidLength,
+            ParseHexError::InvalidHexCharacter { c, index } => {
+                Self::InvalidHexCharacter { c, index }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseSignatureError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uint;
+
+    #[test]
+    fn secp256k1_constants() {
+        assert_eq!(SECP256K1_HALF_N, SECP256K1_N / 2);
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let signature = Signature::new(uint!("0x1234"), uint!("0x5678"), true);
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes[64], 28);
+        assert_eq!(Signature::from_bytes(&bytes).unwrap(), signature);
+
+        let mut bytes = bytes;
+        bytes[64] = 0;
+        assert!(!Signature::from_bytes(&bytes).unwrap().y_parity);
+        bytes[64] = 2;
+        assert_eq!(
+            Signature::from_bytes(&bytes).unwrap_err(),
+            ParseSignatureError::InvalidV(2)
+        );
+    }
+
+    #[test]
+    fn eip2098_compact_signatures() {
+        // Test vectors from EIP-2098.
+        for (r, s, y_parity, compact) in [
+            (
+                uint!("0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90"),
+                uint!("0x7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"),
+                false,
+                "0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+                 7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+            ),
+            (
+                uint!("0x9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76"),
+                uint!("0x139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"),
+                true,
+                "0x9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76\
+                 939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793",
+            ),
+        ] {
+            let signature = Signature::new(r, s, y_parity);
+            assert_eq!(compact.parse::<Signature>().unwrap(), signature);
+            assert_eq!(
+                Signature::from_compact(&hex::decode(compact).unwrap()),
+                signature
+            );
+            assert_eq!(signature.to_compact(), hex::decode::<64>(compact).unwrap());
+        }
+    }
+
+    #[test]
+    fn low_s_normalization() {
+        let low = Signature::new(uint!("1"), SECP256K1_HALF_N, false);
+        assert!(!low.is_high_s());
+        assert_eq!(low.normalize_s(), low);
+
+        let high = Signature::new(uint!("1"), SECP256K1_HALF_N + 1, false);
+        assert!(high.is_high_s());
+        assert_eq!(
+            high.normalize_s(),
+            Signature::new(uint!("1"), SECP256K1_HALF_N, true)
+        );
+        assert_eq!(
+            Signature::from_compact(&high.to_compact()),
+            high.normalize_s()
+        );
+    }
+
+    #[test]
+    fn parse_and_format() {
+        let s = "0x\
+            1111111111111111111111111111111111111111111111111111111111111111\
+            2222222222222222222222222222222222222222222222222222222222222222\
+            1b";
+        let signature = s.parse::<Signature>().unwrap();
+        assert_eq!(signature.to_string(), s);
+        assert_eq!(
+            "0x1234".parse::<Signature>().unwrap_err(),
+            ParseSignatureError::InvalidLength
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Serde serialization implementation for signatures.
+
+use super::Signature;
+use crate::U256;
+use core::fmt::{self, Formatter};
+use serde::{
+    de::{self, value::MapAccessDeserializer, Deserializer, MapAccess, Visitor},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SignatureVisitor)
+    }
+}
+
+struct SignatureVisitor;
+
+impl<'de> Visitor<'de> for SignatureVisitor {
+    type Value = Signature;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a `0x`-prefixed 65 or 64-byte hex string, or an object with signature fields")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        s.strip_prefix("0x")
+            .ok_or_else(|| de::Error::custom("missing `0x`-prefix"))?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let fields = Fields::deserialize(MapAccessDeserializer::new(map))?;
+        let y_parity = match (fields.y_parity, fields.v) {
+            (Some(y_parity), _) if y_parity <= U256::ONE => y_parity == U256::ONE,
+            (Some(_), _) => return Err(de::Error::custom("invalid signature y-parity")),
+            (None, Some(v)) => {
+                let v = u128::try_from(v).map_err(|_| de::Error::custom("invalid signature v"))?;
+                Signature::from_vrs(v, fields.r, fields.s)
+                    .map_err(de::Error::custom)?
+                    .y_parity
+            }
+            (None, None) => return Err(de::Error::missing_field("yParity")),
+        };
+        Ok(Signature::new(fields.r, fields.s, y_parity))
+    }
+}
+
+/// The signature fields, as included in JSON RPC transaction objects.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Fields {
+    r: U256,
+    s: U256,
+    #[serde(default)]
+    y_parity: Option<U256>,
+    #[serde(default)]
+    v: Option<U256>,
+}
+
+impl Serialize for Signature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let buffer = self.fmt_buffer();
+        serializer.serialize_str(buffer.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uint;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_signature() {
+        let signature = Signature::new(uint!("0x1234"), uint!("0x5678"), true);
+        for value in [
+            json!(
+                "0x0000000000000000000000000000000000000000000000000000000000001234\
+                   000000000000000000000000000000000000000000000000000000000000567801"
+            ),
+            json!(
+                "0x0000000000000000000000000000000000000000000000000000000000001234\
+                   8000000000000000000000000000000000000000000000000000000000005678"
+            ),
+            json!({ "r": "0x1234", "s": "0x5678", "yParity": "0x1" }),
+            json!({ "r": "0x1234", "s": "0x5678", "v": "0x1c" }),
+            json!({ "r": "0x1234", "s": "0x5678", "v": "0x26", "yParity": "0x1" }),
+        ] {
+            assert_eq!(
+                serde_json::from_value::<Signature>(value).unwrap(),
+                signature
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_invalid_signature() {
+        for value in [
+            json!({ "r": "0x1234", "s": "0x5678" }),
+            json!({ "r": "0x1234", "s": "0x5678", "yParity": "0x2" }),
+            json!({ "r": "0x1234", "s": "0x5678", "v": "0x1d" }),
+            json!(
+                "0000000000000000000000000000000000000000000000000000000000001234\
+                 000000000000000000000000000000000000000000000000000000000000567801"
+            ),
+        ] {
+            assert!(serde_json::from_value::<Signature>(value).is_err());
+        }
+    }
+
+    #[test]
+    fn serialize_signature() {
+        let signature = Signature::new(uint!("0x1234"), uint!("0x5678"), false);
+        assert_eq!(
+            serde_json::to_value(signature).unwrap(),
+            json!(
+                "0x0000000000000000000000000000000000000000000000000000000000001234\
+                   00000000000000000000000000000000000000000000000000000000000056781b"
+            ),
+        );
+    }
+}
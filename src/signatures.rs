@@ -0,0 +1,305 @@
+//! Embedded database of well-known function and event signatures.
+//!
+//! This module contains a curated table of common function and event
+//! signatures from popular standards and protocols (ERC-20, ERC-721,
+//! ERC-1155, Multicall, Safe, Uniswap and Permit2). It can be used for labelling
+//! calldata and logs offline, without relying on an external signature
+//! database. The selectors and topics are computed at compile time.
+
+use crate::{keccak, Digest};
+
+/// Returns the candidate function signatures for a 4-byte selector.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::signatures;
+/// assert_eq!(
+///     signatures::functions([0xa9, 0x05, 0x9c, 0xbb]).collect::<Vec<_>>(),
+///     ["transfer(address,uint256)"],
+/// );
+/// ```
+pub fn functions(selector: [u8; 4]) -> impl Iterator<Item = &'static str> {
+    FUNCTIONS
+        .iter()
+        .filter(move |(_, digest)| digest[..4] == selector)
+        .map(|(signature, _)| *signature)
+}
+
+/// Returns the candidate event signatures for a log topic.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{digest, signatures};
+/// assert_eq!(
+///     signatures::events(digest!(
+///         "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+///     ))
+///     .collect::<Vec<_>>(),
+///     ["Transfer(address,address,uint256)"],
+/// );
+/// ```
+pub fn events(topic: Digest) -> impl Iterator<Item = &'static str> {
+    EVENTS
+        .iter()
+        .filter(move |(_, digest)| *digest == topic)
+        .map(|(signature, _)| *signature)
+}
+
+macro_rules! table {
+    ($($signature:literal,)*) => {
+        &[$(($signature, keccak!($signature.as_bytes())),)*]
+    };
+}
+
+static FUNCTIONS: &[(&str, Digest)] = table![
+    // ERC-20, EIP-2612 and WETH
+    "name()",
+    "symbol()",
+    "decimals()",
+    "totalSupply()",
+    "balanceOf(address)",
+    "transfer(address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "approve(address,uint256)",
+    "allowance(address,address)",
+    "increaseAllowance(address,uint256)",
+    "decreaseAllowance(address,uint256)",
+    "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+    "nonces(address)",
+    "DOMAIN_SEPARATOR()",
+    "deposit()",
+    "withdraw(uint256)",
+    // ERC-165 and ERC-721
+    "supportsInterface(bytes4)",
+    "ownerOf(uint256)",
+    "safeTransferFrom(address,address,uint256)",
+    "safeTransferFrom(address,address,uint256,bytes)",
+    "getApproved(uint256)",
+    "setApprovalForAll(address,bool)",
+    "isApprovedForAll(address,address)",
+    "tokenURI(uint256)",
+    "onERC721Received(address,address,uint256,bytes)",
+    // ERC-1155
+    "balanceOf(address,uint256)",
+    "balanceOfBatch(address[],uint256[])",
+    "safeTransferFrom(address,address,uint256,uint256,bytes)",
+    "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+    "uri(uint256)",
+    "onERC1155Received(address,address,uint256,uint256,bytes)",
+    "onERC1155BatchReceived(address,address,uint256[],uint256[],bytes)",
+    // Multicall
+    "aggregate((address,bytes)[])",
+    "tryAggregate(bool,(address,bytes)[])",
+    "blockAndAggregate((address,bytes)[])",
+    "tryBlockAndAggregate(bool,(address,bytes)[])",
+    "aggregate3((address,bool,bytes)[])",
+    "aggregate3Value((address,bool,uint256,bytes)[])",
+    "getEthBalance(address)",
+    "getBlockNumber()",
+    "getBlockHash(uint256)",
+    "getLastBlockHash()",
+    "getCurrentBlockTimestamp()",
+    "getCurrentBlockCoinbase()",
+    "getCurrentBlockGasLimit()",
+    "getChainId()",
+    "getBasefee()",
+    "multicall(bytes[])",
+    "multicall(uint256,bytes[])",
+    "multicall(bytes32,bytes[])",
+    // Safe
+    "setup(address[],uint256,address,bytes,address,address,uint256,address)",
+    "execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)",
+    "execTransactionFromModule(address,uint256,bytes,uint8)",
+    "execTransactionFromModuleReturnData(address,uint256,bytes,uint8)",
+    "getTransactionHash(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,uint256)",
+    "checkSignatures(bytes32,bytes,bytes)",
+    "approveHash(bytes32)",
+    "domainSeparator()",
+    "nonce()",
+    "VERSION()",
+    "getOwners()",
+    "getThreshold()",
+    "isOwner(address)",
+    "addOwnerWithThreshold(address,uint256)",
+    "removeOwner(address,address,uint256)",
+    "swapOwner(address,address,address)",
+    "changeThreshold(uint256)",
+    "enableModule(address)",
+    "disableModule(address,address)",
+    "isModuleEnabled(address)",
+    "getModulesPaginated(address,uint256)",
+    "setGuard(address)",
+    "setFallbackHandler(address)",
+    "createProxyWithNonce(address,bytes,uint256)",
+    // Uniswap V2
+    "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+    "swapTokensForExactTokens(uint256,uint256,address[],address,uint256)",
+    "swapExactETHForTokens(uint256,address[],address,uint256)",
+    "swapTokensForExactETH(uint256,uint256,address[],address,uint256)",
+    "swapExactTokensForETH(uint256,uint256,address[],address,uint256)",
+    "swapETHForExactTokens(uint256,address[],address,uint256)",
+    "addLiquidity(address,address,uint256,uint256,uint256,uint256,address,uint256)",
+    "addLiquidityETH(address,uint256,uint256,uint256,address,uint256)",
+    "removeLiquidity(address,address,uint256,uint256,uint256,address,uint256)",
+    "removeLiquidityETH(address,uint256,uint256,uint256,address,uint256)",
+    "getAmountsOut(uint256,address[])",
+    "getAmountsIn(uint256,address[])",
+    "getPair(address,address)",
+    "createPair(address,address)",
+    "getReserves()",
+    "token0()",
+    "token1()",
+    "swap(uint256,uint256,address,bytes)",
+    "sync()",
+    "skim(address)",
+    // Uniswap V3 and Universal Router
+    "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "exactInput((bytes,address,uint256,uint256,uint256))",
+    "exactOutputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "exactOutput((bytes,address,uint256,uint256,uint256))",
+    "exactInputSingle((address,address,uint24,address,uint256,uint256,uint160))",
+    "exactInput((bytes,address,uint256,uint256))",
+    "exactOutputSingle((address,address,uint24,address,uint256,uint256,uint160))",
+    "exactOutput((bytes,address,uint256,uint256))",
+    "getPool(address,address,uint24)",
+    "slot0()",
+    "liquidity()",
+    "fee()",
+    "swap(address,bool,int256,uint160,bytes)",
+    "uniswapV3SwapCallback(int256,int256,bytes)",
+    "execute(bytes,bytes[])",
+    "execute(bytes,bytes[],uint256)",
+    // Permit2
+    "permit(address,((address,uint160,uint48,uint48),address,uint256),bytes)",
+    "permit(address,((address,uint160,uint48,uint48)[],address,uint256),bytes)",
+    "permitTransferFrom(((address,uint256),uint256,uint256),(address,uint256),address,bytes)",
+    "permitWitnessTransferFrom(((address,uint256),uint256,uint256),(address,uint256),address,bytes32,string,bytes)",
+    "transferFrom(address,address,uint160,address)",
+    "approve(address,address,uint160,uint48)",
+    "allowance(address,address,address)",
+    "invalidateNonces(address,address,uint48)",
+    "invalidateUnorderedNonces(uint256,uint256)",
+    "nonceBitmap(address,uint256)",
+    "lockdown((address,address)[])",
+];
+
+static EVENTS: &[(&str, Digest)] = table![
+    // ERC-20, ERC-721 and WETH
+    "Transfer(address,address,uint256)",
+    "Approval(address,address,uint256)",
+    "ApprovalForAll(address,address,bool)",
+    "Deposit(address,uint256)",
+    "Withdrawal(address,uint256)",
+    // ERC-1155
+    "TransferSingle(address,address,address,uint256,uint256)",
+    "TransferBatch(address,address,address,uint256[],uint256[])",
+    "URI(string,uint256)",
+    // Safe
+    "SafeSetup(address,address[],uint256,address,address)",
+    "ExecutionSuccess(bytes32,uint256)",
+    "ExecutionFailure(bytes32,uint256)",
+    "ExecutionFromModuleSuccess(address)",
+    "ExecutionFromModuleFailure(address)",
+    "ApproveHash(bytes32,address)",
+    "SafeReceived(address,uint256)",
+    "AddedOwner(address)",
+    "RemovedOwner(address)",
+    "ChangedThreshold(uint256)",
+    "EnabledModule(address)",
+    "DisabledModule(address)",
+    "ChangedGuard(address)",
+    "ChangedFallbackHandler(address)",
+    "ProxyCreation(address,address)",
+    // Uniswap V2
+    "PairCreated(address,address,address,uint256)",
+    "Swap(address,uint256,uint256,uint256,uint256,address)",
+    "Sync(uint112,uint112)",
+    "Mint(address,uint256,uint256)",
+    "Burn(address,uint256,uint256,address)",
+    // Uniswap V3
+    "PoolCreated(address,address,uint24,int24,address)",
+    "Initialize(uint160,int24)",
+    "Swap(address,address,int256,int256,uint160,uint128,int24)",
+    "Mint(address,address,int24,int24,uint128,uint256,uint256)",
+    "Burn(address,int24,int24,uint128,uint256,uint256)",
+    "Collect(address,address,int24,int24,uint128,uint128)",
+    // Permit2
+    "Approval(address,address,address,uint160,uint48)",
+    "Permit(address,address,address,uint160,uint48,uint48)",
+    "NonceInvalidation(address,address,address,uint48,uint48)",
+    "UnorderedNonceInvalidation(address,uint256,uint256)",
+    "Lockdown(address,address,address)",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digest;
+
+    #[test]
+    fn well_known_selectors() {
+        for (selector, signature) in [
+            ([0x70, 0xa0, 0x82, 0x31], "balanceOf(address)"),
+            ([0x09, 0x5e, 0xa7, 0xb3], "approve(address,uint256)"),
+            ([0x82, 0xad, 0x56, 0xcb], "aggregate3((address,bool,bytes)[])"),
+            (
+                [0x6a, 0x76, 0x12, 0x02],
+                "execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)",
+            ),
+            (
+                [0x38, 0xed, 0x17, 0x39],
+                "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+            ),
+            (
+                [0x2b, 0x67, 0xb5, 0x70],
+                "permit(address,((address,uint160,uint48,uint48),address,uint256),bytes)",
+            ),
+        ] {
+            assert_eq!(functions(selector).collect::<Vec<_>>(), [signature]);
+        }
+    }
+
+    #[test]
+    fn well_known_topics() {
+        for (topic, signature) in [
+            (
+                digest!("0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"),
+                "Approval(address,address,uint256)",
+            ),
+            (
+                digest!("0xd78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822"),
+                "Swap(address,uint256,uint256,uint256,uint256,address)",
+            ),
+            (
+                digest!("0xc42079f94a6350d7e6235f29174924f928cc2ac818eb64fed8004e115fbcca67"),
+                "Swap(address,address,int256,int256,uint160,uint128,int24)",
+            ),
+        ] {
+            assert_eq!(events(topic).collect::<Vec<_>>(), [signature]);
+        }
+    }
+
+    #[test]
+    fn unknown_signatures() {
+        assert_eq!(functions([0xde, 0xad, 0xbe, 0xef]).count(), 0);
+        assert_eq!(events(Digest::default()).count(), 0);
+    }
+
+    #[test]
+    fn no_duplicate_signatures() {
+        for table in [FUNCTIONS, EVENTS] {
+            for (i, (signature, _)) in table.iter().enumerate() {
+                assert!(
+                    table[i + 1..].iter().all(|(other, _)| other != signature),
+                    "{signature}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Local private key signer.
+
+#[cfg(feature = "std")]
+use crate::tx::{Authorization, SignedAuthorization};
+use crate::{
+    hex::{self, ParseHexError},
+    message, Address, Digest, Signature, U256,
+};
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+use k256::ecdsa::SigningKey;
+use zeroize::Zeroizing;
+
+/// A signer backed by a secp256k1 private key held in memory.
+///
+/// The private key is zeroed when the signer is dropped. Signatures use
+/// deterministic [RFC 6979] nonces, so signing the same digest with the same
+/// key always produces the same signature, and are always normalized to have
+/// a low `s` value.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{address, message, signer::LocalSigner};
+/// let signer = "0x4646464646464646464646464646464646464646464646464646464646464646"
+///     .parse::<LocalSigner>()
+///     .unwrap();
+/// assert_eq!(
+///     signer.address(),
+///     address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"),
+/// );
+///
+/// let signature = signer.sign_message("Hello Ethereum!");
+/// let digest = message::eip191_hash("Hello Ethereum!");
+/// assert!(signature.verify(signer.address(), digest));
+/// ```
+///
+/// [RFC 6979]: https://datatracker.ietf.org/doc/html/rfc6979
+#[derive(Clone)]
+pub struct LocalSigner {
+    key: SigningKey,
+    address: Address,
+}
+
+impl LocalSigner {
+    /// Creates a new signer from a secp256k1 signing key.
+    pub fn new(key: SigningKey) -> Self {
+        let address = Address::from_public_key(key.verifying_key());
+        Self { key, address }
+    }
+
+    /// Creates a new signer from a 32-byte private key. Returns an error if
+    /// the private key is not a valid secp256k1 scalar.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, ParsePrivateKeyError> {
+        let key =
+            SigningKey::from_bytes(bytes.into()).map_err(|_| ParsePrivateKeyError::InvalidKey)?;
+        Ok(Self::new(key))
+    }
+
+    /// Creates a new signer for a label, whose private key is the Keccak-256
+    /// digest of the label. The signer's address matches
+    /// [`Address::from_label()`] and Foundry's `makeAddr` cheatcode.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the digest of the label is not a valid secp256k1
+    /// private key. This happens with a probability lower than 1 in 2^127.
+    pub fn from_label(label: &str) -> Self {
+        Self::from_bytes(&Digest::of(label).0).expect("label digest is not a valid private key")
+    }
+
+    /// Returns the signing key.
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.key
+    }
+
+    /// Returns the address of the signer.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Signs a prehashed message.
+    pub fn sign_digest(&self, digest: Digest) -> Signature {
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(&digest.0)
+            .expect("signing a 32-byte prehash is infallible");
+        let (r, s) = signature.split_bytes();
+        Signature::new(
+            U256::from_be_bytes(r.into()),
+            U256::from_be_bytes(s.into()),
+            recovery_id.is_y_odd(),
+        )
+    }
+
+    /// Signs an [EIP-191] personal message, as done by `personal_sign`.
+    ///
+    /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+    pub fn sign_message(&self, message: impl AsRef<[u8]>) -> Signature {
+        self.sign_digest(message::eip191_hash(message))
+    }
+
+    /// Signs [EIP-712] typed data, given its domain separator and struct hash.
+    ///
+    /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+    pub fn sign_typed_data(&self, domain_separator: Digest, struct_hash: Digest) -> Signature {
+        self.sign_digest(message::eip712_hash(domain_separator, struct_hash))
+    }
+
+    /// Signs an [EIP-7702] authorization.
+    ///
+    /// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+    #[cfg(feature = "std")]
+    pub fn sign_authorization(&self, authorization: Authorization) -> SignedAuthorization {
+        authorization.into_signed(self.sign_digest(authorization.signing_hash()))
+    }
+}
+
+impl Debug for LocalSigner {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("LocalSigner")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FromStr for LocalSigner {
+    type Err = ParsePrivateKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = Zeroizing::new(hex::decode::<32>(s)?);
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Represents an error parsing a private key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParsePrivateKeyError {
+    /// The private key does not have the correct length.
+    InvalidLength,
+    /// An invalid character was found.
+    InvalidHexCharacter { c: char, index: usize },
+    /// The private key is not a valid secp256k1 scalar.
+    InvalidKey,
+}
+
+impl Display for ParsePrivateKeyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "invalid private key length"),
+            Self::InvalidHexCharacter { c, index } => {
+                let (c, index) = (*c, *index);
+                write!(f, "{}", ParseHexError::InvalidHexCharacter { c, index })
+            }
+            Self::InvalidKey => write!(f, "invalid secp256k1 private key"),
+        }
+    }
+}
+
+impl From<ParseHexError> for ParsePrivateKeyError {
+    fn from(err: ParseHexError) -> Self {
+        match err {
+            ParseHexError::InvalidLength => Self::InvalidLength,
+            ParseHexError::InvalidHexCharacter { c, index } => {
+                Self::InvalidHexCharacter { c, index }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParsePrivateKeyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest, message::Eip712Domain, uint};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn sign_authorization() {
+        let signer = LocalSigner::from_bytes(&[0x46; 32]).unwrap();
+        let signed = signer.sign_authorization(Authorization {
+            chain_id: U256::ONE,
+            address: address!("0x4444444444444444444444444444444444444444"),
+            nonce: 7,
+        });
+        assert_eq!(
+            signed.signature(),
+            Some(Signature::new(
+                uint!("0xef90fe61a49820f35f245dc06c45b5d6ad0c19e5daa4992d54698c10b44e0e71"),
+                uint!("0x03bdfd069bd7c191615782f44cea708bb05813ca523d1314459e608347093190"),
+                true,
+            )),
+        );
+        assert_eq!(signed.recover_authority().unwrap(), signer.address());
+    }
+
+    #[test]
+    fn sign_eip155_transaction() {
+        // Example from EIP-155.
+        let signer = LocalSigner::from_bytes(&[0x46; 32]).unwrap();
+        let signature = signer.sign_digest(digest!(
+            "0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        ));
+
+        assert_eq!(
+            signature,
+            Signature::new(
+                uint!(
+                    "18515461264373351373200002665853028612451056578545711640558177340181847433846"
+                ),
+                uint!(
+                    "46948507304638947509940763649030358759909902576025900602547168820602576006531"
+                ),
+                false,
+            ),
+        );
+    }
+
+    #[test]
+    fn sign_personal_message() {
+        // Example from EIP-2098.
+        let signer = "0x1234567890123456789012345678901234567890123456789012345678901234"
+            .parse::<LocalSigner>()
+            .unwrap();
+
+        assert_eq!(
+            signer.address(),
+            address!("0x2e988A386a799F506693793c6A5AF6B54dfAaBfB"),
+        );
+        assert_eq!(
+            signer.sign_message("Hello World"),
+            Signature::new(
+                uint!("0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90"),
+                uint!("0x7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"),
+                false,
+            ),
+        );
+    }
+
+    #[test]
+    fn sign_typed_data() {
+        // Example from EIP-712.
+        let signer = LocalSigner::from_bytes(&Digest::of("cow").0).unwrap();
+        let domain = Eip712Domain {
+            name: Some("Ether Mail"),
+            version: Some("1"),
+            chain_id: Some(uint!("1")),
+            verifying_contract: Some(address!("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC")),
+            salt: None,
+        };
+        let mail = digest!("0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e");
+
+        assert_eq!(
+            signer.address(),
+            address!("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+        );
+        assert_eq!(
+            signer.sign_typed_data(domain.separator(), mail),
+            Signature::new(
+                uint!("0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d"),
+                uint!("0x07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562"),
+                true,
+            ),
+        );
+    }
+
+    #[test]
+    fn labeled_signers() {
+        for label in ["alice", "bob"] {
+            assert_eq!(
+                LocalSigner::from_label(label).address(),
+                Address::from_label(label),
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_private_keys() {
+        assert_eq!(
+            LocalSigner::from_bytes(&[0; 32]).unwrap_err(),
+            ParsePrivateKeyError::InvalidKey,
+        );
+        assert_eq!(
+            LocalSigner::from_bytes(&[0xff; 32]).unwrap_err(),
+            ParsePrivateKeyError::InvalidKey,
+        );
+        assert_eq!(
+            "0x1234".parse::<LocalSigner>().unwrap_err(),
+            ParsePrivateKeyError::InvalidLength,
+        );
+    }
+
+    #[test]
+    fn debug_does_not_leak_key() {
+        let signer = LocalSigner::from_bytes(&[0x46; 32]).unwrap();
+        assert!(!format!("{signer:?}").contains("4646"));
+    }
+}
@@ -0,0 +1,291 @@
+//! Solidity storage slot computation.
+//!
+//! This module provides functions for computing the storage slots of Solidity
+//! state variables, following the [storage layout] rules of the Solidity
+//! compiler. Storage slots are represented as [`Digest`]s, as they are used
+//! for storage keys in `eth_getStorageAt`, state overrides and storage proofs.
+//!
+//! All functions are `const fn`, so slots for static inputs can be computed
+//! at compile time.
+//!
+//! # Examples
+//!
+//! Computing the slot of an ERC-20 allowance, for a token contract declaring
+//! `mapping(address => mapping(address => uint256)) allowance` as its second
+//! state variable:
+//!
+//! ```
+//! # use ethprim::{address, digest, storage, Address, Digest, U256};
+//! const OWNER: Address = address!("0x1111111111111111111111111111111111111111");
+//! const SPENDER: Address = address!("0x2222222222222222222222222222222222222222");
+//! const ALLOWANCE: Digest = storage::mapping_address(
+//!     storage::mapping_address(storage::slot(U256::new(1)), &OWNER),
+//!     &SPENDER,
+//! );
+//! assert_eq!(
+//!     ALLOWANCE,
+//!     digest!("0xc1c5f965d29f0d4614dc5d7a10929cd88a089f67386275dfd83b6bd3e280c8cd"),
+//! );
+//! ```
+//!
+//! [storage layout]: https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html
+
+use crate::{Address, Digest, I256, U256};
+
+/// The ERC-1967 proxy implementation slot,
+/// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`.
+pub const ERC1967_IMPLEMENTATION: Digest = erc1967("eip1967.proxy.implementation");
+
+/// The ERC-1967 proxy admin slot,
+/// `bytes32(uint256(keccak256("eip1967.proxy.admin")) - 1)`.
+pub const ERC1967_ADMIN: Digest = erc1967("eip1967.proxy.admin");
+
+/// The ERC-1967 proxy beacon slot,
+/// `bytes32(uint256(keccak256("eip1967.proxy.beacon")) - 1)`.
+pub const ERC1967_BEACON: Digest = erc1967("eip1967.proxy.beacon");
+
+/// Returns the storage slot with the specified index.
+pub const fn slot(index: U256) -> Digest {
+    Digest(to_bytes(index))
+}
+
+/// Returns the storage slot at an offset from another slot. This can be used
+/// for computing the slots of struct members.
+pub const fn offset(slot: Digest, offset: U256) -> Digest {
+    Digest(to_bytes(add(from_bytes(slot.0), offset)))
+}
+
+/// Returns the storage slot of a mapping value for an already padded 32-byte
+/// key. This is used for `bytes32` keys, and is the building block for other
+/// value-type keys.
+pub const fn mapping(slot: Digest, key: &[u8; 32]) -> Digest {
+    Digest::const_of_parts(&[key, &slot.0])
+}
+
+/// Returns the storage slot of a mapping value for an `address` key.
+pub const fn mapping_address(slot: Digest, key: &Address) -> Digest {
+    let mut word = [0; 32];
+    let mut i = 0;
+    while i < 20 {
+        word[12 + i] = key.0[i];
+        i += 1;
+    }
+    mapping(slot, &word)
+}
+
+/// Returns the storage slot of a mapping value for an unsigned integer key.
+pub const fn mapping_uint(slot: Digest, key: U256) -> Digest {
+    mapping(slot, &to_bytes(key))
+}
+
+/// Returns the storage slot of a mapping value for a signed integer key.
+pub const fn mapping_int(slot: Digest, key: I256) -> Digest {
+    mapping(slot, &to_bytes(key.as_u256()))
+}
+
+/// Returns the storage slot of a mapping value for a `string` or `bytes` key.
+/// Note that these keys are not padded.
+pub const fn mapping_bytes(slot: Digest, key: &[u8]) -> Digest {
+    Digest::const_of_parts(&[key, &slot.0])
+}
+
+/// Returns the storage slot of an element of a dynamic array, where each
+/// element occupies `element_size` storage slots.
+///
+/// Note that the Solidity compiler packs elements that fit several times into
+/// a 32-byte slot, i.e. elements of 16 bytes or fewer, into shared slots. The
+/// slot of the `i`-th packed element needs to be computed with an
+/// `element_size` of `1` and `i / (32 / size)` as the index, where `size` is
+/// the element's size in bytes.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{storage, Digest, U256};
+/// let array = storage::slot(U256::new(2));
+/// assert_eq!(
+///     storage::array_element(array, U256::new(3), 2),
+///     storage::offset(Digest::of(array), U256::new(6)),
+/// );
+/// ```
+pub const fn array_element(slot: Digest, index: U256, element_size: u64) -> Digest {
+    let start = from_bytes(Digest::const_of(&slot.0).0);
+    Digest(to_bytes(add(start, mul(index, element_size))))
+}
+
+/// Returns the ERC-7201 namespaced storage root slot for a namespace ID,
+/// computed as `keccak256(abi.encode(uint256(keccak256(id)) - 1)) &
+/// ~bytes32(uint256(0xff))`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{digest, storage};
+/// assert_eq!(
+///     storage::erc7201("example.main"),
+///     digest!("0x183a6125c38840424c4a85fa12bab2ab606c4b6d0e7cc73c0c06ba5300eab500"),
+/// );
+/// ```
+pub const fn erc7201(id: &str) -> Digest {
+    let mut slot = Digest::const_of(&erc1967(id).0);
+    slot.0[31] = 0;
+    slot
+}
+
+/// Computes `bytes32(uint256(keccak256(id)) - 1)`.
+const fn erc1967(id: &str) -> Digest {
+    let digest = from_bytes(Digest::const_of(id.as_bytes()).0);
+    Digest(to_bytes(sub(digest, U256::ONE)))
+}
+
+const fn from_bytes(bytes: [u8; 32]) -> U256 {
+    let mut hi = [0; 16];
+    let mut lo = [0; 16];
+    let mut i = 0;
+    while i < 16 {
+        hi[i] = bytes[i];
+        lo[i] = bytes[16 + i];
+        i += 1;
+    }
+    U256::from_words(u128::from_be_bytes(hi), u128::from_be_bytes(lo))
+}
+
+const fn to_bytes(value: U256) -> [u8; 32] {
+    let (hi, lo) = value.into_words();
+    let (hi, lo) = (hi.to_be_bytes(), lo.to_be_bytes());
+    let mut bytes = [0; 32];
+    let mut i = 0;
+    while i < 16 {
+        bytes[i] = hi[i];
+        bytes[16 + i] = lo[i];
+        i += 1;
+    }
+    bytes
+}
+
+/// Wrapping 256-bit addition, as a `const fn`.
+const fn add(a: U256, b: U256) -> U256 {
+    let ((ah, al), (bh, bl)) = (a.into_words(), b.into_words());
+    let (lo, carry) = al.overflowing_add(bl);
+    U256::from_words(ah.wrapping_add(bh).wrapping_add(carry as u128), lo)
+}
+
+/// Wrapping 256-bit subtraction, as a `const fn`.
+const fn sub(a: U256, b: U256) -> U256 {
+    let ((ah, al), (bh, bl)) = (a.into_words(), b.into_words());
+    let (lo, borrow) = al.overflowing_sub(bl);
+    U256::from_words(ah.wrapping_sub(bh).wrapping_sub(borrow as u128), lo)
+}
+
+/// Wrapping 256-bit multiplication by a 64-bit integer, as a `const fn`.
+const fn mul(a: U256, b: u64) -> U256 {
+    let (hi, lo) = a.into_words();
+    let limbs = [lo as u64, (lo >> 64) as u64, hi as u64, (hi >> 64) as u64];
+    let mut result = [0_u64; 4];
+    let mut carry = 0_u128;
+    let mut i = 0;
+    while i < 4 {
+        let product = limbs[i] as u128 * b as u128 + carry;
+        result[i] = product as u64;
+        carry = product >> 64;
+        i += 1;
+    }
+    U256::from_words(
+        ((result[3] as u128) << 64) | result[2] as u128,
+        ((result[1] as u128) << 64) | result[0] as u128,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest, int, keccak, uint};
+
+    #[test]
+    fn erc1967_slots() {
+        assert_eq!(
+            ERC1967_IMPLEMENTATION,
+            digest!("0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"),
+        );
+        assert_eq!(
+            ERC1967_ADMIN,
+            digest!("0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103"),
+        );
+        assert_eq!(
+            ERC1967_BEACON,
+            digest!("0xa3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50"),
+        );
+    }
+
+    #[test]
+    fn erc7201_slots() {
+        // Slots from the OpenZeppelin upgradeable contracts.
+        assert_eq!(
+            erc7201("openzeppelin.storage.Ownable"),
+            digest!("0x9016d09d72d40fdae2fd8ceac6b6234c7706214fd39c1cd1e609a0528c199300"),
+        );
+        assert_eq!(
+            erc7201("openzeppelin.storage.ERC20"),
+            digest!("0x52c63247e1f47db19d5ce0460030c497f067ca4cebf71ba98eeadabe20bace00"),
+        );
+    }
+
+    #[test]
+    fn mapping_slots() {
+        let slot = slot(uint!("3"));
+        let address = address!("0x1111111111111111111111111111111111111111");
+
+        let mut key = [0; 32];
+        key[12..].copy_from_slice(address.as_slice());
+        assert_eq!(
+            mapping_address(slot, &address),
+            Digest::of([&key[..], &slot.0].concat())
+        );
+        assert_eq!(
+            mapping_uint(slot, U256::ONE),
+            mapping(slot, &to_bytes(U256::ONE))
+        );
+        assert_eq!(mapping_int(slot, int!("-1")), mapping(slot, &[0xff; 32]));
+        assert_eq!(
+            mapping_bytes(slot, b"hello"),
+            Digest::of([&b"hello"[..], &slot.0].concat())
+        );
+    }
+
+    #[test]
+    fn array_element_slots() {
+        let array = slot(uint!("1"));
+        let start = keccak!(&[0; 31], &[1]);
+        assert_eq!(array_element(array, U256::ZERO, 1), start);
+        assert_eq!(
+            array_element(array, uint!("10"), 3),
+            offset(start, uint!("30"))
+        );
+    }
+
+    #[test]
+    fn wrapping_arithmetic() {
+        assert_eq!(add(U256::MAX, uint!("2")), U256::ONE);
+        assert_eq!(
+            add(uint!("0xffffffffffffffffffffffffffffffff"), U256::ONE),
+            uint!("0x100000000000000000000000000000000")
+        );
+        assert_eq!(sub(U256::ZERO, U256::ONE), U256::MAX);
+        assert_eq!(
+            sub(uint!("0x100000000000000000000000000000000"), U256::ONE),
+            uint!("0xffffffffffffffffffffffffffffffff")
+        );
+        assert_eq!(
+            mul(
+                uint!("0x1234567890abcdef1234567890abcdef1234567890"),
+                0xfedcba98
+            ),
+            uint!("0x1234567890abcdef1234567890abcdef1234567890") * 0xfedcba98
+        );
+        assert_eq!(mul(U256::MAX, 2), U256::MAX - 1);
+    }
+}
@@ -0,0 +1,303 @@
+//! Merkle Patricia Trie root computation.
+//!
+//! This module computes the root hashes of Ethereum's [Merkle Patricia Trie],
+//! as used for the transactions, receipts, withdrawals and state roots of
+//! block headers.
+//!
+//! [Merkle Patricia Trie]: https://ethereum.org/en/developers/docs/data-structures-and-encoding/patricia-merkle-trie/
+
+use crate::{
+    keccak,
+    rlp::{self, Header},
+    Digest,
+};
+use std::collections::BTreeMap;
+
+/// The root hash of an empty trie, the Keccak-256 digest of the RLP encoding
+/// of an empty string.
+pub const EMPTY_ROOT_HASH: Digest = keccak!(&[0x80]);
+
+/// The Keccak-256 digest of empty input. This is the code hash of accounts
+/// without code.
+pub const KECCAK_EMPTY: Digest = keccak!(b"");
+
+/// A builder for computing the root hash of a trie.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{digest, trie::TrieBuilder};
+/// let mut trie = TrieBuilder::new();
+/// trie.insert("do", "verb");
+/// trie.insert("dog", "puppy");
+/// trie.insert("doge", "coin");
+/// trie.insert("horse", "stallion");
+/// assert_eq!(
+///     trie.root(),
+///     digest!("0x5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84"),
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TrieBuilder {
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl TrieBuilder {
+    /// Creates a new empty trie builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value into the trie, replacing any existing value for the
+    /// key. Inserting an empty value removes the key from the trie.
+    pub fn insert(&mut self, key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) {
+        let (key, value) = (key.as_ref(), value.as_ref());
+        if value.is_empty() {
+            self.entries.remove(key);
+        } else {
+            self.entries.insert(key.to_vec(), value.to_vec());
+        }
+    }
+
+    /// Computes the root hash of the trie.
+    pub fn root(&self) -> Digest {
+        let entries = self
+            .entries
+            .iter()
+            .map(|(key, value)| (nibbles(key), value.as_slice()))
+            .collect::<Vec<_>>();
+        Digest::of(encode_node(&entries, 0))
+    }
+}
+
+/// A builder for computing the root hash of a secure trie, where keys are
+/// hashed with Keccak-256 before being inserted. This is used for the state
+/// trie and account storage tries.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SecureTrieBuilder {
+    trie: TrieBuilder,
+}
+
+impl SecureTrieBuilder {
+    /// Creates a new empty secure trie builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value into the trie with the Keccak-256 digest of the key,
+    /// replacing any existing value. Inserting an empty value removes the key
+    /// from the trie.
+    pub fn insert(&mut self, key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) {
+        self.trie.insert(Digest::of(key), value);
+    }
+
+    /// Computes the root hash of the trie.
+    pub fn root(&self) -> Digest {
+        self.trie.root()
+    }
+}
+
+/// Computes the root hash of an ordered trie, where each value is keyed by the
+/// RLP encoding of its index. This is used for the transactions, receipts and
+/// withdrawals roots of block headers, where values are the EIP-2718 encoded
+/// transactions, receipts and RLP encoded withdrawals respectively.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::trie::{self, EMPTY_ROOT_HASH};
+/// assert_eq!(trie::ordered_trie_root::<[&[u8]; 0]>([]), EMPTY_ROOT_HASH);
+/// ```
+pub fn ordered_trie_root<I>(values: I) -> Digest
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let mut trie = TrieBuilder::new();
+    for (index, value) in values.into_iter().enumerate() {
+        trie.insert(rlp::encode(&index), value);
+    }
+    trie.root()
+}
+
+/// Computes the root hash of a secure trie, where keys are hashed with
+/// Keccak-256.
+pub fn secure_trie_root<I, K, V>(entries: I) -> Digest
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let mut trie = SecureTrieBuilder::new();
+    for (key, value) in entries {
+        trie.insert(key, value);
+    }
+    trie.root()
+}
+
+/// Splits bytes into nibbles.
+pub(crate) fn nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0xf])
+        .collect()
+}
+
+/// Returns the RLP encoding of the trie node for the sorted entries, whose
+/// keys share a common prefix of `depth` nibbles.
+fn encode_node(entries: &[(Vec<u8>, &[u8])], depth: usize) -> Vec<u8> {
+    match entries {
+        [] => rlp::encode(&[0_u8; 0][..]),
+        [(key, value)] => encode_list(&[
+            rlp::encode(&hex_prefix(&key[depth..], true)[..]),
+            rlp::encode(*value),
+        ]),
+        [(first, _), .., (last, _)] => {
+            // Since entries are sorted, the common prefix of the first and last
+            // keys is shared by all keys.
+            let common = first[depth..]
+                .iter()
+                .zip(&last[depth..])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if common > 0 {
+                return encode_list(&[
+                    rlp::encode(&hex_prefix(&first[depth..depth + common], false)[..]),
+                    node_reference(encode_node(entries, depth + common)),
+                ]);
+            }
+
+            // Since entries are sorted, a key that ends at this branch node
+            // can only be the first one.
+            let (value, entries) = match entries.split_first() {
+                Some(((key, value), rest)) if key.len() == depth => (*value, rest),
+                _ => (&[][..], entries),
+            };
+            let mut items = (0..16)
+                .map(|nibble| {
+                    let start = entries.partition_point(|(key, _)| key[depth] < nibble);
+                    let end = entries.partition_point(|(key, _)| key[depth] <= nibble);
+                    match &entries[start..end] {
+                        [] => rlp::encode(&[0_u8; 0][..]),
+                        children => node_reference(encode_node(children, depth + 1)),
+                    }
+                })
+                .collect::<Vec<_>>();
+            items.push(rlp::encode(value));
+            encode_list(&items)
+        }
+    }
+}
+
+/// Returns the reference to a node from its parent. Nodes whose encoding is
+/// shorter than 32 bytes are embedded, and are otherwise referenced by hash.
+fn node_reference(node: Vec<u8>) -> Vec<u8> {
+    if node.len() < 32 {
+        node
+    } else {
+        rlp::encode(&Digest::of(node))
+    }
+}
+
+/// Encodes an RLP list from already encoded items.
+fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload_length = items.iter().map(Vec::len).sum();
+    let mut buffer = Vec::with_capacity(payload_length + 9);
+    Header::list(payload_length).encode(&mut buffer);
+    for item in items {
+        buffer.extend_from_slice(item);
+    }
+    buffer
+}
+
+/// Returns the hex-prefix encoding of a path of nibbles, with a flag
+/// indicating whether it belongs to a leaf or extension node.
+fn hex_prefix(path: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 0x20 } else { 0x00 };
+    let (first, rest) = if path.len() % 2 == 1 {
+        (flag | 0x10 | path[0], &path[1..])
+    } else {
+        (flag, path)
+    };
+    let mut encoded = vec![first];
+    encoded.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digest;
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            EMPTY_ROOT_HASH,
+            digest!("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+        );
+        assert_eq!(
+            KECCAK_EMPTY,
+            digest!("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+        );
+        assert_eq!(TrieBuilder::new().root(), EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn hex_prefix_encoding() {
+        assert_eq!(hex_prefix(&[1, 2, 3, 4, 5], false), [0x11, 0x23, 0x45]);
+        assert_eq!(
+            hex_prefix(&[0, 1, 2, 3, 4, 5], false),
+            [0x00, 0x01, 0x23, 0x45]
+        );
+        assert_eq!(
+            hex_prefix(&[0, 15, 1, 12, 11, 8], true),
+            [0x20, 0x0f, 0x1c, 0xb8]
+        );
+        assert_eq!(hex_prefix(&[15, 1, 12, 11, 8], true), [0x3f, 0x1c, 0xb8]);
+    }
+
+    #[test]
+    fn ordered_roots() {
+        assert_eq!(
+            ordered_trie_root([[0x42]]),
+            digest!("0xaa7edc369bc2d729c09682203219b7a8dba662b5dbca4af88d44f4fb15706bfc"),
+        );
+        assert_eq!(
+            ordered_trie_root((0..200).map(|i| vec![i as u8; i % 40 + 1])),
+            digest!("0x09f3ef3772261d6fa788bf21d351a88d96c9ca902badf5a0931af21df2bb16cd"),
+        );
+    }
+
+    #[test]
+    fn secure_roots() {
+        assert_eq!(
+            secure_trie_root((0..50_u8).map(|i| ([i], [i; 33]))),
+            digest!("0x9d8c422edf246b244435fc0553c2a39260aad798577d3c7d4bbff27f37cf19f5"),
+        );
+    }
+
+    #[test]
+    fn insertion_order_and_removal() {
+        let mut a = TrieBuilder::new();
+        a.insert("doe", "reindeer");
+        a.insert("dog", "puppy");
+        a.insert("dogglesworth", "cat");
+
+        let mut b = TrieBuilder::new();
+        b.insert("dogglesworth", "cat");
+        b.insert("dog", "puppy");
+        b.insert("horse", "stallion");
+        b.insert("doe", "reindeer");
+        b.insert("horse", "");
+
+        assert_eq!(a.root(), b.root());
+        assert_eq!(
+            a.root(),
+            digest!("0x8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"),
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! EIP-2930 access lists.
+
+use crate::{
+    rlp::{self, Decodable, DecodeError, Encodable, Output},
+    Address, Digest,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An EIP-2930 access list, specifying the addresses and storage keys that a
+/// transaction plans to access.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(transparent))]
+pub struct AccessList(pub Vec<AccessListItem>);
+
+/// An access list entry for a single account.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize, Serialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AccessListItem {
+    /// The accessed account address.
+    pub address: Address,
+    /// The accessed storage keys of the account.
+    pub storage_keys: Vec<Digest>,
+}
+
+impl AccessList {
+    /// Returns the total number of storage keys in the access list.
+    pub fn storage_key_count(&self) -> usize {
+        self.0.iter().map(|item| item.storage_keys.len()).sum()
+    }
+}
+
+impl Encodable for AccessList {
+    fn encode(&self, out: &mut dyn Output) {
+        self.0.encode(out)
+    }
+
+    fn length(&self) -> usize {
+        self.0.length()
+    }
+}
+
+impl Decodable for AccessList {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        Vec::decode(buf).map(Self)
+    }
+}
+
+impl AccessListItem {
+    fn fields(&self) -> [&dyn Encodable; 2] {
+        [&self.address, &self.storage_keys]
+    }
+}
+
+impl Encodable for AccessListItem {
+    fn encode(&self, out: &mut dyn Output) {
+        rlp::encode_fields(&self.fields(), &[], out)
+    }
+
+    fn length(&self) -> usize {
+        rlp::fields_length(&self.fields())
+    }
+}
+
+impl Decodable for AccessListItem {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        rlp::decode_fields(buf, |fields| {
+            Ok(Self {
+                address: Decodable::decode(fields)?,
+                storage_keys: Decodable::decode(fields)?,
+            })
+        })
+    }
+}
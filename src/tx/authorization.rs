@@ -0,0 +1,288 @@
+//! EIP-7702 authorizations.
+
+#[cfg(feature = "k256")]
+use crate::RecoverSignatureError;
+use crate::{
+    rlp::{self, Decodable, DecodeError, Encodable, Output},
+    Address, Digest, Hasher, Signature, U256,
+};
+
+/// The prefix of the EIP-7702 delegation designator, the code set on accounts
+/// that delegated their code to a contract.
+pub const DELEGATION_DESIGNATOR_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// An EIP-7702 authorization, allowing an account to delegate its code to a
+/// contract.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Authorization {
+    /// The chain ID the authorization is valid for, or `0` for any chain.
+    pub chain_id: U256,
+    /// The address of the contract to delegate code to.
+    pub address: Address,
+    /// The nonce of the authorizing account.
+    pub nonce: u64,
+}
+
+/// A signed EIP-7702 authorization, as included in a set code transaction's
+/// authorization list.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SignedAuthorization {
+    /// The authorization.
+    pub authorization: Authorization,
+    /// The signature `y_parity`. This is kept as a byte instead of a boolean,
+    /// since authorization lists may include signatures with parity values
+    /// other than `0` or `1`. Such authorizations are invalid but do not make
+    /// the transaction including them invalid.
+    pub y_parity: u8,
+    /// The signature `r` value.
+    pub r: U256,
+    /// The signature `s` value.
+    pub s: U256,
+}
+
+impl Authorization {
+    /// The magic byte prepended to the RLP encoding of authorizations when
+    /// computing their signing hash.
+    pub const MAGIC: u8 = 0x05;
+
+    /// Returns the hash of the authorization that gets signed by the
+    /// authorizing account, `keccak256(MAGIC || rlp([chain_id, address,
+    /// nonce]))`.
+    pub fn signing_hash(&self) -> Digest {
+        let mut hasher = Hasher::new();
+        hasher.update([Self::MAGIC]);
+        self.encode(&mut hasher);
+        hasher.finalize()
+    }
+
+    /// Returns the signed authorization with the specified signature.
+    pub fn into_signed(self, signature: Signature) -> SignedAuthorization {
+        SignedAuthorization {
+            authorization: self,
+            y_parity: signature.y_parity as _,
+            r: signature.r,
+            s: signature.s,
+        }
+    }
+
+    fn fields(&self) -> [&dyn Encodable; 3] {
+        [&self.chain_id, &self.address, &self.nonce]
+    }
+}
+
+impl SignedAuthorization {
+    /// Returns the signature of the authorizing account, or `None` if the
+    /// signature has an invalid `y_parity` value.
+    pub fn signature(&self) -> Option<Signature> {
+        match self.y_parity {
+            0 | 1 => Some(Signature::new(self.r, self.s, self.y_parity == 1)),
+            _ => None,
+        }
+    }
+
+    /// Recovers the address of the authorizing account.
+    ///
+    /// Note that this does not verify that the authorization is valid for a
+    /// specific chain or account nonce.
+    #[cfg(feature = "k256")]
+    pub fn recover_authority(&self) -> Result<Address, RecoverSignatureError> {
+        self.signature()
+            .ok_or(RecoverSignatureError::InvalidSignature)?
+            .recover_address(self.authorization.signing_hash())
+    }
+
+    fn fields(&self) -> [&dyn Encodable; 6] {
+        [
+            &self.authorization.chain_id,
+            &self.authorization.address,
+            &self.authorization.nonce,
+            &self.y_parity,
+            &self.r,
+            &self.s,
+        ]
+    }
+}
+
+impl Encodable for Authorization {
+    fn encode(&self, out: &mut dyn Output) {
+        rlp::encode_fields(&self.fields(), &[], out)
+    }
+
+    fn length(&self) -> usize {
+        rlp::fields_length(&self.fields())
+    }
+}
+
+impl Decodable for Authorization {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        rlp::decode_fields(buf, |fields| {
+            Ok(Self {
+                chain_id: Decodable::decode(fields)?,
+                address: Decodable::decode(fields)?,
+                nonce: Decodable::decode(fields)?,
+            })
+        })
+    }
+}
+
+impl Encodable for SignedAuthorization {
+    fn encode(&self, out: &mut dyn Output) {
+        rlp::encode_fields(&self.fields(), &[], out)
+    }
+
+    fn length(&self) -> usize {
+        rlp::fields_length(&self.fields())
+    }
+}
+
+impl Decodable for SignedAuthorization {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        rlp::decode_fields(buf, |fields| {
+            Ok(Self {
+                authorization: Authorization {
+                    chain_id: Decodable::decode(fields)?,
+                    address: Decodable::decode(fields)?,
+                    nonce: Decodable::decode(fields)?,
+                },
+                y_parity: Decodable::decode(fields)?,
+                r: Decodable::decode(fields)?,
+                s: Decodable::decode(fields)?,
+            })
+        })
+    }
+}
+
+/// Returns the delegation designator code for an account delegating its code
+/// to the specified address, `0xef0100 || address`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{address, tx};
+/// let delegate = address!("0x4444444444444444444444444444444444444444");
+/// let code = tx::delegation_designator(delegate);
+/// assert_eq!(code[..3], [0xef, 0x01, 0x00]);
+/// assert_eq!(tx::delegated_address(&code), Some(delegate));
+/// ```
+pub fn delegation_designator(address: Address) -> [u8; 23] {
+    let mut code = [0; 23];
+    code[..3].copy_from_slice(&DELEGATION_DESIGNATOR_PREFIX);
+    code[3..].copy_from_slice(&address.0);
+    code
+}
+
+/// Returns the address an account delegates its code to, or `None` if the
+/// account code is not a delegation designator.
+pub fn delegated_address(code: &[u8]) -> Option<Address> {
+    let address = code.strip_prefix(&DELEGATION_DESIGNATOR_PREFIX)?;
+    Some(Address(address.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest, rlp, uint};
+
+    fn authorization() -> Authorization {
+        Authorization {
+            chain_id: U256::ONE,
+            address: address!("0x4444444444444444444444444444444444444444"),
+            nonce: 7,
+        }
+    }
+
+    #[test]
+    fn signing_hash() {
+        assert_eq!(
+            authorization().signing_hash(),
+            digest!("0x38cdc4da72e66c02eec8e42fa3605778dc8eb83146c8c4784abca0a2d411f3aa"),
+        );
+        assert_eq!(
+            Authorization {
+                chain_id: U256::ZERO,
+                nonce: 0,
+                ..authorization()
+            }
+            .signing_hash(),
+            digest!("0xc6aa09c8313fbe0b47c4dc2be9f26bd66fbadd0a516df4eac57e213bab6cd013"),
+        );
+    }
+
+    #[test]
+    fn encoding_roundtrip() {
+        let signed = authorization().into_signed(Signature::new(
+            uint!("0xef90fe61a49820f35f245dc06c45b5d6ad0c19e5daa4992d54698c10b44e0e71"),
+            uint!("0x03bdfd069bd7c191615782f44cea708bb05813ca523d1314459e608347093190"),
+            true,
+        ));
+        let encoded = rlp::encode(&signed);
+        assert_eq!(encoded.len(), signed.length());
+        assert_eq!(
+            rlp::decode::<SignedAuthorization>(&encoded).unwrap(),
+            signed
+        );
+    }
+
+    #[cfg(feature = "k256")]
+    #[test]
+    fn recover_authority() {
+        let signed = authorization().into_signed(Signature::new(
+            uint!("0xef90fe61a49820f35f245dc06c45b5d6ad0c19e5daa4992d54698c10b44e0e71"),
+            uint!("0x03bdfd069bd7c191615782f44cea708bb05813ca523d1314459e608347093190"),
+            true,
+        ));
+        assert_eq!(
+            signed.recover_authority().unwrap(),
+            address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"),
+        );
+
+        let tampered = SignedAuthorization {
+            authorization: Authorization {
+                nonce: 8,
+                ..signed.authorization
+            },
+            ..signed
+        };
+        assert_ne!(
+            tampered.recover_authority().ok(),
+            Some(address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F")),
+        );
+    }
+
+    #[cfg(feature = "k256")]
+    #[test]
+    fn invalid_y_parity() {
+        let signed = SignedAuthorization {
+            y_parity: 2,
+            ..authorization().into_signed(Signature::new(
+                uint!("0xef90fe61a49820f35f245dc06c45b5d6ad0c19e5daa4992d54698c10b44e0e71"),
+                uint!("0x03bdfd069bd7c191615782f44cea708bb05813ca523d1314459e608347093190"),
+                true,
+            ))
+        };
+        assert_eq!(signed.signature(), None);
+        assert_eq!(
+            signed.recover_authority(),
+            Err(RecoverSignatureError::InvalidSignature),
+        );
+    }
+
+    #[test]
+    fn delegation_designators() {
+        let delegate = address!("0x4444444444444444444444444444444444444444");
+        let code = delegation_designator(delegate);
+        assert_eq!(code[..3], DELEGATION_DESIGNATOR_PREFIX);
+        assert_eq!(code[3..], delegate.0);
+        assert_eq!(delegated_address(&code), Some(delegate));
+
+        assert_eq!(delegated_address(&[]), None);
+        assert_eq!(delegated_address(&code[..22]), None);
+        assert_eq!(delegated_address(&[&code[..], &[0]].concat()), None);
+        assert_eq!(
+            delegated_address(&[&[0xef, 0x01, 0x01], &delegate.0[..]].concat()),
+            None
+        );
+    }
+}
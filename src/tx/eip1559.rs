@@ -0,0 +1,107 @@
+//! EIP-1559 dynamic fee transactions.
+
+use super::AccessList;
+use crate::{
+    rlp::{Decodable, DecodeError, Encodable},
+    Address, U256,
+};
+
+/// An EIP-1559 dynamic fee transaction.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Eip1559Transaction {
+    /// The chain ID.
+    pub chain_id: u64,
+    /// The sender account nonce.
+    pub nonce: u64,
+    /// The maximum priority fee per gas in wei paid to the block producer.
+    pub max_priority_fee_per_gas: u128,
+    /// The maximum total fee per gas in wei, including the base fee.
+    pub max_fee_per_gas: u128,
+    /// The gas limit.
+    pub gas_limit: u64,
+    /// The recipient address, or `None` for contract creations.
+    pub to: Option<Address>,
+    /// The value transferred in wei.
+    pub value: U256,
+    /// The transaction input data.
+    pub input: Vec<u8>,
+    /// The access list.
+    pub access_list: AccessList,
+}
+
+super::impl_typed_transaction!(Eip1559Transaction, 0x02);
+
+impl Eip1559Transaction {
+    fn fields(&self) -> [&dyn Encodable; 9] {
+        [
+            &self.chain_id,
+            &self.nonce,
+            &self.max_priority_fee_per_gas,
+            &self.max_fee_per_gas,
+            &self.gas_limit,
+            &self.to,
+            &self.value,
+            &self.input,
+            &self.access_list,
+        ]
+    }
+
+    /// Decodes the unsigned transaction fields, in the order returned by
+    /// `fields`.
+    pub(super) fn decode_fields(fields: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            chain_id: Decodable::decode(fields)?,
+            nonce: Decodable::decode(fields)?,
+            max_priority_fee_per_gas: Decodable::decode(fields)?,
+            max_fee_per_gas: Decodable::decode(fields)?,
+            gas_limit: Decodable::decode(fields)?,
+            to: Decodable::decode(fields)?,
+            value: Decodable::decode(fields)?,
+            input: Decodable::decode(fields)?,
+            access_list: Decodable::decode(fields)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        address, digest,
+        tests::hex,
+        tx::{tests::signature, AccessListItem},
+    };
+
+    #[test]
+    fn signed_encoding() {
+        let tx = Eip1559Transaction {
+            chain_id: 1,
+            nonce: 2,
+            max_priority_fee_per_gas: 1_000_000_000,
+            max_fee_per_gas: 30_000_000_000,
+            gas_limit: 60_000,
+            to: None,
+            value: U256::ZERO,
+            input: vec![0x60, 0x80],
+            access_list: AccessList(vec![AccessListItem {
+                address: address!("0x1111111111111111111111111111111111111111"),
+                storage_keys: vec![digest!(
+                    "0x0000000000000000000000000000000000000000000000000000000000000001"
+                )],
+            }]),
+        };
+
+        assert_eq!(
+            tx.signing_hash(),
+            digest!("0x112f247f6d1b57ba9ad0ac48b6a0055281d7e87e1081ef96e43c51b037e39bf4"),
+        );
+        assert_eq!(
+            tx.encode_signed(&signature()),
+            hex("02f87c0102843b9aca008506fc23ac0082ea608080826080f838f7941111111111111111111111111111111111111111e1a00000000000000000000000000000000000000000000000000000000000000001019b1111000000000000000000000000000000000000000000000000008f022220000000000000000000000000"),
+        );
+        assert_eq!(
+            tx.tx_hash(&signature()),
+            digest!("0x3e27b1a0093ce5ac35bbe8e10854c557c30a0fa9820093d904b4c71bb8ad3217"),
+        );
+    }
+}
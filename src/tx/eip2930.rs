@@ -0,0 +1,102 @@
+//! EIP-2930 access list transactions.
+
+use super::AccessList;
+use crate::{
+    rlp::{Decodable, DecodeError, Encodable},
+    Address, U256,
+};
+
+/// An EIP-2930 access list transaction.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Eip2930Transaction {
+    /// The chain ID.
+    pub chain_id: u64,
+    /// The sender account nonce.
+    pub nonce: u64,
+    /// The gas price in wei.
+    pub gas_price: u128,
+    /// The gas limit.
+    pub gas_limit: u64,
+    /// The recipient address, or `None` for contract creations.
+    pub to: Option<Address>,
+    /// The value transferred in wei.
+    pub value: U256,
+    /// The transaction input data.
+    pub input: Vec<u8>,
+    /// The access list.
+    pub access_list: AccessList,
+}
+
+super::impl_typed_transaction!(Eip2930Transaction, 0x01);
+
+impl Eip2930Transaction {
+    fn fields(&self) -> [&dyn Encodable; 8] {
+        [
+            &self.chain_id,
+            &self.nonce,
+            &self.gas_price,
+            &self.gas_limit,
+            &self.to,
+            &self.value,
+            &self.input,
+            &self.access_list,
+        ]
+    }
+
+    /// Decodes the unsigned transaction fields, in the order returned by
+    /// `fields`.
+    pub(super) fn decode_fields(fields: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            chain_id: Decodable::decode(fields)?,
+            nonce: Decodable::decode(fields)?,
+            gas_price: Decodable::decode(fields)?,
+            gas_limit: Decodable::decode(fields)?,
+            to: Decodable::decode(fields)?,
+            value: Decodable::decode(fields)?,
+            input: Decodable::decode(fields)?,
+            access_list: Decodable::decode(fields)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        address, digest,
+        tests::hex,
+        tx::{tests::signature, AccessListItem},
+    };
+
+    #[test]
+    fn signed_encoding() {
+        let tx = Eip2930Transaction {
+            chain_id: 1,
+            nonce: 1,
+            gas_price: 2_000_000_000,
+            gas_limit: 50_000,
+            to: Some(address!("0x2222222222222222222222222222222222222222")),
+            value: U256::ONE,
+            input: vec![0xde, 0xad],
+            access_list: AccessList(vec![AccessListItem {
+                address: address!("0x1111111111111111111111111111111111111111"),
+                storage_keys: vec![digest!(
+                    "0x0000000000000000000000000000000000000000000000000000000000000001"
+                )],
+            }]),
+        };
+
+        assert_eq!(
+            tx.signing_hash(),
+            digest!("0x282bfd825cd87338cd973dc57eae512042a3e65536a32ffb19cddedf48ff887a"),
+        );
+        assert_eq!(
+            tx.encode_signed(&signature()),
+            hex("01f88a0101847735940082c3509422222222222222222222222222222222222222220182deadf838f7941111111111111111111111111111111111111111e1a00000000000000000000000000000000000000000000000000000000000000001019b1111000000000000000000000000000000000000000000000000008f022220000000000000000000000000"),
+        );
+        assert_eq!(
+            tx.tx_hash(&signature()),
+            digest!("0x392dbcad60789e193c9e6827b6e7b66b401e7bfa615351490bc54ce7e97e7230"),
+        );
+    }
+}
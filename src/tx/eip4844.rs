@@ -0,0 +1,113 @@
+//! EIP-4844 blob transactions.
+
+use super::AccessList;
+use crate::{
+    rlp::{Decodable, DecodeError, Encodable},
+    Address, Digest, U256,
+};
+
+/// An EIP-4844 blob transaction.
+///
+/// Note that this represents the transaction as it is included in a block,
+/// without the blob sidecar used for network propagation.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Eip4844Transaction {
+    /// The chain ID.
+    pub chain_id: u64,
+    /// The sender account nonce.
+    pub nonce: u64,
+    /// The maximum priority fee per gas in wei paid to the block producer.
+    pub max_priority_fee_per_gas: u128,
+    /// The maximum total fee per gas in wei, including the base fee.
+    pub max_fee_per_gas: u128,
+    /// The gas limit.
+    pub gas_limit: u64,
+    /// The recipient address. Blob transactions cannot create contracts.
+    pub to: Address,
+    /// The value transferred in wei.
+    pub value: U256,
+    /// The transaction input data.
+    pub input: Vec<u8>,
+    /// The access list.
+    pub access_list: AccessList,
+    /// The maximum fee per blob gas in wei.
+    pub max_fee_per_blob_gas: u128,
+    /// The versioned hashes of the KZG commitments of the blobs.
+    pub blob_versioned_hashes: Vec<Digest>,
+}
+
+super::impl_typed_transaction!(Eip4844Transaction, 0x03);
+
+impl Eip4844Transaction {
+    fn fields(&self) -> [&dyn Encodable; 11] {
+        [
+            &self.chain_id,
+            &self.nonce,
+            &self.max_priority_fee_per_gas,
+            &self.max_fee_per_gas,
+            &self.gas_limit,
+            &self.to,
+            &self.value,
+            &self.input,
+            &self.access_list,
+            &self.max_fee_per_blob_gas,
+            &self.blob_versioned_hashes,
+        ]
+    }
+
+    /// Decodes the unsigned transaction fields, in the order returned by
+    /// `fields`.
+    pub(super) fn decode_fields(fields: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            chain_id: Decodable::decode(fields)?,
+            nonce: Decodable::decode(fields)?,
+            max_priority_fee_per_gas: Decodable::decode(fields)?,
+            max_fee_per_gas: Decodable::decode(fields)?,
+            gas_limit: Decodable::decode(fields)?,
+            to: Decodable::decode(fields)?,
+            value: Decodable::decode(fields)?,
+            input: Decodable::decode(fields)?,
+            access_list: Decodable::decode(fields)?,
+            max_fee_per_blob_gas: Decodable::decode(fields)?,
+            blob_versioned_hashes: Decodable::decode(fields)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, digest, tests::hex, tx::tests::signature};
+
+    #[test]
+    fn signed_encoding() {
+        let tx = Eip4844Transaction {
+            chain_id: 1,
+            nonce: 3,
+            max_priority_fee_per_gas: 1_000_000_000,
+            max_fee_per_gas: 30_000_000_000,
+            gas_limit: 70_000,
+            to: address!("0x3333333333333333333333333333333333333333"),
+            value: U256::ZERO,
+            input: vec![],
+            access_list: AccessList::default(),
+            max_fee_per_blob_gas: 1_000_000,
+            blob_versioned_hashes: vec![digest!(
+                "0x0100000000000000000000000000000000000000000000000000000000000000"
+            )],
+        };
+
+        assert_eq!(
+            tx.signing_hash(),
+            digest!("0x4a3198075b96803400e0601f32e62b362c456e5b3263b0c11751a046649a01e7"),
+        );
+        assert_eq!(
+            tx.encode_signed(&signature()),
+            hex("03f87c0103843b9aca008506fc23ac00830111709433333333333333333333333333333333333333338080c0830f4240e1a00100000000000000000000000000000000000000000000000000000000000000019b1111000000000000000000000000000000000000000000000000008f022220000000000000000000000000"),
+        );
+        assert_eq!(
+            tx.tx_hash(&signature()),
+            digest!("0x74d5ff28794716bd9f036f09e08f6cc49e9df848b6d30bdc776b4069e3162ccd"),
+        );
+    }
+}
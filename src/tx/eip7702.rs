@@ -0,0 +1,136 @@
+//! EIP-7702 set code transactions.
+
+use super::{AccessList, SignedAuthorization};
+use crate::{
+    rlp::{Decodable, DecodeError, Encodable},
+    Address, U256,
+};
+
+/// An EIP-7702 set code transaction.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Eip7702Transaction {
+    /// The chain ID.
+    pub chain_id: u64,
+    /// The sender account nonce.
+    pub nonce: u64,
+    /// The maximum priority fee per gas in wei paid to the block producer.
+    pub max_priority_fee_per_gas: u128,
+    /// The maximum total fee per gas in wei, including the base fee.
+    pub max_fee_per_gas: u128,
+    /// The gas limit.
+    pub gas_limit: u64,
+    /// The recipient address. Set code transactions cannot create contracts.
+    pub to: Address,
+    /// The value transferred in wei.
+    pub value: U256,
+    /// The transaction input data.
+    pub input: Vec<u8>,
+    /// The access list.
+    pub access_list: AccessList,
+    /// The signed code delegation authorizations.
+    pub authorization_list: Vec<SignedAuthorization>,
+}
+
+super::impl_typed_transaction!(Eip7702Transaction, 0x04);
+
+impl Eip7702Transaction {
+    fn fields(&self) -> [&dyn Encodable; 10] {
+        [
+            &self.chain_id,
+            &self.nonce,
+            &self.max_priority_fee_per_gas,
+            &self.max_fee_per_gas,
+            &self.gas_limit,
+            &self.to,
+            &self.value,
+            &self.input,
+            &self.access_list,
+            &self.authorization_list,
+        ]
+    }
+
+    /// Decodes the unsigned transaction fields, in the order returned by
+    /// `fields`.
+    pub(super) fn decode_fields(fields: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            chain_id: Decodable::decode(fields)?,
+            nonce: Decodable::decode(fields)?,
+            max_priority_fee_per_gas: Decodable::decode(fields)?,
+            max_fee_per_gas: Decodable::decode(fields)?,
+            gas_limit: Decodable::decode(fields)?,
+            to: Decodable::decode(fields)?,
+            value: Decodable::decode(fields)?,
+            input: Decodable::decode(fields)?,
+            access_list: Decodable::decode(fields)?,
+            authorization_list: Decodable::decode(fields)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        address, digest,
+        tests::hex,
+        tx::{tests::signature, Authorization, SignedTransaction, Transaction},
+    };
+
+    #[test]
+    fn signed_encoding() {
+        let tx = Eip7702Transaction {
+            chain_id: 1,
+            nonce: 4,
+            max_priority_fee_per_gas: 1,
+            max_fee_per_gas: 2,
+            gas_limit: 80_000,
+            to: address!("0x5555555555555555555555555555555555555555"),
+            value: U256::ZERO,
+            input: vec![],
+            access_list: AccessList::default(),
+            authorization_list: vec![SignedAuthorization {
+                authorization: Authorization {
+                    chain_id: U256::ONE,
+                    address: address!("0x4444444444444444444444444444444444444444"),
+                    nonce: 7,
+                },
+                y_parity: 1,
+                r: U256::new(0x5555),
+                s: U256::new(0x6666),
+            }],
+        };
+
+        assert_eq!(
+            tx.signing_hash(),
+            digest!("0xb44f328a7a6a24b9c347bf3d8bbc9c2a780b8ee4270042b75048517cd1e603d0"),
+        );
+        assert_eq!(
+            tx.encode_signed(&signature()),
+            hex("04f86d01040102830138809455555555555555555555555555555555555555558080c0dfde019444444444444444444444444444444444444444440701825555826666019b1111000000000000000000000000000000000000000000000000008f022220000000000000000000000000"),
+        );
+        assert_eq!(
+            tx.tx_hash(&signature()),
+            digest!("0xcbff82e1d482245e511fc6dc794d815ff1954a517fe94310d907ad576efddef3"),
+        );
+    }
+
+    #[test]
+    fn invalid_authorization_y_parity() {
+        let tx = Eip7702Transaction {
+            authorization_list: vec![SignedAuthorization {
+                y_parity: 2,
+                r: U256::new(0x5555),
+                s: U256::new(0x6666),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let signed = SignedTransaction::decode(&tx.encode_signed(&signature())).unwrap();
+        assert_eq!(signed.transaction, tx.into());
+        let Transaction::Eip7702(tx) = signed.transaction else {
+            unreachable!()
+        };
+        assert_eq!(tx.authorization_list[0].signature(), None);
+    }
+}
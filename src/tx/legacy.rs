@@ -0,0 +1,204 @@
+//! Legacy transactions.
+
+use super::TxHash;
+use crate::{
+    rlp::{self, Decodable, DecodeError, Encodable},
+    Address, Digest, Signature, U256,
+};
+
+/// A legacy transaction, with optional EIP-155 replay protection.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LegacyTransaction {
+    /// The EIP-155 chain ID, or `None` for transactions without replay
+    /// protection.
+    pub chain_id: Option<u64>,
+    /// The sender account nonce.
+    pub nonce: u64,
+    /// The gas price in wei.
+    pub gas_price: u128,
+    /// The gas limit.
+    pub gas_limit: u64,
+    /// The recipient address, or `None` for contract creations.
+    pub to: Option<Address>,
+    /// The value transferred in wei.
+    pub value: U256,
+    /// The transaction input data.
+    pub input: Vec<u8>,
+}
+
+impl LegacyTransaction {
+    /// Returns the hash of the transaction that gets signed by the sender. For
+    /// EIP-155 transactions, this includes the chain ID.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::tx::LegacyTransaction;
+    /// # use ethprim::{address, digest, uint};
+    /// // Example transaction from EIP-155.
+    /// let tx = LegacyTransaction {
+    ///     chain_id: Some(1),
+    ///     nonce: 9,
+    ///     gas_price: 20_000_000_000,
+    ///     gas_limit: 21_000,
+    ///     to: Some(address!("0x3535353535353535353535353535353535353535")),
+    ///     value: uint!("1000000000000000000"),
+    ///     input: vec![],
+    /// };
+    /// assert_eq!(
+    ///     tx.signing_hash(),
+    ///     digest!("0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"),
+    /// );
+    /// ```
+    pub fn signing_hash(&self) -> Digest {
+        match &self.chain_id {
+            Some(chain_id) => super::hash_fields(&self.fields(), &[chain_id, &0_u64, &0_u64]),
+            None => super::hash_fields(&self.fields(), &[]),
+        }
+    }
+
+    /// Returns the RLP encoding of the signed transaction,
+    /// `rlp([fields..., v, r, s])`.
+    pub fn encode_signed(&self, signature: &Signature) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        let v = self.v(signature);
+        rlp::encode_fields(
+            &self.fields(),
+            &[&v, &signature.r, &signature.s],
+            &mut buffer,
+        );
+        buffer
+    }
+
+    /// Returns the hash of the signed transaction.
+    pub fn tx_hash(&self, signature: &Signature) -> TxHash {
+        let v = self.v(signature);
+        super::hash_fields(&self.fields(), &[&v, &signature.r, &signature.s])
+    }
+
+    /// Returns the `v` value of a signature for this transaction. This is
+    /// `27 + y_parity` for transactions without replay protection, and
+    /// `35 + 2 * chain_id + y_parity` for EIP-155 transactions.
+    pub fn v(&self, signature: &Signature) -> u128 {
+        signature.v(self.chain_id)
+    }
+
+    fn fields(&self) -> [&dyn Encodable; 6] {
+        [
+            &self.nonce,
+            &self.gas_price,
+            &self.gas_limit,
+            &self.to,
+            &self.value,
+            &self.input,
+        ]
+    }
+
+    /// Decodes the unsigned transaction fields, in the order returned by
+    /// `fields`. The chain ID is derived from the signature `v` value
+    /// and is left unset.
+    pub(super) fn decode_fields(fields: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            chain_id: None,
+            nonce: Decodable::decode(fields)?,
+            gas_price: Decodable::decode(fields)?,
+            gas_limit: Decodable::decode(fields)?,
+            to: Decodable::decode(fields)?,
+            value: Decodable::decode(fields)?,
+            input: Decodable::decode(fields)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        address, digest,
+        tests::hex,
+        tx::{self, tests::signature, SignedTransaction},
+        uint,
+    };
+
+    #[test]
+    fn eip155_transaction() {
+        let tx = LegacyTransaction {
+            chain_id: Some(1),
+            nonce: 9,
+            gas_price: 20_000_000_000,
+            gas_limit: 21_000,
+            to: Some(address!("0x3535353535353535353535353535353535353535")),
+            value: uint!("1000000000000000000"),
+            input: vec![],
+        };
+        let signature = Signature::new(
+            uint!("18515461264373351373200002665853028612451056578545711640558177340181847433846"),
+            uint!("46948507304638947509940763649030358759909902576025900602547168820602576006531"),
+            false,
+        );
+
+        assert_eq!(tx.v(&signature), 37);
+        assert_eq!(
+            tx.encode_signed(&signature),
+            hex("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"),
+        );
+        assert_eq!(
+            tx.tx_hash(&signature),
+            Digest::of(tx.encode_signed(&signature))
+        );
+    }
+
+    #[test]
+    fn unprotected_contract_creation() {
+        let tx = LegacyTransaction {
+            chain_id: None,
+            nonce: 0,
+            gas_price: 1,
+            gas_limit: 53_000,
+            to: None,
+            value: U256::ZERO,
+            input: vec![0x60, 0x00],
+        };
+        let signature = signature();
+
+        assert_eq!(tx.v(&signature), 28);
+        assert_eq!(
+            tx.signing_hash(),
+            digest!("0xef49478fed85cec0448daca885a42c86adbd89dcf2092af1b428e2b3b6643146"),
+        );
+        assert_eq!(
+            tx.tx_hash(&signature),
+            digest!("0xe2cc5e1d6289bbcf744624807fc430aa08e3e62e401f1de30b98d092c6aa1b2f"),
+        );
+    }
+
+    #[test]
+    fn maximum_chain_id() {
+        let tx = LegacyTransaction {
+            chain_id: Some(u64::MAX),
+            ..Default::default()
+        };
+        let signature = signature();
+
+        assert_eq!(tx.v(&signature), (1 << 65) + 34);
+        let signed = SignedTransaction::decode(&tx.encode_signed(&signature)).unwrap();
+        assert_eq!(signed.transaction, tx.into());
+        assert_eq!(signed.signature, signature);
+    }
+
+    #[test]
+    fn chain_id_overflow() {
+        let mut encoded = Vec::new();
+        rlp::encode_fields(
+            &LegacyTransaction::default().fields(),
+            &[&((1_u128 << 65) + 35), &U256::ONE, &U256::ONE],
+            &mut encoded,
+        );
+        assert!(matches!(
+            SignedTransaction::decode(&encoded),
+            Err(tx::DecodeError::InvalidSignature),
+        ));
+    }
+}
@@ -0,0 +1,292 @@
+//! Ethereum transactions.
+//!
+//! This module provides types for each of the supported transaction types:
+//! - [`LegacyTransaction`]: pre-[EIP-2718] transactions, with optional
+//!   [EIP-155] replay protection
+//! - [`Eip2930Transaction`]: [EIP-2930] access list transactions
+//! - [`Eip1559Transaction`]: [EIP-1559] dynamic fee transactions
+//! - [`Eip4844Transaction`]: [EIP-4844] blob transactions
+//! - [`Eip7702Transaction`]: [EIP-7702] set code transactions
+//!
+//! Each transaction type can compute its signing hash, as well as its signed
+//! [EIP-2718] encoding and transaction hash given a [`Signature`]. Raw signed
+//! transactions can be decoded with [`SignedTransaction::decode`].
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::tx::{Eip1559Transaction, Transaction};
+//! # use ethprim::{address, uint, Signature};
+//! let tx = Transaction::from(Eip1559Transaction {
+//!     chain_id: 1,
+//!     nonce: 0,
+//!     max_priority_fee_per_gas: 1_000_000_000,
+//!     max_fee_per_gas: 30_000_000_000,
+//!     gas_limit: 21_000,
+//!     to: Some(address!("0x1111111111111111111111111111111111111111")),
+//!     value: uint!("1000000000000000000"),
+//!     ..Default::default()
+//! });
+//!
+//! let signature = Signature::new(uint!("1"), uint!("2"), false);
+//! let encoded = tx.encode_signed(&signature);
+//! assert_eq!(encoded[0], Eip1559Transaction::TYPE);
+//! assert_eq!(tx.tx_hash(&signature), ethprim::Digest::of(&encoded));
+//! ```
+//!
+//! [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+//! [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+//! [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+//! [EIP-2930]: https://eips.ethereum.org/EIPS/eip-2930
+//! [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+//! [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+
+mod access_list;
+mod authorization;
+mod eip1559;
+mod eip2930;
+mod eip4844;
+mod eip7702;
+mod legacy;
+mod signed;
+
+#[cfg(feature = "k256")]
+pub use self::signed::RecoverError;
+pub use self::{
+    access_list::{AccessList, AccessListItem},
+    authorization::{
+        delegated_address, delegation_designator, Authorization, SignedAuthorization,
+        DELEGATION_DESIGNATOR_PREFIX,
+    },
+    eip1559::Eip1559Transaction,
+    eip2930::Eip2930Transaction,
+    eip4844::Eip4844Transaction,
+    eip7702::Eip7702Transaction,
+    legacy::LegacyTransaction,
+    signed::{DecodeError, SignedTransaction},
+};
+use crate::{
+    gas::{self, IntrinsicGas},
+    rlp::{self, Encodable, Output},
+    Digest, Hasher, Signature,
+};
+
+/// A transaction hash.
+pub type TxHash = Digest;
+
+/// An Ethereum transaction of any of the supported types.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Transaction {
+    /// A legacy transaction.
+    Legacy(LegacyTransaction),
+    /// An EIP-2930 access list transaction.
+    Eip2930(Eip2930Transaction),
+    /// An EIP-1559 dynamic fee transaction.
+    Eip1559(Eip1559Transaction),
+    /// An EIP-4844 blob transaction.
+    Eip4844(Eip4844Transaction),
+    /// An EIP-7702 set code transaction.
+    Eip7702(Eip7702Transaction),
+}
+
+macro_rules! dispatch {
+    ($self:expr, $tx:ident => $e:expr) => {
+        match $self {
+            Self::Legacy($tx) => $e,
+            Self::Eip2930($tx) => $e,
+            Self::Eip1559($tx) => $e,
+            Self::Eip4844($tx) => $e,
+            Self::Eip7702($tx) => $e,
+        }
+    };
+}
+
+impl Transaction {
+    /// Returns the EIP-2718 transaction type. Legacy transactions have a type
+    /// of `0`.
+    pub fn tx_type(&self) -> u8 {
+        match self {
+            Self::Legacy(_) => 0,
+            Self::Eip2930(_) => Eip2930Transaction::TYPE,
+            Self::Eip1559(_) => Eip1559Transaction::TYPE,
+            Self::Eip4844(_) => Eip4844Transaction::TYPE,
+            Self::Eip7702(_) => Eip7702Transaction::TYPE,
+        }
+    }
+
+    /// Returns the chain ID of the transaction. This is only `None` for
+    /// legacy transactions without EIP-155 replay protection.
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            Self::Legacy(tx) => tx.chain_id,
+            Self::Eip2930(tx) => Some(tx.chain_id),
+            Self::Eip1559(tx) => Some(tx.chain_id),
+            Self::Eip4844(tx) => Some(tx.chain_id),
+            Self::Eip7702(tx) => Some(tx.chain_id),
+        }
+    }
+
+    /// Returns the intrinsic gas of the transaction.
+    pub fn intrinsic_gas(&self) -> IntrinsicGas {
+        let empty = AccessList::default();
+        let (input, create, access_list, authorizations) = match self {
+            Self::Legacy(tx) => (&tx.input, tx.to.is_none(), &empty, 0),
+            Self::Eip2930(tx) => (&tx.input, tx.to.is_none(), &tx.access_list, 0),
+            Self::Eip1559(tx) => (&tx.input, tx.to.is_none(), &tx.access_list, 0),
+            Self::Eip4844(tx) => (&tx.input, false, &tx.access_list, 0),
+            Self::Eip7702(tx) => (
+                &tx.input,
+                false,
+                &tx.access_list,
+                tx.authorization_list.len(),
+            ),
+        };
+        gas::intrinsic_gas(input, create, access_list, authorizations)
+    }
+
+    /// Returns the hash of the transaction that gets signed by the sender.
+    pub fn signing_hash(&self) -> Digest {
+        dispatch!(self, tx => tx.signing_hash())
+    }
+
+    /// Returns the EIP-2718 encoding of the signed transaction.
+    pub fn encode_signed(&self, signature: &Signature) -> Vec<u8> {
+        dispatch!(self, tx => tx.encode_signed(signature))
+    }
+
+    /// Returns the hash of the signed transaction.
+    pub fn tx_hash(&self, signature: &Signature) -> TxHash {
+        dispatch!(self, tx => tx.tx_hash(signature))
+    }
+}
+
+macro_rules! impl_from {
+    ($($variant:ident($tx:ty)),*) => {$(
+        impl From<$tx> for Transaction {
+            fn from(tx: $tx) -> Self {
+                Self::$variant(tx)
+            }
+        }
+    )*};
+}
+
+impl_from! {
+    Legacy(LegacyTransaction),
+    Eip2930(Eip2930Transaction),
+    Eip1559(Eip1559Transaction),
+    Eip4844(Eip4844Transaction),
+    Eip7702(Eip7702Transaction)
+}
+
+/// Implements the signing and hashing methods for an EIP-2718 typed
+/// transaction with the specified type byte. The transaction type must
+/// provide a private `fields` method returning its RLP encoded fields.
+macro_rules! impl_typed_transaction {
+    ($tx:ty, $type:expr) => {
+        impl $tx {
+            /// The EIP-2718 transaction type.
+            pub const TYPE: u8 = $type;
+
+            /// Returns the hash of the transaction that gets signed by the
+            /// sender, `keccak256(type || rlp([fields...]))`.
+            pub fn signing_hash(&self) -> $crate::Digest {
+                let mut hasher = $crate::Hasher::new();
+                $crate::tx::encode_typed(Self::TYPE, &self.fields(), &[], &mut hasher);
+                hasher.finalize()
+            }
+
+            /// Returns the EIP-2718 encoding of the signed transaction,
+            /// `type || rlp([fields..., y_parity, r, s])`.
+            pub fn encode_signed(&self, signature: &$crate::Signature) -> Vec<u8> {
+                let mut buffer = Vec::new();
+                $crate::tx::encode_typed(
+                    Self::TYPE,
+                    &self.fields(),
+                    &$crate::tx::signature_fields(signature),
+                    &mut buffer,
+                );
+                buffer
+            }
+
+            /// Returns the hash of the signed transaction.
+            pub fn tx_hash(&self, signature: &$crate::Signature) -> $crate::tx::TxHash {
+                let mut hasher = $crate::Hasher::new();
+                $crate::tx::encode_typed(
+                    Self::TYPE,
+                    &self.fields(),
+                    &$crate::tx::signature_fields(signature),
+                    &mut hasher,
+                );
+                hasher.finalize()
+            }
+        }
+    };
+}
+
+use impl_typed_transaction;
+
+/// Returns the RLP encoded fields of a signature for typed transactions.
+fn signature_fields(signature: &Signature) -> [&dyn Encodable; 3] {
+    [&signature.y_parity, &signature.r, &signature.s]
+}
+
+/// Encodes an EIP-2718 typed transaction envelope.
+fn encode_typed(
+    tx_type: u8,
+    fields: &[&dyn Encodable],
+    trailer: &[&dyn Encodable],
+    out: &mut dyn Output,
+) {
+    out.write(&[tx_type]);
+    rlp::encode_fields(fields, trailer, out);
+}
+
+/// Computes the Keccak-256 digest of RLP encoded fields.
+fn hash_fields(fields: &[&dyn Encodable], trailer: &[&dyn Encodable]) -> Digest {
+    let mut hasher = Hasher::new();
+    rlp::encode_fields(fields, trailer, &mut hasher);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, U256};
+
+    /// The signature used for the test transaction vectors.
+    pub(super) fn signature() -> Signature {
+        Signature::new(U256::new(0x1111) << 200, U256::new(0x2222) << 100, true)
+    }
+
+    #[test]
+    fn transaction_envelope() {
+        let txs = [
+            Transaction::from(LegacyTransaction {
+                chain_id: Some(5),
+                ..Default::default()
+            }),
+            Transaction::from(Eip2930Transaction::default()),
+            Transaction::from(Eip1559Transaction::default()),
+            Transaction::from(Eip4844Transaction::default()),
+            Transaction::from(Eip7702Transaction {
+                to: address!("0x1111111111111111111111111111111111111111"),
+                ..Default::default()
+            }),
+        ];
+        let signature = signature();
+        for (i, tx) in txs.iter().enumerate() {
+            let encoded = tx.encode_signed(&signature);
+            assert_eq!(tx.tx_type() as usize, i);
+            assert_eq!(tx.tx_hash(&signature), Digest::of(&encoded));
+            if i > 0 {
+                assert_eq!(encoded[0], tx.tx_type());
+                assert_eq!(tx.chain_id(), Some(0));
+            } else {
+                assert!(encoded[0] >= 0xc0);
+                assert_eq!(tx.chain_id(), Some(5));
+            }
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Signed transactions.
+
+use super::{
+    Eip1559Transaction, Eip2930Transaction, Eip4844Transaction, Eip7702Transaction,
+    LegacyTransaction, Transaction, TxHash,
+};
+#[cfg(feature = "k256")]
+use crate::Address;
+use crate::{
+    rlp::{self, Decodable},
+    Digest, Signature, U256,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// A transaction along with its signature.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SignedTransaction {
+    /// The signed transaction.
+    pub transaction: Transaction,
+    /// The transaction signature.
+    pub signature: Signature,
+}
+
+impl SignedTransaction {
+    /// Decodes a signed transaction from its raw EIP-2718 encoding, as returned
+    /// by `eth_getRawTransactionByHash` and accepted by
+    /// `eth_sendRawTransaction`.
+    ///
+    /// This verifies that the signature `y_parity` of typed transactions is
+    /// either `0` or `1`, and that the `v` value of legacy transactions is
+    /// either `27` or `28`, or encodes an EIP-155 chain ID.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethprim::tx::{Eip1559Transaction, SignedTransaction};
+    /// # use ethprim::{uint, Signature};
+    /// let tx = Eip1559Transaction {
+    ///     chain_id: 1,
+    ///     gas_limit: 21_000,
+    ///     ..Default::default()
+    /// };
+    /// let signature = Signature::new(uint!("1"), uint!("2"), true);
+    ///
+    /// let signed = SignedTransaction::decode(&tx.encode_signed(&signature)).unwrap();
+    /// assert_eq!(signed.transaction, tx.into());
+    /// assert_eq!(signed.signature, signature);
+    /// ```
+    pub fn decode(raw: &[u8]) -> Result<Self, DecodeError> {
+        let (&tx_type, payload) = raw
+            .split_first()
+            .ok_or(DecodeError::Rlp(rlp::DecodeError::UnexpectedEof))?;
+        let (transaction, signature) = match tx_type {
+            0xc0..=0xff => {
+                let (mut tx, v, r, s) =
+                    decode_envelope(raw, LegacyTransaction::decode_fields, u128::decode)?;
+                let y_parity;
+                (tx.chain_id, y_parity) = match v {
+                    27 | 28 => (None, v == 28),
+                    35.. => {
+                        let chain_id = u64::try_from((v - 35) / 2)
+                            .map_err(|_| DecodeError::InvalidSignature)?;
+                        (Some(chain_id), (v - 35) % 2 == 1)
+                    }
+                    _ => return Err(DecodeError::InvalidSignature),
+                };
+                (tx.into(), Signature::new(r, s, y_parity))
+            }
+            Eip2930Transaction::TYPE => decode_typed(payload, Eip2930Transaction::decode_fields)?,
+            Eip1559Transaction::TYPE => decode_typed(payload, Eip1559Transaction::decode_fields)?,
+            Eip4844Transaction::TYPE => decode_typed(payload, Eip4844Transaction::decode_fields)?,
+            Eip7702Transaction::TYPE => decode_typed(payload, Eip7702Transaction::decode_fields)?,
+            _ => return Err(DecodeError::UnsupportedType(tx_type)),
+        };
+
+        Ok(Self {
+            transaction,
+            signature,
+        })
+    }
+
+    /// Returns the EIP-2718 encoding of the signed transaction.
+    pub fn encode(&self) -> Vec<u8> {
+        self.transaction.encode_signed(&self.signature)
+    }
+
+    /// Returns the transaction hash.
+    pub fn tx_hash(&self) -> TxHash {
+        self.transaction.tx_hash(&self.signature)
+    }
+
+    /// Returns the hash of the transaction that was signed by the sender.
+    pub fn signing_hash(&self) -> Digest {
+        self.transaction.signing_hash()
+    }
+
+    /// Recovers the address of the transaction sender, verifying that the
+    /// transaction is valid for the specified chain ID.
+    ///
+    /// Note that legacy transactions without EIP-155 replay protection are
+    /// valid on all chains.
+    #[cfg(feature = "k256")]
+    pub fn recover_sender(&self, chain_id: u64) -> Result<Address, RecoverError> {
+        if !matches!(self.transaction.chain_id(), Some(id) if id == chain_id)
+            && !matches!(&self.transaction, Transaction::Legacy(tx) if tx.chain_id.is_none())
+        {
+            return Err(RecoverError::ChainIdMismatch);
+        }
+        self.signature
+            .recover_address(self.signing_hash())
+            .map_err(|_| RecoverError::InvalidSignature)
+    }
+}
+
+/// Decodes an RLP list of transaction fields followed by a signature, where
+/// the signature's `v` value is decoded with the specified function.
+fn decode_envelope<T, V>(
+    data: &[u8],
+    decode: fn(&mut &[u8]) -> Result<T, rlp::DecodeError>,
+    decode_v: fn(&mut &[u8]) -> Result<V, rlp::DecodeError>,
+) -> Result<(T, V, U256, U256), DecodeError> {
+    let mut buf = data;
+    let result = rlp::decode_fields(&mut buf, |fields| -> Result<_, rlp::DecodeError> {
+        Ok((
+            decode(fields)?,
+            decode_v(fields)?,
+            Decodable::decode(fields)?,
+            Decodable::decode(fields)?,
+        ))
+    })?;
+    if !buf.is_empty() {
+        return Err(DecodeError::Rlp(rlp::DecodeError::TrailingBytes));
+    }
+    Ok(result)
+}
+
+/// Decodes the payload of a typed transaction envelope.
+fn decode_typed<T>(
+    payload: &[u8],
+    decode: fn(&mut &[u8]) -> Result<T, rlp::DecodeError>,
+) -> Result<(Transaction, Signature), DecodeError>
+where
+    T: Into<Transaction>,
+{
+    let (tx, y_parity, r, s) = decode_envelope(payload, decode, u64::decode)?;
+    let y_parity = match y_parity {
+        0 => false,
+        1 => true,
+        _ => return Err(DecodeError::InvalidSignature),
+    };
+    Ok((tx.into(), Signature::new(r, s, y_parity)))
+}
+
+/// Represents an error decoding a raw transaction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The transaction is not correctly RLP encoded.
+    Rlp(rlp::DecodeError),
+    /// The EIP-2718 transaction type is not supported.
+    UnsupportedType(u8),
+    /// The signature `v` or `y_parity` value is invalid.
+    InvalidSignature,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Rlp(err) => write!(f, "invalid transaction encoding: {err}"),
+            Self::UnsupportedType(tx_type) => {
+                write!(f, "unsupported transaction type {tx_type:#04x}")
+            }
+            Self::InvalidSignature => write!(f, "invalid transaction signature"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<rlp::DecodeError> for DecodeError {
+    fn from(err: rlp::DecodeError) -> Self {
+        Self::Rlp(err)
+    }
+}
+
+/// Represents an error recovering a transaction sender.
+#[cfg(feature = "k256")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecoverError {
+    /// The transaction is not valid for the expected chain ID.
+    ChainIdMismatch,
+    /// The signature is invalid.
+    InvalidSignature,
+}
+
+#[cfg(feature = "k256")]
+impl Display for RecoverError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::ChainIdMismatch => write!(f, "transaction chain ID mismatch"),
+            Self::InvalidSignature => write!(f, "invalid transaction signature"),
+        }
+    }
+}
+
+#[cfg(feature = "k256")]
+impl std::error::Error for RecoverError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, tests::hex, uint};
+
+    #[test]
+    fn decode_eip155_transaction() {
+        let raw = hex("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83");
+        let signed = SignedTransaction::decode(&raw).unwrap();
+
+        assert_eq!(
+            signed.transaction,
+            Transaction::Legacy(LegacyTransaction {
+                chain_id: Some(1),
+                nonce: 9,
+                gas_price: 20_000_000_000,
+                gas_limit: 21_000,
+                to: Some(address!("0x3535353535353535353535353535353535353535")),
+                value: uint!("1000000000000000000"),
+                input: vec![],
+            }),
+        );
+        assert!(!signed.signature.y_parity);
+        assert_eq!(signed.encode(), raw);
+    }
+
+    #[test]
+    fn roundtrip_typed_transactions() {
+        let signature = crate::tx::tests::signature();
+        for tx in [
+            Transaction::from(LegacyTransaction::default()),
+            Transaction::from(Eip2930Transaction::default()),
+            Transaction::from(Eip1559Transaction {
+                input: vec![1, 2, 3],
+                ..Default::default()
+            }),
+            Transaction::from(Eip4844Transaction::default()),
+            Transaction::from(Eip7702Transaction::default()),
+        ] {
+            let raw = tx.encode_signed(&signature);
+            let signed = SignedTransaction::decode(&raw).unwrap();
+            assert_eq!(signed.transaction, tx);
+            assert_eq!(signed.signature, signature);
+            assert_eq!(signed.tx_hash(), Digest::of(&raw));
+        }
+    }
+
+    #[test]
+    fn decode_invalid_transactions() {
+        let raw = Eip1559Transaction::default().encode_signed(&Signature::default());
+        let with_y_parity = |y_parity: u8| {
+            let mut raw = raw.clone();
+            let i = raw.len() - 3;
+            raw[i] = y_parity;
+            raw
+        };
+
+        assert_eq!(
+            SignedTransaction::decode(&with_y_parity(0x02)).unwrap_err(),
+            DecodeError::InvalidSignature,
+        );
+        assert_eq!(
+            SignedTransaction::decode(&[0x05, 0xc0]).unwrap_err(),
+            DecodeError::UnsupportedType(0x05),
+        );
+        assert_eq!(
+            SignedTransaction::decode(&[raw.clone(), vec![0x00]].concat()).unwrap_err(),
+            DecodeError::Rlp(rlp::DecodeError::TrailingBytes),
+        );
+        assert_eq!(
+            SignedTransaction::decode(&[]).unwrap_err(),
+            DecodeError::Rlp(rlp::DecodeError::UnexpectedEof),
+        );
+
+        let mut legacy = LegacyTransaction::default().encode_signed(&Signature::default());
+        let i = legacy.len() - 3;
+        legacy[i] = 29;
+        assert_eq!(
+            SignedTransaction::decode(&legacy).unwrap_err(),
+            DecodeError::InvalidSignature,
+        );
+    }
+
+    #[cfg(feature = "k256")]
+    #[test]
+    fn recover_eip155_sender() {
+        let raw = hex("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83");
+        let signed = SignedTransaction::decode(&raw).unwrap();
+
+        assert_eq!(
+            signed.recover_sender(1).unwrap(),
+            address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"),
+        );
+        assert_eq!(
+            signed.recover_sender(5).unwrap_err(),
+            RecoverError::ChainIdMismatch,
+        );
+
+        // EIP-2 forbids malleable signatures with high `s` values.
+        let order = uint!("0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        let malleable = SignedTransaction {
+            signature: Signature::new(
+                signed.signature.r,
+                order - signed.signature.s,
+                !signed.signature.y_parity,
+            ),
+            ..signed
+        };
+        assert_eq!(
+            malleable.recover_sender(1).unwrap_err(),
+            RecoverError::InvalidSignature,
+        );
+    }
+}
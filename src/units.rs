@@ -0,0 +1,600 @@
+//! Ether and token unit conversion.
+//!
+//! This module converts between integer amounts and their decimal
+//! representation in a unit with a fixed number of decimals, such as ether
+//! amounts in wei or ERC-20 token amounts in their smallest denomination.
+//!
+//! Parsing is exact: amounts with more fractional digits than the unit's
+//! decimals are rejected instead of being rounded. Formatting is also exact by
+//! default, and only rounds when a precision is specified with
+//! [`FormatOptions`], using the specified [`Rounding`] mode.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! # use ethprim::{uint, units::{self, Unit}};
+//! let amount = units::parse_units("1.5", Unit::Ether).unwrap();
+//! assert_eq!(amount, uint!("1500000000000000000"));
+//! assert_eq!(units::format_units(amount, Unit::Ether), "1.5");
+//!
+//! // Token amounts with arbitrary decimals.
+//! let usdc = units::parse_units("1234.56", 6).unwrap();
+//! assert_eq!(usdc, uint!("1234560000"));
+//! ```
+
+use crate::{I256, U256};
+use std::fmt::{self, Display, Formatter, Write as _};
+
+/// An Ethereum denomination.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Unit {
+    /// Wei, the smallest denomination of ether.
+    Wei,
+    /// Kwei, 10³ wei.
+    Kwei,
+    /// Mwei, 10⁶ wei.
+    Mwei,
+    /// Gwei, 10⁹ wei. This is the unit commonly used for gas prices.
+    Gwei,
+    /// Szabo, 10¹² wei.
+    Szabo,
+    /// Finney, 10¹⁵ wei.
+    Finney,
+    /// Ether, 10¹⁸ wei.
+    Ether,
+}
+
+impl Unit {
+    /// Returns the number of decimals of the unit relative to wei.
+    pub const fn decimals(self) -> u8 {
+        match self {
+            Self::Wei => 0,
+            Self::Kwei => 3,
+            Self::Mwei => 6,
+            Self::Gwei => 9,
+            Self::Szabo => 12,
+            Self::Finney => 15,
+            Self::Ether => 18,
+        }
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Wei => "wei",
+            Self::Kwei => "kwei",
+            Self::Mwei => "mwei",
+            Self::Gwei => "gwei",
+            Self::Szabo => "szabo",
+            Self::Finney => "finney",
+            Self::Ether => "ether",
+        })
+    }
+}
+
+impl From<Unit> for u8 {
+    fn from(unit: Unit) -> Self {
+        unit.decimals()
+    }
+}
+
+/// The rounding mode used when formatting amounts with a fixed precision.
+///
+/// Rounding is applied to the magnitude of signed amounts, so that negative
+/// amounts are rounded symmetrically to positive ones.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Rounding {
+    /// Round towards zero, truncating the discarded digits.
+    #[default]
+    Down,
+    /// Round away from zero if any of the discarded digits are non-zero.
+    Up,
+    /// Round to the nearest value, with ties rounding away from zero.
+    HalfUp,
+}
+
+/// Options for formatting amounts.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FormatOptions {
+    /// The number of fractional digits to format. When unset, amounts are
+    /// formatted exactly with trailing fractional zeros removed.
+    pub precision: Option<usize>,
+    /// The rounding mode used when the precision discards non-zero digits.
+    pub rounding: Rounding,
+    /// The separator inserted between groups of three integer digits.
+    pub thousands_separator: Option<char>,
+}
+
+/// Parses a decimal amount into an integer amount with the specified number
+/// of decimals.
+///
+/// Amounts are made up of ASCII digits with an optional fractional part, and
+/// may not have more significant fractional digits than the number of
+/// decimals.
+pub fn parse_units(s: &str, decimals: impl Into<u8>) -> Result<U256, ParseUnitsError> {
+    parse_magnitude(s, decimals.into(), 0)
+}
+
+/// Parses a signed decimal amount into an integer amount with the specified
+/// number of decimals. Amounts may be prefixed with a `-` or `+` sign.
+pub fn parse_units_signed(s: &str, decimals: impl Into<u8>) -> Result<I256, ParseUnitsError> {
+    let (negative, digits) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let magnitude = parse_magnitude(digits, decimals.into(), s.len() - digits.len())?;
+    if negative {
+        if magnitude > I256::MIN.unsigned_abs() {
+            return Err(ParseUnitsError::Overflow);
+        }
+        Ok(magnitude.as_i256().wrapping_neg())
+    } else {
+        if magnitude > I256::MAX.as_u256() {
+            return Err(ParseUnitsError::Overflow);
+        }
+        Ok(magnitude.as_i256())
+    }
+}
+
+/// Formats an integer amount with the specified number of decimals exactly,
+/// removing trailing fractional zeros.
+pub fn format_units(value: U256, decimals: impl Into<u8>) -> String {
+    format_units_with(value, decimals, &FormatOptions::default())
+}
+
+/// Formats an integer amount with the specified number of decimals and
+/// formatting options.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{uint, units::{self, FormatOptions, Rounding, Unit}};
+/// let options = FormatOptions {
+///     precision: Some(2),
+///     rounding: Rounding::HalfUp,
+///     thousands_separator: Some(','),
+/// };
+/// assert_eq!(
+///     units::format_units_with(uint!("1234567890000000000000"), Unit::Ether, &options),
+///     "1,234.57",
+/// );
+/// ```
+pub fn format_units_with(value: U256, decimals: impl Into<u8>, options: &FormatOptions) -> String {
+    format_magnitude(false, value, decimals.into(), options)
+}
+
+/// Formats a signed integer amount with the specified number of decimals
+/// exactly, removing trailing fractional zeros.
+pub fn format_units_signed(value: I256, decimals: impl Into<u8>) -> String {
+    format_units_signed_with(value, decimals, &FormatOptions::default())
+}
+
+/// Formats a signed integer amount with the specified number of decimals and
+/// formatting options. Amounts that round to zero are formatted without a
+/// sign.
+pub fn format_units_signed_with(
+    value: I256,
+    decimals: impl Into<u8>,
+    options: &FormatOptions,
+) -> String {
+    format_magnitude(value < 0, value.unsigned_abs(), decimals.into(), options)
+}
+
+/// Parses the magnitude of a decimal amount. The offset is the position of
+/// the amount in the original string, used for reporting invalid digits.
+fn parse_magnitude(s: &str, decimals: u8, offset: usize) -> Result<U256, ParseUnitsError> {
+    let (integer, fraction) = match s.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (s, None),
+    };
+    if integer.is_empty() || fraction == Some("") {
+        return Err(ParseUnitsError::InvalidFormat);
+    }
+    let fraction = fraction.unwrap_or_default();
+
+    let check = |digits: &str, start: usize| {
+        let invalid = digits.char_indices().find(|(_, c)| !c.is_ascii_digit());
+        match invalid {
+            Some((index, c)) => Err(ParseUnitsError::InvalidDigit {
+                c,
+                index: offset + start + index,
+            }),
+            None => Ok(()),
+        }
+    };
+    check(integer, 0)?;
+    check(fraction, integer.len() + 1)?;
+
+    let decimals = usize::from(decimals);
+    if fraction.len() > decimals && fraction[decimals..].bytes().any(|b| b != b'0') {
+        return Err(ParseUnitsError::Precision);
+    }
+
+    let fraction = fraction.bytes().chain(std::iter::repeat(b'0'));
+    integer
+        .bytes()
+        .chain(fraction.take(decimals))
+        .try_fold(U256::ZERO, |value, digit| {
+            value
+                .checked_mul(U256::new(10))?
+                .checked_add(U256::from(digit - b'0'))
+        })
+        .ok_or(ParseUnitsError::Overflow)
+}
+
+/// Formats the magnitude of an amount with an optional negative sign.
+fn format_magnitude(negative: bool, value: U256, decimals: u8, options: &FormatOptions) -> String {
+    // Left pad the digits so that there is always at least one integer digit.
+    let decimals = usize::from(decimals);
+    let mut digits = value.to_string().into_bytes();
+    if digits.len() <= decimals {
+        let padding = decimals + 1 - digits.len();
+        digits.splice(0..0, std::iter::repeat_n(b'0', padding));
+    }
+    let mut point = digits.len() - decimals;
+
+    match options.precision {
+        Some(precision) if precision < decimals => {
+            let discarded = digits.split_off(point + precision);
+            let round_up = match options.rounding {
+                Rounding::Down => false,
+                Rounding::Up => discarded.iter().any(|&d| d != b'0'),
+                Rounding::HalfUp => discarded[0] >= b'5',
+            };
+            if round_up && increment(&mut digits) {
+                point += 1;
+            }
+        }
+        Some(precision) => {
+            digits.resize(point + precision, b'0');
+        }
+        None => {
+            while digits.len() > point && digits.last() == Some(&b'0') {
+                digits.pop();
+            }
+        }
+    }
+
+    let (integer, fraction) = digits.split_at(point);
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3 + 2);
+    if negative && digits.iter().any(|&d| d != b'0') {
+        result.push('-');
+    }
+    for (i, &digit) in integer.iter().enumerate() {
+        if let Some(separator) = options.thousands_separator {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                result.push(separator);
+            }
+        }
+        result.push(char::from(digit));
+    }
+    if !fraction.is_empty() {
+        result.push('.');
+        result.extend(fraction.iter().copied().map(char::from));
+    }
+    result
+}
+
+/// Increments decimal digits in place, returning whether or not a new leading
+/// digit was added.
+fn increment(digits: &mut Vec<u8>) -> bool {
+    for digit in digits.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            return false;
+        }
+    }
+    digits.insert(0, b'1');
+    true
+}
+
+/// An amount of ether in wei.
+///
+/// Amounts are displayed in ether, honouring the formatter's precision with
+/// [`Rounding::HalfUp`] rounding. Use [`Wei::display`] to display the amount
+/// in other units.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethprim::{uint, units::{Unit, Wei}};
+/// let amount = Wei(uint!("1234500000000000000"));
+/// assert_eq!(amount.to_string(), "1.2345 ether");
+/// assert_eq!(format!("{amount:.2}"), "1.23 ether");
+/// assert_eq!(amount.display(Unit::Gwei).to_string(), "1234500000 gwei");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Wei(pub U256);
+
+impl Wei {
+    /// Parses a decimal amount in the specified unit.
+    pub fn parse(s: &str, unit: Unit) -> Result<Self, ParseUnitsError> {
+        parse_units(s, unit).map(Self)
+    }
+
+    /// Returns a value that displays the amount in the specified unit.
+    pub fn display(&self, unit: Unit) -> DisplayUnits {
+        DisplayUnits {
+            value: self.0,
+            unit,
+        }
+    }
+}
+
+impl Display for Wei {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.display(Unit::Ether), f)
+    }
+}
+
+impl From<U256> for Wei {
+    fn from(value: U256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Wei> for U256 {
+    fn from(value: Wei) -> Self {
+        value.0
+    }
+}
+
+/// Displays an amount of wei in a specific unit.
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayUnits {
+    value: U256,
+    unit: Unit,
+}
+
+impl Display for DisplayUnits {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let options = FormatOptions {
+            precision: f.precision(),
+            rounding: Rounding::HalfUp,
+            thousands_separator: None,
+        };
+        let amount = format!(
+            "{} {}",
+            format_units_with(self.value, self.unit, &options),
+            self.unit,
+        );
+
+        // Note that `Formatter::pad` can't be used, since it would truncate
+        // the string to the precision, which is used for decimals instead.
+        let padding = f
+            .width()
+            .unwrap_or(0)
+            .saturating_sub(amount.chars().count());
+        let (before, after) = match f.align() {
+            None | Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        f.write_str(&amount)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents an error parsing a decimal amount.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseUnitsError {
+    /// The amount is empty, or has an empty integer or fractional part.
+    InvalidFormat,
+    /// An invalid character was found.
+    InvalidDigit { c: char, index: usize },
+    /// The amount has more significant fractional digits than decimals.
+    Precision,
+    /// The amount does not fit in 256 bits.
+    Overflow,
+}
+
+impl Display for ParseUnitsError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => write!(f, "invalid decimal amount"),
+            Self::InvalidDigit { c, index } => {
+                write!(f, "invalid character {c:?} at position {index}")
+            }
+            Self::Precision => write!(f, "amount has too many fractional digits"),
+            Self::Overflow => write!(f, "amount overflows 256 bits"),
+        }
+    }
+}
+
+impl std::error::Error for ParseUnitsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{int, uint};
+
+    #[test]
+    fn parse_amounts() {
+        for (s, decimals, value) in [
+            ("1", 18, uint!("1000000000000000000")),
+            ("1.5", 18, uint!("1500000000000000000")),
+            ("0.000000000000000001", 18, uint!("1")),
+            ("30", 9, uint!("30000000000")),
+            ("1.10", 1, uint!("11")),
+            ("007", 0, uint!("7")),
+            ("0", 100, uint!("0")),
+        ] {
+            assert_eq!(parse_units(s, decimals).unwrap(), value);
+        }
+        assert_eq!(
+            parse_units(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                0
+            )
+            .unwrap(),
+            U256::MAX,
+        );
+    }
+
+    #[test]
+    fn parse_invalid_amounts() {
+        for (s, decimals, err) in [
+            ("", 18, ParseUnitsError::InvalidFormat),
+            (".5", 18, ParseUnitsError::InvalidFormat),
+            ("1.", 18, ParseUnitsError::InvalidFormat),
+            (
+                "1.2.3",
+                18,
+                ParseUnitsError::InvalidDigit { c: '.', index: 3 },
+            ),
+            (
+                "1,000",
+                18,
+                ParseUnitsError::InvalidDigit { c: ',', index: 1 },
+            ),
+            ("-1", 18, ParseUnitsError::InvalidDigit { c: '-', index: 0 }),
+            ("1.05", 1, ParseUnitsError::Precision),
+            ("0.0000000001", 9, ParseUnitsError::Precision),
+            ("1", 78, ParseUnitsError::Overflow),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639936",
+                0,
+                ParseUnitsError::Overflow,
+            ),
+        ] {
+            assert_eq!(parse_units(s, decimals), Err(err), "{s}");
+        }
+    }
+
+    #[test]
+    fn parse_signed_amounts() {
+        for (s, value) in [
+            ("-1.5", int!("-1500")),
+            ("+1.5", int!("1500")),
+            ("2", int!("2000")),
+            ("-0", int!("0")),
+        ] {
+            assert_eq!(parse_units_signed(s, 3).unwrap(), value);
+        }
+        assert_eq!(
+            parse_units_signed(&I256::MIN.to_string(), 0).unwrap(),
+            I256::MIN
+        );
+        assert_eq!(
+            parse_units_signed(&I256::MAX.to_string(), 0).unwrap(),
+            I256::MAX
+        );
+        assert_eq!(
+            parse_units_signed(&format!("+{}", I256::MIN.unsigned_abs()), 0),
+            Err(ParseUnitsError::Overflow)
+        );
+        assert_eq!(
+            parse_units_signed("-1.x", 3),
+            Err(ParseUnitsError::InvalidDigit { c: 'x', index: 3 })
+        );
+        assert_eq!(
+            parse_units_signed("-", 3),
+            Err(ParseUnitsError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn format_amounts() {
+        for (value, decimals, s) in [
+            (uint!("1000000000000000000"), 18, "1"),
+            (uint!("1500000000000000000"), 18, "1.5"),
+            (uint!("1"), 18, "0.000000000000000001"),
+            (uint!("0"), 18, "0"),
+            (uint!("1234"), 0, "1234"),
+        ] {
+            assert_eq!(format_units(value, decimals), s);
+        }
+        assert_eq!(
+            format_units(uint!("1"), 80),
+            format!("0.{}1", "0".repeat(79))
+        );
+        assert_eq!(format_units_signed(int!("-1500000000"), Unit::Gwei), "-1.5");
+        assert_eq!(format_units_signed(I256::MIN, 0), I256::MIN.to_string());
+    }
+
+    #[test]
+    fn format_with_rounding() {
+        let format = |value, precision, rounding| {
+            let options = FormatOptions {
+                precision: Some(precision),
+                rounding,
+                ..Default::default()
+            };
+            format_units_signed_with(value, 3, &options)
+        };
+        for (value, precision, down, up, half_up) in [
+            (int!("1234"), 2, "1.23", "1.24", "1.23"),
+            (int!("1235"), 2, "1.23", "1.24", "1.24"),
+            (int!("-1235"), 2, "-1.23", "-1.24", "-1.24"),
+            (int!("1230"), 2, "1.23", "1.23", "1.23"),
+            (int!("9999"), 1, "9.9", "10.0", "10.0"),
+            (int!("9999"), 0, "9", "10", "10"),
+            (int!("1500"), 5, "1.50000", "1.50000", "1.50000"),
+            (int!("-4"), 2, "0.00", "-0.01", "0.00"),
+            (int!("-5"), 2, "0.00", "-0.01", "-0.01"),
+        ] {
+            assert_eq!(format(value, precision, Rounding::Down), down);
+            assert_eq!(format(value, precision, Rounding::Up), up);
+            assert_eq!(format(value, precision, Rounding::HalfUp), half_up);
+        }
+    }
+
+    #[test]
+    fn format_with_thousands_separator() {
+        let options = FormatOptions {
+            thousands_separator: Some('_'),
+            ..Default::default()
+        };
+        for (value, s) in [
+            (uint!("1"), "0.001"),
+            (uint!("123000"), "123"),
+            (uint!("1234000"), "1_234"),
+            (uint!("123456789012"), "123_456_789.012"),
+        ] {
+            assert_eq!(format_units_with(value, 3, &options), s);
+        }
+        assert_eq!(
+            format_units_signed_with(int!("-1234567"), 0, &options),
+            "-1_234_567"
+        );
+    }
+
+    #[test]
+    fn display_wei() {
+        let amount = Wei(uint!("1999999999999999999"));
+        assert_eq!(amount.to_string(), "1.999999999999999999 ether");
+        assert_eq!(format!("{amount:.3}"), "2.000 ether");
+        assert_eq!(
+            format!("{:.0}", amount.display(Unit::Gwei)),
+            "2000000000 gwei"
+        );
+        assert_eq!(Wei::default().to_string(), "0 ether");
+        assert_eq!(format!("{amount:>14.3}"), "   2.000 ether");
+        assert_eq!(format!("{amount:*<12.1}"), "2.0 ether***");
+        assert_eq!(format!("{amount:^12.0}"), "  2 ether   ");
+        assert_eq!(
+            Wei::parse("30", Unit::Gwei)
+                .unwrap()
+                .display(Unit::Gwei)
+                .to_string(),
+            "30 gwei"
+        );
+    }
+}